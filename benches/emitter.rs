@@ -0,0 +1,259 @@
+//! Compare the String and the streaming emitters on a large calendar, and the String
+//! concatenating emitter which they replaced.
+//!
+//! Besides the timings, the number of allocations made by each emitter is printed.
+
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate ical;
+
+use criterion::{black_box, Criterion};
+use ical::generator::{Emitter, IcalCalendar, IcalCalendarBuilder, IcalEventBuilder};
+use ical::property::{Format, Property};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, BufWriter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Count the allocations made by the process.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// A copy of the emitter building each line by concatenating `String`s, before the
+/// `write_to` one, kept as the baseline of the benchmark.
+mod baseline {
+    use ical::generator::{IcalCalendar, IcalEvent, Property};
+    use ical::parser::ical::component::{
+        IcalAlarm, IcalFreeBusy, IcalJournal, IcalTimeZone, IcalTimeZoneTransition,
+        IcalTimeZoneTransitionType, IcalTodo,
+    };
+
+    pub trait Emitter {
+        fn generate(&self) -> String;
+    }
+
+    fn get_value(value: &Option<String>) -> String {
+        let value = value.as_deref().unwrap_or_default();
+        if !value.contains(['\r', '\n']) {
+            return String::from(":") + value;
+        }
+
+        String::from(":") + &value.replace("\r\n", "\\n").replace(['\r', '\n'], "\\n")
+    }
+
+    fn split_line(str: String) -> String {
+        let width = 75;
+        if str.len() <= width {
+            return str;
+        }
+
+        let mut folded = String::with_capacity(str.len() + str.len() / width * 3);
+        let mut line_len = 0;
+        let mut limit = width;
+
+        for c in str.chars() {
+            if line_len + c.len_utf8() > limit && line_len > 0 {
+                folded.push_str("\r\n ");
+                line_len = 0;
+                limit = width - 1;
+            }
+            folded.push(c);
+            line_len += c.len_utf8();
+        }
+
+        folded
+    }
+
+    fn protect_params(param: &String) -> String {
+        let str = param.as_str();
+        let len = str.len() - 1;
+        let in_quotes = len > 1 && &str[0..1] == "\"" && &str[len..] == "\"";
+
+        let to_escape: Vec<(usize, char)> = str
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| {
+                c == &'\"'
+                    || c == &'\n'
+                    || !in_quotes && (c == &';' || c == &':' || c == &',' || c == &'\\')
+            })
+            .collect();
+        let mut ret = param.to_string();
+        for (pos, ch) in to_escape.iter().rev() {
+            let pos = *pos;
+            if ch == &'\n' {
+                ret.replace_range(pos..pos + 1, "\\n");
+            } else if pos > 0 && pos < len && &str[pos - 1..pos] != "\\" {
+                ret.insert(pos, '\\');
+            }
+        }
+        ret + ","
+    }
+
+    fn get_params(params: &Option<Vec<(String, Vec<String>)>>) -> String {
+        match params {
+            None => String::new(),
+            Some(vec) => vec
+                .iter()
+                .map(|(name, values)| {
+                    let mut value = values.iter().map(protect_params).collect::<String>();
+                    value.pop();
+                    format!(";{}={}", name, value)
+                })
+                .collect::<String>(),
+        }
+    }
+
+    impl Emitter for Property {
+        fn generate(&self) -> String {
+            split_line(self.name.clone() + &get_params(&self.params) + &get_value(&self.value))
+                + "\r\n"
+        }
+    }
+
+    impl Emitter for IcalTimeZoneTransition {
+        fn generate(&self) -> String {
+            let key = match &self.transition {
+                IcalTimeZoneTransitionType::STANDARD => "STANDARD",
+                IcalTimeZoneTransitionType::DAYLIGHT => "DAYLIGHT",
+            };
+            String::from("BEGIN:")
+                + key
+                + "\r\n"
+                + &self
+                    .properties
+                    .iter()
+                    .map(Emitter::generate)
+                    .collect::<String>()
+                + "END:"
+                + key
+                + "\r\n"
+        }
+    }
+
+    macro_rules! generate_emitter {
+        ($struct:ident, $key:literal, $($prop:ident),+) => {
+            impl Emitter for $struct {
+                fn generate(&self) -> String {
+                    let mut text = String::from("BEGIN:") + $key + "\r\n";
+                    $(text += &self.$prop
+                    .iter()
+                    .map(Emitter::generate)
+                    .collect::<String>();)+
+
+                    text + "END:" + $key + "\r\n"
+                }
+            }
+        };
+    }
+
+    generate_emitter!(IcalAlarm, "VALARM", properties);
+    generate_emitter!(IcalFreeBusy, "VFREEBUSY", properties);
+    generate_emitter!(IcalJournal, "VJOURNAL", properties);
+    generate_emitter!(IcalEvent, "VEVENT", properties, alarms);
+    generate_emitter!(IcalTodo, "VTODO", properties, alarms);
+    generate_emitter!(IcalTimeZone, "VTIMEZONE", properties, transitions);
+    generate_emitter!(
+        IcalCalendar,
+        "VCALENDAR",
+        properties,
+        timezones,
+        events,
+        alarms,
+        todos,
+        journals,
+        free_busys
+    );
+}
+
+fn calendar(events: usize) -> IcalCalendar {
+    let mut calendar = IcalCalendarBuilder::version("2.0")
+        .gregorian()
+        .prodid("-//ical-rs//bench//EN")
+        .build();
+
+    for i in 0..events {
+        let event = IcalEventBuilder::tzid("Europe/Berlin")
+            .uid(format!("{}@bench.ical-rs", i))
+            .changed_utc("20201201T120423Z")
+            .start("20201206T170000")
+            .duration("PT2H45M0S")
+            .set(Property::from_text(
+                "SUMMARY",
+                "Weekly meeting, room 3",
+                Format::Ical,
+            ))
+            .set(Property::from_text(
+                "DESCRIPTION",
+                &"A long description which needs to be folded on several lines. ".repeat(4),
+                Format::Ical,
+            ))
+            .set(ical_property!(
+                "ATTENDEE",
+                "mailto:someone@example.com",
+                ical_param!("CN", "Someone"),
+                ical_param!("ROLE", "REQ-PARTICIPANT")
+            ))
+            .build();
+        calendar.events.push(event);
+    }
+
+    calendar
+}
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn emitter(c: &mut Criterion) {
+    let calendar = calendar(10_000);
+
+    // The baseline gives the same output, the calendar having no availability.
+    assert_eq!(baseline::Emitter::generate(&calendar), calendar.generate());
+
+    println!(
+        "allocations for 10k events: baseline {}, generate {}, write_to {}",
+        allocations(|| {
+            black_box(baseline::Emitter::generate(&calendar));
+        }),
+        allocations(|| {
+            black_box(calendar.generate());
+        }),
+        allocations(|| calendar.write_to(&mut io::sink()).unwrap()),
+    );
+
+    c.bench_function("baseline generate 10k events", |b| {
+        b.iter(|| black_box(baseline::Emitter::generate(&calendar)))
+    });
+    c.bench_function("generate 10k events", |b| {
+        b.iter(|| black_box(calendar.generate()))
+    });
+    c.bench_function("write_to 10k events", |b| {
+        b.iter(|| calendar.write_to(&mut BufWriter::new(io::sink())).unwrap())
+    });
+}
+
+criterion_group!(benches, emitter);
+criterion_main!(benches);
@@ -9,6 +9,6 @@ fn main() {
     let reader = ical::LineReader::new(buf);
 
     for line in reader {
-        println!("{:?}", line);
+        println!("{:?}", line.unwrap());
     }
 }
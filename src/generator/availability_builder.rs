@@ -0,0 +1,177 @@
+use parser::ical::component::{IcalAvailability, IcalAvailable};
+use property::Property;
+use {ical_param, ical_property};
+
+pub struct IcalAvailabilityBuilder {
+    tzid: String,
+    availability: IcalAvailability,
+}
+pub struct Uid(IcalAvailabilityBuilder);
+pub struct DtStamp(IcalAvailabilityBuilder);
+pub struct Finalizer(IcalAvailabilityBuilder);
+
+/// Builds a new [RFC 7953 - VAVAILABILITY Component](https://tools.ietf.org/html/rfc7953#section-3.1)
+///
+/// ```
+/// # use ical::generator::*;
+/// #
+/// let office_hours = IcalAvailableBuilder::tzid("Europe/Berlin")
+///     .uid("office-hours@example.com")
+///     .changed_utc("20240105T090000Z")
+///     .start("20240108T090000")
+///     .end("20240108T170000")
+///     .repeat_rule(Recur::new(Frequency::Weekly))
+///     .unwrap()
+///     .build();
+///
+/// let availability = IcalAvailabilityBuilder::tzid("Europe/Berlin")
+///     .uid("availability@example.com")
+///     .changed_utc("20240105T090000Z")
+///     .start("20240101T000000")
+///     .priority(1)
+///     .add_available(office_hours)
+///     .build();
+/// ```
+impl IcalAvailabilityBuilder {
+    /// Starts an availability whose times are local to `timezone`.
+    pub fn tzid<S: Into<String>>(timezone: S) -> Uid {
+        Uid(Self {
+            tzid: timezone.into(),
+            availability: IcalAvailability::new(),
+        })
+    }
+}
+
+impl Uid {
+    /// Sets the `UID` of the availability.
+    pub fn uid<S: Into<String>>(mut self, uid: S) -> DtStamp {
+        self.0
+            .availability
+            .properties
+            .push(ical_property!("UID", uid));
+        DtStamp(self.0)
+    }
+}
+
+impl DtStamp {
+    /// Sets the `DTSTAMP` of the availability. Signals the date of the last change in UTC.
+    pub fn changed_utc<S: Into<String>>(mut self, dtstamp: S) -> Finalizer {
+        self.0
+            .availability
+            .properties
+            .push(ical_property!("DTSTAMP", dtstamp.into()));
+        Finalizer(self.0)
+    }
+}
+
+impl Finalizer {
+    pub fn build(self) -> IcalAvailability {
+        self.0.availability
+    }
+
+    /// Sets the `DTSTART` of the time covered by the availability, which is unbounded
+    /// without it.
+    ///
+    /// Needs a timestamp (YYYYMMDDTHHmmSS)
+    pub fn start<S: Into<String>>(mut self, dtstart: S) -> Self {
+        self.0.availability.properties.push(ical_property!(
+            "DTSTART",
+            dtstart.into(),
+            ical_param!("TZID", &self.0.tzid)
+        ));
+        self
+    }
+
+    /// Sets the `DTEND` of the time covered by the availability, which is unbounded without
+    /// it.
+    pub fn end<S: Into<String>>(mut self, dtend: S) -> Self {
+        self.0.availability.properties.push(ical_property!(
+            "DTEND",
+            dtend.into(),
+            ical_param!("TZID", &self.0.tzid)
+        ));
+        self
+    }
+
+    /// Sets the `PRIORITY` of the availability, from 1 (the highest) to 9 (the lowest). The
+    /// availabilities without priority are the lowest ones.
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.0
+            .availability
+            .properties
+            .push(ical_property!("PRIORITY", priority.to_string()));
+        self
+    }
+
+    /// Sets the `BUSYTYPE` of the time not covered by the `AVAILABLE` components:
+    /// `BUSY-UNAVAILABLE` (the default), `BUSY` or `BUSY-TENTATIVE`.
+    pub fn busy_type<S: Into<String>>(mut self, busy_type: S) -> Self {
+        self.0
+            .availability
+            .properties
+            .push(ical_property!("BUSYTYPE", busy_type.into()));
+        self
+    }
+
+    /// Setting arbitrary property.
+    ///
+    /// You can use the [ical_property!-macro](/ical/macro.ical_property.html).
+    ///
+    pub fn set(mut self, property: Property) -> Self {
+        self.0.availability.properties.push(property);
+        self
+    }
+
+    /// Adds a free time slot, which can be built with the
+    /// [IcalAvailableBuilder](struct.IcalAvailableBuilder.html).
+    pub fn add_available(mut self, available: IcalAvailable) -> Self {
+        self.0.availability.available.push(available);
+        self
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use generator::*;
+    use ical_property;
+
+    #[test]
+    fn build_availability() {
+        let expect = "BEGIN:VAVAILABILITY\r\n\
+       UID:availability@example.com\r\n\
+       DTSTAMP:20240105T090000Z\r\n\
+       DTSTART;TZID=America/Montreal:20240101T000000\r\n\
+       PRIORITY:1\r\n\
+       BEGIN:AVAILABLE\r\n\
+       UID:office-hours@example.com\r\n\
+       DTSTAMP:20240105T090000Z\r\n\
+       DTSTART;TZID=America/Montreal:20240108T090000\r\n\
+       DTEND;TZID=America/Montreal:20240108T170000\r\n\
+       RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\r\n\
+       SUMMARY:Office hours\r\n\
+       END:AVAILABLE\r\n\
+       END:VAVAILABILITY\r\n\
+      ";
+        let rule: Recur = "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR".parse().unwrap();
+
+        let office_hours = IcalAvailableBuilder::tzid("America/Montreal")
+            .uid("office-hours@example.com")
+            .changed_utc("20240105T090000Z")
+            .start("20240108T090000")
+            .end("20240108T170000")
+            .repeat_rule(rule)
+            .unwrap()
+            .set(ical_property!("SUMMARY", "Office hours"))
+            .build();
+
+        let availability = IcalAvailabilityBuilder::tzid("America/Montreal")
+            .uid("availability@example.com")
+            .changed_utc("20240105T090000Z")
+            .start("20240101T000000")
+            .priority(1)
+            .add_available(office_hours)
+            .build();
+
+        assert_eq!(expect, availability.generate());
+    }
+}
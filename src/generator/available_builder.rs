@@ -0,0 +1,125 @@
+use parser::ical::component::IcalAvailable;
+use property::Property;
+use value::recur::{Recur, RecurError};
+use {ical_param, ical_property};
+
+pub struct IcalAvailableBuilder {
+    tzid: String,
+    available: IcalAvailable,
+}
+pub struct Uid(IcalAvailableBuilder);
+pub struct DtStamp(IcalAvailableBuilder);
+pub struct DtStart(IcalAvailableBuilder);
+pub struct DtEnd(IcalAvailableBuilder);
+pub struct Finalizer(IcalAvailableBuilder);
+
+/// Builds a new [RFC 7953 - AVAILABLE Component](https://tools.ietf.org/html/rfc7953#section-3.1),
+/// to add to a [VAVAILABILITY](struct.IcalAvailabilityBuilder.html).
+///
+/// ```
+/// # use ical::generator::*;
+/// #
+/// let lunch_break = IcalAvailableBuilder::tzid("Europe/Berlin")
+///     .uid("lunch@example.com")
+///     .changed_utc("20240105T090000Z")
+///     .start("20240108T120000")
+///     .duration("PT1H")
+///     .repeat_rule(Recur::new(Frequency::Daily))
+///     .unwrap()
+///     .build();
+/// ```
+impl IcalAvailableBuilder {
+    /// Starts a free time slot whose times are local to `timezone`.
+    pub fn tzid<S: Into<String>>(timezone: S) -> Uid {
+        Uid(Self {
+            tzid: timezone.into(),
+            available: IcalAvailable::new(),
+        })
+    }
+}
+
+impl Uid {
+    /// Sets the `UID` of the time slot.
+    pub fn uid<S: Into<String>>(mut self, uid: S) -> DtStamp {
+        self.0.available.properties.push(ical_property!("UID", uid));
+        DtStamp(self.0)
+    }
+}
+
+impl DtStamp {
+    /// Sets the `DTSTAMP` of the time slot. Signals the date of the last change in UTC.
+    pub fn changed_utc<S: Into<String>>(mut self, dtstamp: S) -> DtStart {
+        self.0
+            .available
+            .properties
+            .push(ical_property!("DTSTAMP", dtstamp.into()));
+        DtStart(self.0)
+    }
+}
+
+impl DtStart {
+    /// Sets the `DTSTART` of the time slot.
+    ///
+    /// Needs a timestamp (YYYYMMDDTHHmmSS)
+    pub fn start<S: Into<String>>(mut self, dtstart: S) -> DtEnd {
+        self.0.available.properties.push(ical_property!(
+            "DTSTART",
+            dtstart.into(),
+            ical_param!("TZID", &self.0.tzid)
+        ));
+        DtEnd(self.0)
+    }
+}
+
+impl DtEnd {
+    /// Sets the `DTEND` of the time slot, **not inclusive**.
+    pub fn end<S: Into<String>>(mut self, dtend: S) -> Finalizer {
+        self.0.available.properties.push(ical_property!(
+            "DTEND",
+            dtend.into(),
+            ical_param!("TZID", &self.0.tzid)
+        ));
+        Finalizer(self.0)
+    }
+
+    /// Sets the `DURATION` of the time slot.
+    ///
+    /// `value` starts with `PT` + duration eg. PT45M
+    pub fn duration<S: Into<String>>(mut self, value: S) -> Finalizer {
+        self.0
+            .available
+            .properties
+            .push(ical_property!("DURATION", value.into()));
+        Finalizer(self.0)
+    }
+}
+
+impl Finalizer {
+    pub fn build(self) -> IcalAvailable {
+        self.0.available
+    }
+
+    /// Setting arbitrary property.
+    ///
+    /// You can use the [ical_property!-macro](/ical/macro.ical_property.html).
+    ///
+    pub fn set(mut self, property: Property) -> Self {
+        self.0.available.properties.push(property);
+        self
+    }
+
+    /// Rule for the repeating time slot.
+    ///
+    /// @see <https://tools.ietf.org/html/rfc5545#section-3.8.5.3>
+    ///
+    /// Fails if the rule breaks RFC 5545, such as an `INTERVAL=0` or a `BYSETPOS` without any
+    /// other `BYxxx` rule part (see `Recur::validate`).
+    pub fn repeat_rule(mut self, rule: Recur) -> Result<Self, RecurError> {
+        rule.validate()?;
+        self.0
+            .available
+            .properties
+            .push(ical_property!("RRULE", rule.to_string()));
+        Ok(self)
+    }
+}
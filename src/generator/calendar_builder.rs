@@ -1,5 +1,5 @@
 use crate::ical_property;
-use parser::ical::component::{IcalCalendar, IcalEvent, IcalTimeZone};
+use parser::ical::component::{IcalAvailability, IcalCalendar, IcalEvent, IcalTimeZone};
 use property::Property;
 
 pub struct IcalCalendarBuilder {
@@ -68,8 +68,17 @@ impl ProdId {
 
 impl Finalizer {
     /// creates a complete IcalCalendar-object.
+    ///
+    /// With the `tzdb` feature, the `VTIMEZONE`s of the `TZID`s used by the events and not
+    /// added with [`add_tz`](#method.add_tz) are generated from the IANA database.
     pub fn build(self) -> IcalCalendar {
-        self.0.cal
+        #[allow(unused_mut)]
+        let mut cal = self.0.cal;
+
+        #[cfg(feature = "tzdb")]
+        cal.add_missing_timezones();
+
+        cal
     }
 
     pub fn set(mut self, property: Property) -> Self {
@@ -86,4 +95,46 @@ impl Finalizer {
         self.0.cal.timezones.push(tz);
         self
     }
+
+    pub fn add_availability(mut self, availability: IcalAvailability) -> Self {
+        self.0.cal.availabilities.push(availability);
+        self
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use generator::*;
+
+    #[test]
+    #[cfg(feature = "tzdb")]
+    fn add_the_missing_timezones() {
+        let event = IcalEventBuilder::tzid("Europe/Berlin")
+            .uid("UID_@_test")
+            .changed_utc("20201201T120423Z")
+            .start("20201206T170000")
+            .duration("PT2H45M0S")
+            .build();
+
+        let calendar = IcalCalendarBuilder::version("2.0")
+            .gregorian()
+            .prodid("-//ical-rs//test//EN")
+            .add_event(event)
+            .build();
+
+        assert_eq!(calendar.timezones.len(), 1);
+        assert_eq!(calendar.timezones[0].tzid(), Some("Europe/Berlin"));
+
+        let dtstart = crate::value::DateTime::Zoned {
+            date_time: chrono::NaiveDate::from_ymd_opt(2020, 12, 6)
+                .unwrap()
+                .and_hms_opt(17, 0, 0)
+                .unwrap(),
+            tzid: "Europe/Berlin".to_string(),
+        };
+        assert_eq!(
+            calendar.to_utc(&dtstart).unwrap().to_string(),
+            "2020-12-06 16:00:00 UTC"
+        );
+    }
 }
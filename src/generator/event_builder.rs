@@ -1,5 +1,6 @@
 use parser::ical::component::IcalEvent;
 use property::Property;
+use value::recur::{Recur, RecurError};
 use {ical_param, ical_property};
 
 #[allow(dead_code)]
@@ -26,13 +27,17 @@ pub struct Finalizer(IcalEventBuilder);
 ///     .uid("f9508b86-1a17-4594-b5d3-a87b5e9392a7")
 ///     .changed("2022-01-01T000000")
 ///     .one_day("1900-12-25")
-///     .repeat_rule("FREQ=YEARLY")
+///     .repeat_rule(Recur::new(Frequency::Yearly))
+///     .unwrap()
 ///     .set(ical_property!("DESCRIPTION","X-mas Day"))
 ///     .set(ical_property!("SUMMARY;LANGUAGE=de", "1. Weihnachtstag"))
 ///     .build();
 /// ```
-
 impl IcalEventBuilder {
+    /// Starts an event whose times are local to `timezone`.
+    ///
+    /// With the `tzdb` feature, the [IcalCalendarBuilder](struct.IcalCalendarBuilder.html)
+    /// adds the matching `VTIMEZONE` to the calendar holding the event.
     pub fn tzid<S: Into<String>>(timezone: S) -> Uid {
         Uid(Self {
             tzid: timezone.into(),
@@ -163,12 +168,18 @@ impl Finalizer {
     }
 
     /// Rule for the repeating occurrence.
-    pub fn repeat_rule<S: Into<String>>(mut self, value: S) -> Self {
+    ///
+    /// @see <https://tools.ietf.org/html/rfc5545#section-3.8.5.3>
+    ///
+    /// Fails if the rule breaks RFC 5545, such as an `INTERVAL=0` or a `BYSETPOS` without any
+    /// other `BYxxx` rule part (see `Recur::validate`).
+    pub fn repeat_rule(mut self, rule: Recur) -> Result<Self, RecurError> {
+        rule.validate()?;
         self.0
             .event
             .properties
-            .push(ical_property!("RRULE", value.into()));
-        Finalizer(self.0)
+            .push(ical_property!("RRULE", rule.to_string()));
+        Ok(Finalizer(self.0))
     }
 }
 
@@ -176,7 +187,8 @@ impl Finalizer {
 mod should {
     use crate::*;
     use generator::event_builder::IcalEventBuilder;
-    use property::Property;
+    use property::{Format, Property};
+    use value::recur::{Frequency, Recur};
 
     #[test]
     fn build_minimal_ical_event() {
@@ -246,7 +258,8 @@ mod should {
             .uid("19970901T130000Z-123403@example.com")
             .changed_utc("19970901T130000Z")
             .one_day("19971102")
-            .repeat_rule("FREQ=YEARLY")
+            .repeat_rule(Recur::new(Frequency::Yearly))
+            .unwrap()
             .set(ical_property!("SUMMARY", "Our Blissful Anniversary"))
             .set(ical_property!("TRANSP", "TRANSPARENT"))
             .set(ical_property!("CLASS", "CONFIDENTIAL"))
@@ -257,4 +270,48 @@ mod should {
             .build();
         assert_eq!(expect, event.generate());
     }
+
+    #[test]
+    fn build_escaped_text() {
+        use generator::Emitter;
+        let expect = "BEGIN:VEVENT\r\n\
+       UID:UID_@_test\r\n\
+       DTSTAMP:20201201T120423Z\r\n\
+       DTSTART;VALUE=DATE:20201206\r\n\
+       SUMMARY:Lunch\\, then nap\\; maybe\r\n\
+       DESCRIPTION:C:\\\\Temp\\nSecond line\r\n\
+       END:VEVENT\r\n\
+      ";
+        let event = IcalEventBuilder::tzid("Europe/Berlin")
+            .uid("UID_@_test")
+            .changed_utc("20201201T120423Z")
+            .one_day("20201206")
+            .set(Property::from_text(
+                "SUMMARY",
+                "Lunch, then nap; maybe",
+                Format::Ical,
+            ))
+            .set(Property::from_text(
+                "DESCRIPTION",
+                "C:\\Temp\nSecond line",
+                Format::Ical,
+            ))
+            .build();
+
+        assert_eq!(expect, event.generate());
+        assert_eq!(event.properties[4].text().unwrap(), "C:\\Temp\nSecond line");
+    }
+
+    #[test]
+    fn reject_invalid_rule() {
+        let mut rule = Recur::new(Frequency::Monthly);
+        rule.interval = Some(0);
+
+        let result = IcalEventBuilder::tzid("Europe/Berlin")
+            .uid("UID_@_test")
+            .changed_utc("20201201T120423Z")
+            .one_day("20201206")
+            .repeat_rule(rule);
+        assert!(result.is_err());
+    }
 }
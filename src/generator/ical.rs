@@ -1,9 +1,95 @@
 use crate::{PARAM_DELIMITER, PARAM_VALUE_DELIMITER, VALUE_DELIMITER};
+use parser::document::{DocumentComponent, DocumentEntry, DocumentProperty};
 use parser::ical::component::{
-    IcalAlarm, IcalCalendar, IcalEvent, IcalFreeBusy, IcalJournal, IcalTimeZone,
-    IcalTimeZoneTransition, IcalTodo,
+    IcalAlarm, IcalAvailability, IcalAvailable, IcalCalendar, IcalEvent, IcalFreeBusy, IcalJournal,
+    IcalTimeZone, IcalTimeZoneTransition, IcalTodo, IcalUnknownComponent,
 };
-use property::Property;
+use property::{encode_param_value, escape_text, Format, Property};
+use std::io;
+use value::{ValueType, TEXT_LISTS, VCARD_STRUCTURED};
+
+/// Folding of the content lines longer than a given width.
+///
+/// [RFC5545 section 3.1](https://tools.ietf.org/html/rfc5545#section-3.1) limits the lines
+/// to 75 octets, excluding the line break. A long line is split in several ones, each
+/// following one starting with a space.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Folding {
+    /// Fold the lines longer than the given number of octets, never splitting a UTF-8
+    /// sequence. Built by `Folding::octets`.
+    Octets(Width),
+    /// Never fold the lines.
+    Disabled,
+}
+
+/// A folding width of at least 2 octets, for a continuation line to hold a character after
+/// its leading space.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Width(usize);
+
+impl Width {
+    /// Return the width in octets.
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl Folding {
+    /// Fold the lines longer than `width` octets, or return `None` if `width` is narrower
+    /// than 2 octets.
+    pub fn octets(width: usize) -> Option<Folding> {
+        if width < 2 {
+            return None;
+        }
+
+        Some(Folding::Octets(Width(width)))
+    }
+}
+
+impl Default for Folding {
+    /// Fold at 75 octets, as required by RFC 5545 and RFC 6350.
+    fn default() -> Folding {
+        Folding::Octets(Width(75))
+    }
+}
+
+/// Return the value given to [`ical_property!`](../macro.ical_property.html), escaped if it's
+/// a single `TEXT` in both iCalendar and vCard.
+///
+/// The type is taken from the `VALUE` parameter, or else from the name of the property. The
+/// lists, such as `CATEGORIES`, and the structured values, such as `N` or `REQUEST-STATUS`,
+/// are kept as given, their delimiters being escaped by the caller.
+pub fn escape_property_value(
+    name: &str,
+    params: &[(String, Vec<String>)],
+    value: String,
+) -> String {
+    // The name may be followed by its parameters, as in `SUMMARY;LANGUAGE=de`.
+    let name = name.split(';').next().unwrap_or_default().to_uppercase();
+
+    let value_type = params
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("VALUE"))
+        .and_then(|(_, values)| values.first());
+    let text = match value_type {
+        Some(value_type) => ValueType::from_name(value_type) == ValueType::Text,
+        None => {
+            ValueType::default_for(&name, Format::Ical) == ValueType::Text
+                && ValueType::default_for(&name, Format::Vcard) == ValueType::Text
+        }
+    };
+    let name = name.as_str();
+
+    if !text
+        || TEXT_LISTS.contains(&name)
+        || VCARD_STRUCTURED.contains(&name)
+        || name == "REQUEST-STATUS"
+    {
+        return value;
+    }
+
+    escape_text(&value, Format::Ical)
+}
 
 ///
 /// Emits the content of the Component in ical-format.
@@ -11,71 +97,157 @@ use property::Property;
 pub trait Emitter {
     /// creates a textual-representation of this object and all it's properties
     /// in ical-format.
-    fn generate(&self) -> String;
+    fn generate(&self) -> String {
+        self.generate_with(Folding::default())
+    }
+
+    /// creates a textual-representation of this object and all it's properties
+    /// in ical-format, folding the lines as given.
+    fn generate_with(&self, folding: Folding) -> String {
+        let mut buffer = Vec::new();
+        self.write_with(&mut buffer, folding)
+            .expect("writing in a Vec never fails");
+
+        String::from_utf8(buffer).expect("the properties are valid UTF-8")
+    }
+
+    /// writes this object and all it's properties in ical-format to `writer`.
+    ///
+    /// Nothing is buffered, wrap `writer` in a `BufWriter` if it's costly to write to.
+    fn write_to<W: io::Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        self.write_with(writer, Folding::default())
+    }
+
+    /// writes this object and all it's properties in ical-format to `writer`, folding the
+    /// lines as given.
+    fn write_with(&self, writer: &mut dyn io::Write, folding: Folding) -> io::Result<()>;
+}
+
+/// Writes a content line, folding it on the fly.
+struct LineWriter<'w> {
+    writer: &'w mut dyn io::Write,
+    folding: Folding,
+    /// Octets already written on the current physical line.
+    line_len: usize,
+    /// Whether the current physical line is a continuation one, starting with a space.
+    continued: bool,
 }
 
-fn get_value(value: &Option<String>) -> String {
-    VALUE_DELIMITER.to_string() + value.as_ref().unwrap_or(&String::new())
+impl<'w> LineWriter<'w> {
+    fn new(writer: &'w mut dyn io::Write, folding: Folding) -> LineWriter<'w> {
+        LineWriter {
+            writer,
+            folding,
+            line_len: 0,
+            continued: false,
+        }
+    }
+
+    /// Write a part of the line.
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        let width = match self.folding {
+            Folding::Octets(width) => width.get(),
+            Folding::Disabled => return self.writer.write_all(text.as_bytes()),
+        };
+
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            let limit = if self.continued { width - 1 } else { width };
+
+            if self.line_len + c.len_utf8() > limit && self.line_len > 0 {
+                self.writer.write_all(&text.as_bytes()[start..i])?;
+                self.writer.write_all(b"\r\n ")?;
+                start = i;
+                self.line_len = 0;
+                self.continued = true;
+            }
+            self.line_len += c.len_utf8();
+        }
+
+        self.writer.write_all(&text.as_bytes()[start..])
+    }
+
+    fn write_char(&mut self, c: char) -> io::Result<()> {
+        self.write(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Write a parameter value, caret-encoded and quoted if it holds a `:`, `;` or `,`.
+    fn write_param(&mut self, param: &str) -> io::Result<()> {
+        let quoted = param.contains([':', ';', ',']);
+
+        if quoted {
+            self.write("\"")?;
+        }
+        self.write(&encode_param_value(param))?;
+        if quoted {
+            self.write("\"")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a value, its raw line breaks being escaped as `\n` to keep the line whole.
+    fn write_value(&mut self, value: &str) -> io::Result<()> {
+        if !value.contains(['\r', '\n']) {
+            return self.write(value);
+        }
+
+        self.write(&value.replace("\r\n", "\\n").replace(['\r', '\n'], "\\n"))
+    }
+
+    /// Terminate the line.
+    fn end(self) -> io::Result<()> {
+        self.writer.write_all(b"\r\n")
+    }
 }
 
 pub(crate) fn split_line<T: Into<String>>(str: T) -> String {
-    let str = str.into();
-    let mut chars = str.chars();
-    let mut first = true;
-    let sub_string = (0..)
-        .map(|_| chars.by_ref().take(if first { first = false; 75 } else { 74 }).collect::<String>())
-        .take_while(|s| !s.is_empty())
-        .collect::<Vec<_>>();
-    sub_string.join("\r\n ")
+    fold_line(str, Folding::default())
+}
+
+/// Fold `str` following `folding`. The first line holds up to `width` octets and the
+/// following ones, after their leading space, up to `width - 1`.
+pub(crate) fn fold_line<T: Into<String>>(str: T, folding: Folding) -> String {
+    let mut buffer = Vec::new();
+    LineWriter::new(&mut buffer, folding)
+        .write(&str.into())
+        .expect("writing in a Vec never fails");
+
+    String::from_utf8(buffer).expect("a line is only split between characters")
 }
 
 //
-// @see: https://tools.ietf.org/html/rfc5545#section-3.3.11
+// @see: https://tools.ietf.org/html/rfc5545#section-3.2
 //
-// `text = *(TSAFE-CHAR / ":" / DQUOTE / ESCAPED-CHAR)`
-//     Folded according to description above
+// `param-value = paramtext / quoted-string`
+// `paramtext = *SAFE-CHAR`
+// `quoted-string = DQUOTE *QSAFE-CHAR DQUOTE`
+//     A value holding ":", ";" or "," must be quoted.
 //
-// `ESCAPED-CHAR = ("\\" / "\;" / "\," / "\N" / "\n")`
-//     \\ encodes \, \N or \n encodes newline
-//     \; encodes ;, \, encodes ,
+// @see: https://tools.ietf.org/html/rfc6868
 //
-// `TSAFE-CHAR = WSP / %x21 / %x23-2B / %x2D-39 / %x3C-5B /
-//              %x5D-7E / NON-US-ASCII`
-//     Any character except CONTROLs not needed by the current
-//     character set, DQUOTE, ";", ":", "\", ","
+//     A DQUOTE is encoded ^', a newline ^n and a ^ is encoded ^^.
 //
 #[allow(clippy::ptr_arg)]
 pub(crate) fn protect_params(param: &String) -> String {
-    let str = param.as_str();
-    let len = str.len() - 1;
-    // starts and ends the param with quotes?
-    let in_quotes = len > 1 && &str[0..1] == "\"" && &str[len..] == "\"";
-
-    let to_escape: Vec<(usize, char)> = str
-        .chars()
-        .enumerate()
-        .filter(|(_, c)| {
-            c == &'\"'
-                || c == &'\n'
-                || !in_quotes && (c == &';' || c == &':' || c == &',' || c == &'\\')
-        })
-        .collect();
-    let mut ret = param.to_string();
-    for (pos, ch) in to_escape.iter().rev() {
-        let pos = *pos;
-        if ch == &'\n' {
-            ret.replace_range(pos..pos + 1, "\\n");
-        } else if pos > 0 && pos < len && &str[pos - 1..pos] != "\\" {
-            ret.insert(pos, '\\');
-        }
-    }
-    ret + &PARAM_VALUE_DELIMITER.to_string()
+    let mut buffer = Vec::new();
+    let mut line = LineWriter::new(&mut buffer, Folding::Disabled);
+    line.write_param(param)
+        .and_then(|_| line.write_char(PARAM_VALUE_DELIMITER))
+        .expect("writing in a Vec never fails");
+
+    String::from_utf8(buffer).expect("only ASCII characters are inserted")
 }
 
 #[allow(unused)]
 mod should {
+    use generator::ical::fold_line;
     use generator::protect_params;
     use generator::split_line;
+    use generator::Folding;
 
     #[test]
     fn split_long_line() {
@@ -89,92 +261,232 @@ mod should {
     #[test]
     fn split_long_line_multibyte() {
         // the following text includes multibyte characters (UTF-8) at strategic places to ensure
-        // split_line would panic if not multibyte aware
-        let text = "DESCRIPTION:ABCDEFGHIJ\\n\\nKLMNOPQRSTUVWXYZ123456789üABCDEFGHIJKLMNOPQRS\\n\\n\r\n \
-                     TUVWXYZ123456ä7890ABCDEFGHIJKLM\\n\\nNOPQRSTUVWXYZ1234567890ABCDEFGHIJKLMNOP\r\n \
-                     QRSTUVWXöYZ1234567890ABCDEFGHIJKLMNOPQRSTUVWX\\n\\nYZ1234567890abcdefghiÜjkl\r\n \
-                     m\\nnopqrstuvwx";
+        // split_line counts octets and never splits a character
+        let text = "DESCRIPTION:ABCDEFGHIJ\\n\\nKLMNOPQRSTUVWXYZ123456789üABCDEFGHIJKLMNOPQRS\\n\\\r\n \
+                     nTUVWXYZ123456ä7890ABCDEFGHIJKLM\\n\\nNOPQRSTUVWXYZ1234567890ABCDEFGHIJKLMN\r\n \
+                     OPQRSTUVWXöYZ1234567890ABCDEFGHIJKLMNOPQRSTUVWX\\n\\nYZ1234567890abcdefghi\r\n \
+                     Üjklm\\nnopqrstuvwx";
         assert_eq!(text, split_line(text.replace("\r\n ", "")));
+        assert!(text.split("\r\n").all(|line| line.len() <= 75));
+    }
+
+    #[test]
+    fn fold_at_the_given_width() {
+        let text = "SUMMARY:ééééé";
+
+        let octets = |width| Folding::octets(width).unwrap();
+
+        assert_eq!(fold_line(text, octets(12)), "SUMMARY:éé\r\n ééé");
+        assert_eq!(fold_line(text, octets(18)), text);
+        assert_eq!(
+            fold_line(text, octets(2)),
+            "SU\r\n M\r\n M\r\n A\r\n R\r\n Y\r\n :\r\n é\r\n é\r\n é\r\n é\r\n é"
+        );
+        assert_eq!(
+            fold_line(text.repeat(20), Folding::Disabled),
+            text.repeat(20)
+        );
+    }
+
+    #[test]
+    fn reject_too_narrow_foldings() {
+        assert_eq!(Folding::octets(0), None);
+        assert_eq!(Folding::octets(1), None);
+        assert_eq!(Folding::octets(75), Some(Folding::default()));
+    }
+
+    #[test]
+    fn write_to_a_writer() {
+        use generator::{Emitter, Property};
+        use parser::ical::component::IcalAlarm;
+
+        let mut property = Property::new();
+        property.name = String::from("DESCRIPTION");
+        property.params = Some(vec![(String::from("LANGUAGE"), vec![String::from("fr")])]);
+        property.value = Some("Réveil".repeat(15));
+
+        let mut alarm = IcalAlarm::new();
+        alarm.properties.push(property);
+
+        let mut written = Vec::new();
+        alarm.write_to(&mut written).unwrap();
+
+        assert_eq!(String::from_utf8(written).unwrap(), alarm.generate());
+        assert_eq!(
+            alarm.generate_with(Folding::octets(40).unwrap()),
+            "BEGIN:VALARM\r\n\
+             DESCRIPTION;LANGUAGE=fr:RéveilRéveilR\r\n \
+             éveilRéveilRéveilRéveilRéveilRéve\r\n \
+             ilRéveilRéveilRéveilRéveilRéveilR\r\n \
+             éveilRéveil\r\n\
+             END:VALARM\r\n"
+        );
+    }
+
+    #[test]
+    fn escape_the_raw_line_breaks() {
+        use generator::{Emitter, Property};
+
+        let mut property = Property::new();
+        property.name = String::from("DESCRIPTION");
+        property.value = Some(String::from("First\r\nSecond\nThird\rFourth\\nFifth"));
+
+        assert_eq!(
+            property.generate(),
+            "DESCRIPTION:First\\nSecond\\nThird\\nFourth\\nFifth\r\n"
+        );
+    }
+
+    #[test]
+    fn escape_the_text_of_the_macro() {
+        use generator::{Emitter, Property};
+        use {ical_param, ical_property};
+
+        assert_eq!(
+            ical_property!("SUMMARY", "a, b; c\\d").generate(),
+            "SUMMARY:a\\, b\\; c\\\\d\r\n"
+        );
+        assert_eq!(
+            ical_property!("X-NOTE", "a,b", ical_param!("VALUE", "TEXT")).generate(),
+            "X-NOTE;VALUE=TEXT:a\\,b\r\n"
+        );
+        assert_eq!(
+            ical_property!("CATEGORIES", "WORK,HOME").generate(),
+            "CATEGORIES:WORK,HOME\r\n"
+        );
+        assert_eq!(
+            ical_property!("ADR", ";;1 Main St.;Springfield").generate(),
+            "ADR:;;1 Main St.;Springfield\r\n"
+        );
+        assert_eq!(
+            ical_property!("RRULE", "FREQ=DAILY;COUNT=2").generate(),
+            "RRULE:FREQ=DAILY;COUNT=2\r\n"
+        );
     }
 
     #[test]
     fn protect_chars_in_params() {
         assert_eq!(
-            protect_params(&String::from("\"value: in quotes;\"")),
+            protect_params(&String::from("value: in quotes;")),
             "\"value: in quotes;\","
         );
         assert_eq!(
-            protect_params(&String::from("\"value, in quotes\"")),
-            "\"value, in quotes\","
+            protect_params(&String::from("value, \"with\" something")),
+            "\"value, ^'with^' something\","
         );
         assert_eq!(
-            protect_params(&String::from("value, \"with\" something")),
-            "value\\, \\\"with\\\" something,"
+            protect_params(&String::from("Directory; C:\\Programme")),
+            "\"Directory; C:\\Programme\","
         );
         assert_eq!(
-            protect_params(&String::from("\"Directory; C:\\\\Programme\"")),
-            "\"Directory; C:\\\\Programme\","
+            protect_params(&String::from("\"already quoted\"")),
+            "^'already quoted^',"
         );
         assert_eq!(
             protect_params(&String::from("First\nSecond")),
-            "First\\nSecond,"
+            "First^nSecond,"
         );
+        assert_eq!(protect_params(&String::from("^_^")), "^^_^^,");
     }
 }
 
-fn get_params(params: &Option<Vec<(String, Vec<String>)>>) -> String {
-    match params {
-        None => String::new(),
-        Some(vec) => {
-            vec.iter()
-                .map(|(name, values)| {
-                    let mut value = values.iter().map(protect_params).collect::<String>();
-                    value.pop(); // remove last comma
-                    format!("{}{}={}", PARAM_DELIMITER, name, value)
-                })
-                .collect::<String>()
+/// The value is written as stored, in its escaped form, except its raw line breaks which are
+/// escaped as `\n`. Use `Property::from_text` or `Property::set_text` to store an unescaped
+/// `TEXT`.
+impl Emitter for Property {
+    fn write_with(&self, writer: &mut dyn io::Write, folding: Folding) -> io::Result<()> {
+        let mut line = LineWriter::new(writer, folding);
+
+        if let Some(group) = &self.group {
+            line.write(group)?;
+            line.write(".")?;
         }
+        line.write(&self.name)?;
+        for (name, values) in self.params.iter().flatten() {
+            line.write_char(PARAM_DELIMITER)?;
+            line.write(name)?;
+            line.write("=")?;
+
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    line.write_char(PARAM_VALUE_DELIMITER)?;
+                }
+                line.write_param(value)?;
+            }
+        }
+        line.write_char(VALUE_DELIMITER)?;
+        line.write_value(self.value.as_deref().unwrap_or_default())?;
+
+        line.end()
     }
 }
 
-impl Emitter for Property {
-    fn generate(&self) -> String {
-        split_line(self.name.clone() + &get_params(&self.params) + &get_value(&self.value)) + "\r\n"
+/// The octets the property was read from are written back if it wasn't changed since.
+impl Emitter for DocumentProperty {
+    fn write_with(&self, writer: &mut dyn io::Write, folding: Folding) -> io::Result<()> {
+        match self.raw() {
+            Some(raw) => writer.write_all(raw),
+            None => self.property.write_with(writer, folding),
+        }
+    }
+}
+
+impl Emitter for DocumentComponent {
+    fn write_with(&self, writer: &mut dyn io::Write, folding: Folding) -> io::Result<()> {
+        self.begin().write_with(writer, folding)?;
+        for entry in &self.entries {
+            match entry {
+                DocumentEntry::Property(property) => property.write_with(writer, folding)?,
+                DocumentEntry::Component(component) => component.write_with(writer, folding)?,
+            }
+        }
+
+        self.end().write_with(writer, folding)
     }
 }
 
 impl Emitter for IcalTimeZoneTransition {
-    fn generate(&self) -> String {
+    fn write_with(&self, writer: &mut dyn io::Write, folding: Folding) -> io::Result<()> {
         use crate::parser::ical::component::IcalTimeZoneTransitionType::{DAYLIGHT, STANDARD};
         let key = match &self.transition {
             STANDARD => "STANDARD",
             DAYLIGHT => "DAYLIGHT",
         };
-        String::from("BEGIN:")
-            + key
-            + "\r\n"
-            + &self
-                .properties
-                .iter()
-                .map(Emitter::generate)
-                .collect::<String>()
-            + "END:"
-            + key
-            + "\r\n"
+
+        write!(writer, "BEGIN:{}\r\n", key)?;
+        for property in &self.properties {
+            property.write_with(writer, folding)?;
+        }
+        for component in &self.unknown_components {
+            component.write_with(writer, folding)?;
+        }
+        write!(writer, "END:{}\r\n", key)
+    }
+}
+
+impl Emitter for IcalUnknownComponent {
+    fn write_with(&self, writer: &mut dyn io::Write, folding: Folding) -> io::Result<()> {
+        write!(writer, "BEGIN:{}\r\n", self.name)?;
+        for property in &self.properties {
+            property.write_with(writer, folding)?;
+        }
+        for component in &self.sub_components {
+            component.write_with(writer, folding)?;
+        }
+        write!(writer, "END:{}\r\n", self.name)
     }
 }
 
 macro_rules! generate_emitter {
     ($struct:ident, $key:literal, $($prop:ident),+) => {
         impl Emitter for $struct {
-            fn generate(&self) -> String {
-                let mut text = String::from("BEGIN:") + $key + "\r\n";
-                $(text += &self.$prop
-                .iter()
-                .map(Emitter::generate)
-                .collect::<String>();)+
-
-                text + "END:" + $key + "\r\n"
+            fn write_with(&self, writer: &mut dyn io::Write, folding: Folding) -> io::Result<()> {
+                writer.write_all(concat!("BEGIN:", $key, "\r\n").as_bytes())?;
+                $(for child in &self.$prop {
+                    child.write_with(writer, folding)?;
+                })+
+
+                writer.write_all(concat!("END:", $key, "\r\n").as_bytes())
             }
         }
     };
@@ -184,14 +496,28 @@ macro_rules! generate_emitter {
 use parser::vcard::component::VcardContact;
 
 #[cfg(feature = "vcard")]
-generate_emitter!(VcardContact, "VCARD", properties);
-
-generate_emitter!(IcalAlarm, "VALARM", properties);
-generate_emitter!(IcalFreeBusy, "VFREEBUSY", properties);
-generate_emitter!(IcalJournal, "VJOURNAL", properties);
-generate_emitter!(IcalEvent, "VEVENT", properties, alarms);
-generate_emitter!(IcalTodo, "VTODO", properties, alarms);
-generate_emitter!(IcalTimeZone, "VTIMEZONE", properties, transitions);
+generate_emitter!(VcardContact, "VCARD", properties, unknown_components);
+
+generate_emitter!(IcalAlarm, "VALARM", properties, unknown_components);
+generate_emitter!(IcalFreeBusy, "VFREEBUSY", properties, unknown_components);
+generate_emitter!(IcalJournal, "VJOURNAL", properties, unknown_components);
+generate_emitter!(IcalEvent, "VEVENT", properties, alarms, unknown_components);
+generate_emitter!(IcalTodo, "VTODO", properties, alarms, unknown_components);
+generate_emitter!(IcalAvailable, "AVAILABLE", properties, unknown_components);
+generate_emitter!(
+    IcalAvailability,
+    "VAVAILABILITY",
+    properties,
+    available,
+    unknown_components
+);
+generate_emitter!(
+    IcalTimeZone,
+    "VTIMEZONE",
+    properties,
+    transitions,
+    unknown_components
+);
 generate_emitter!(
     IcalCalendar,
     "VCALENDAR",
@@ -201,5 +527,7 @@ generate_emitter!(
     alarms,
     todos,
     journals,
-    free_busys
+    free_busys,
+    availabilities,
+    unknown_components
 );
@@ -10,24 +10,37 @@
 //!
 //!
 
+mod availability_builder;
+mod available_builder;
 mod calendar_builder;
 mod event_builder;
 mod ical;
 mod vcard_builder;
 
+// The builders share the names of their steps, such as `Finalizer`, so only the ones of the
+// event builder are exported.
 #[cfg(feature = "ical")]
-pub use self::calendar_builder::*;
+pub use self::availability_builder::IcalAvailabilityBuilder;
 #[cfg(feature = "ical")]
-pub use self::event_builder::*;
+pub use self::available_builder::IcalAvailableBuilder;
+#[cfg(feature = "ical")]
+pub use self::calendar_builder::{CalScale, IcalCalendarBuilder, ProdId};
+#[cfg(feature = "ical")]
+pub use self::event_builder::{
+    DtEnd, DtEndDate, DtStamp, DtStart, IcalEventBuilder, Uid, ICAL_DATE_FORMAT,
+};
 #[cfg(any(feature = "ical", feature = "vcard"))]
 pub use self::ical::*;
 #[cfg(feature = "vcard")]
-pub use self::vcard_builder::*;
+pub use self::vcard_builder::{FormattedName, IcalVcardBuilder, Name};
 #[cfg(feature = "ical")]
-pub use crate::parser::ical::component::{IcalCalendar, IcalEvent};
+pub use crate::parser::ical::component::{
+    IcalAvailability, IcalAvailable, IcalCalendar, IcalEvent,
+};
 #[cfg(feature = "vcard")]
 pub use crate::parser::vcard::component::VcardContact;
-pub use crate::property::Property;
+pub use crate::property::{Format, Property};
+pub use crate::value::recur::{Frequency, Recur};
 
 mod helper {
 
@@ -52,6 +65,10 @@ mod helper {
     /// [IcalVcardBuilder](generator/struct.IcalVcardBuilder.html),
     /// `IcalTodo`, `IcalJournal` ...
     ///
+    /// A single `TEXT` value is escaped, see
+    /// [`escape_property_value`](generator/fn.escape_property_value.html): pass the raw text,
+    /// such as `"Lunch, then nap"`. The lists and the structured values are kept as given.
+    ///
     /// # Example
     /// ```
     /// # #[macro_use] extern crate ical;
@@ -64,11 +81,13 @@ mod helper {
     ///         );
     /// let debug_output = "Property { \
     ///     name: \"NAME\", \
+    ///     group: None, \
     ///     params: Some([\
     ///         (\"param2\", [\"pvalue1\", \"pvalue2\"]), \
     ///         (\"param3\", [\"pvalue3\"])\
     ///     ]), \
-    ///     value: Some(\"value\") \
+    ///     value: Some(\"value\"), \
+    ///     span: None \
     /// }";
     /// assert_eq!(debug_output, format!("{:?}", prop));
     /// ```
@@ -77,16 +96,29 @@ mod helper {
         ($name:literal, $value:expr) => {
             Property {
                 name: String::from($name),
-                value: Some($value.into()),
+                group: None,
+                value: Some($crate::generator::escape_property_value(
+                    $name,
+                    &[],
+                    $value.into(),
+                )),
                 params: None,
+                span: None,
             }
         };
-        ($name:literal, $value:expr, $($params:expr),+) => {
+        ($name:literal, $value:expr, $($params:expr),+) => {{
+            let params = vec![$($params,)+];
             Property {
                 name: String::from($name),
-                value: Some(String::from($value)),
-                params: Some(vec![$($params,)+]),
+                group: None,
+                value: Some($crate::generator::escape_property_value(
+                    $name,
+                    &params,
+                    String::from($value),
+                )),
+                params: Some(params),
+                span: None,
             }
-        };
+        }};
     }
 }
@@ -1,6 +1,6 @@
 use crate::ical_property;
 use parser::vcard::component::VcardContact;
-use property::Property;
+use property::{escape_component, Format, Property};
 
 pub struct IcalVcardBuilder {
     vcard: VcardContact,
@@ -99,10 +99,14 @@ impl Name {
             clean(honorific_suffixes),
         ];
 
+        let components = names
+            .iter()
+            .map(|name| escape_component(name))
+            .collect::<Vec<_>>();
         self.0
             .vcard
             .properties
-            .push(ical_property!("N", names.join(";")));
+            .push(ical_property!("N", components.join(";")));
         FormattedName {
             builder: self.0,
             names,
@@ -121,7 +125,7 @@ impl FormattedName {
         self.builder
             .vcard
             .properties
-            .push(ical_property!("FN", f_n.into()));
+            .push(Property::from_text("FN", &f_n.into(), Format::Vcard));
         Finalizer(self.builder)
     }
 
@@ -143,7 +147,7 @@ impl FormattedName {
         let formatted_name = add_sep(self.names.get(3), " ")
             + &add_sep(self.names.get(1), " ")
             + &add_sep(self.names.get(2), " ")
-            + &add_sep(self.names.get(0), " ")
+            + &add_sep(self.names.first(), " ")
             + &add_sep(self.names.get(4), " ");
 
         Self::formatted_name(self, formatted_name.trim())
@@ -182,10 +186,9 @@ mod should {
         PHOTO;MEDIATYPE=image/gif:http://www.example.com/dir_photos/my_photo.gif\r\n\
         TEL;TYPE=work,voice;VALUE=uri:tel:+1-111-555-1212\r\n\
         TEL;TYPE=home,voice;VALUE=uri:tel:+1-404-555-1212\r\n\
-        ADR;TYPE=WORK;PREF=1;LABEL=\"100 Waters Edge\\nBaytown\\, LA 30314\\nUnited Sta\r\n \
-         tes of America\":;;100 Waters Edge;Baytown;LA;30314;United States of Americ\r\n \
-         a\r\n\
-        ADR;TYPE=HOME;LABEL=\"42 Plantation St.\\nBaytown\\, LA 30314\\nUnited States o\r\n f \
+        ADR;TYPE=WORK;PREF=1;LABEL=\"100 Waters Edge^nBaytown, LA 30314^nUnited Stat\r\n \
+         es of America\":;;100 Waters Edge;Baytown;LA;30314;United States of America\r\n\
+        ADR;TYPE=HOME;LABEL=\"42 Plantation St.^nBaytown, LA 30314^nUnited States of\r\n  \
          America\":;;42 Plantation St.;Baytown;LA;30314;United States of America\r\n\
         EMAIL:forrestgump@example.com\r\n\
         REV:20080424T195243Z\r\n\
@@ -222,7 +225,7 @@ mod should {
                 ical_param!("PREF", "1"),
                 ical_param!(
                     "LABEL",
-                    "\"100 Waters Edge\nBaytown\\, LA 30314\nUnited States of America\""
+                    "100 Waters Edge\nBaytown, LA 30314\nUnited States of America"
                 )
             ))
             .set(ical_property!(
@@ -231,7 +234,7 @@ mod should {
                 ical_param!("TYPE", "HOME"),
                 ical_param!(
                     "LABEL",
-                    "\"42 Plantation St.\nBaytown\\, LA 30314\nUnited States of America\""
+                    "42 Plantation St.\nBaytown, LA 30314\nUnited States of America"
                 )
             ))
             .set(ical_property!("EMAIL", "forrestgump@example.com"))
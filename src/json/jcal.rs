@@ -0,0 +1,179 @@
+//! jCal ([RFC7265](https://tools.ietf.org/html/rfc7265)), the JSON format of iCalendar.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["jcal"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use ical::json::Jcal;
+//! use ical::parser::ical::component::IcalCalendar;
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/ical_example_1.ics")
+//!     .unwrap());
+//!
+//! let calendar = ical::IcalParser::new(buf).next().unwrap().unwrap();
+//! let jcal = calendar.to_jcal_string().unwrap();
+//!
+//! assert_eq!(IcalCalendar::from_jcal_str(&jcal).unwrap().to_jcal_string().unwrap(), jcal);
+//! ```
+
+// Internal mods
+use super::{component_from_json, component_name, component_to_json, expect_component, JsonError};
+use crate::parser::ical::component::{
+    IcalAlarm, IcalAvailability, IcalAvailable, IcalCalendar, IcalEvent, IcalFreeBusy, IcalJournal,
+    IcalTimeZone, IcalTimeZoneTransition, IcalTimeZoneTransitionType, IcalTodo,
+    IcalUnknownComponent,
+};
+use crate::property::Format;
+use serde_json::Value;
+
+/// Converts a component to and from jCal.
+pub trait Jcal: Sized {
+    /// Convert the component and all its sub-components into jCal.
+    fn to_jcal(&self) -> Result<Value, JsonError>;
+
+    /// Build a component from its jCal representation.
+    fn from_jcal(jcal: &Value) -> Result<Self, JsonError>;
+
+    /// Convert the component and all its sub-components into a jCal string.
+    fn to_jcal_string(&self) -> Result<String, JsonError> {
+        Ok(serde_json::to_string(&self.to_jcal()?)?)
+    }
+
+    /// Build a component from a jCal string.
+    fn from_jcal_str(jcal: &str) -> Result<Self, JsonError> {
+        Self::from_jcal(&serde_json::from_str(jcal)?)
+    }
+}
+
+impl Jcal for IcalUnknownComponent {
+    fn to_jcal(&self) -> Result<Value, JsonError> {
+        let components = self
+            .sub_components
+            .iter()
+            .map(Jcal::to_jcal)
+            .collect::<Result<Vec<Value>, JsonError>>()?;
+
+        component_to_json(&self.name, &self.properties, components, Format::Ical)
+    }
+
+    fn from_jcal(jcal: &Value) -> Result<IcalUnknownComponent, JsonError> {
+        let (name, properties, components) = component_from_json(jcal, Format::Ical)?;
+
+        let mut component = IcalUnknownComponent::new(&name);
+        component.properties = properties;
+        component.sub_components = components
+            .iter()
+            .map(Jcal::from_jcal)
+            .collect::<Result<Vec<IcalUnknownComponent>, JsonError>>()?;
+
+        Ok(component)
+    }
+}
+
+impl Jcal for IcalTimeZoneTransition {
+    fn to_jcal(&self) -> Result<Value, JsonError> {
+        let name = match &self.transition {
+            IcalTimeZoneTransitionType::STANDARD => "STANDARD",
+            IcalTimeZoneTransitionType::DAYLIGHT => "DAYLIGHT",
+        };
+        let components = self
+            .unknown_components
+            .iter()
+            .map(Jcal::to_jcal)
+            .collect::<Result<Vec<Value>, JsonError>>()?;
+
+        component_to_json(name, &self.properties, components, Format::Ical)
+    }
+
+    fn from_jcal(jcal: &Value) -> Result<IcalTimeZoneTransition, JsonError> {
+        let (name, properties, components) = component_from_json(jcal, Format::Ical)?;
+
+        let mut transition = match name.as_str() {
+            "STANDARD" => IcalTimeZoneTransition::new(IcalTimeZoneTransitionType::STANDARD),
+            "DAYLIGHT" => IcalTimeZoneTransition::new(IcalTimeZoneTransitionType::DAYLIGHT),
+            found => {
+                return Err(JsonError::UnexpectedComponent {
+                    expected: String::from("STANDARD or DAYLIGHT"),
+                    found: found.to_string(),
+                })
+            }
+        };
+        transition.properties = properties;
+        transition.unknown_components = components
+            .iter()
+            .map(Jcal::from_jcal)
+            .collect::<Result<Vec<IcalUnknownComponent>, JsonError>>()?;
+
+        Ok(transition)
+    }
+}
+
+macro_rules! jcal_component {
+    ($struct:ident, $name:literal, $($($sub:literal)|+ => $field:ident),*) => {
+        impl Jcal for $struct {
+            fn to_jcal(&self) -> Result<Value, JsonError> {
+                let mut components = Vec::new();
+                $(for component in &self.$field {
+                    components.push(component.to_jcal()?);
+                })*
+                for component in &self.unknown_components {
+                    components.push(component.to_jcal()?);
+                }
+
+                component_to_json($name, &self.properties, components, Format::Ical)
+            }
+
+            fn from_jcal(jcal: &Value) -> Result<$struct, JsonError> {
+                let (name, properties, components) = component_from_json(jcal, Format::Ical)?;
+                expect_component(&name, $name)?;
+
+                let mut component = $struct::new();
+                component.properties = properties;
+                for sub_component in components {
+                    match component_name(sub_component)?.as_str() {
+                        $($($sub)|+ => component.$field.push(Jcal::from_jcal(sub_component)?),)*
+                        _ => component
+                            .unknown_components
+                            .push(Jcal::from_jcal(sub_component)?),
+                    }
+                }
+
+                Ok(component)
+            }
+        }
+    };
+}
+
+jcal_component!(IcalAlarm, "VALARM",);
+jcal_component!(IcalFreeBusy, "VFREEBUSY",);
+jcal_component!(IcalJournal, "VJOURNAL",);
+jcal_component!(IcalEvent, "VEVENT", "VALARM" => alarms);
+jcal_component!(IcalTodo, "VTODO", "VALARM" => alarms);
+jcal_component!(IcalAvailable, "AVAILABLE",);
+jcal_component!(IcalAvailability, "VAVAILABILITY", "AVAILABLE" => available);
+jcal_component!(
+    IcalTimeZone,
+    "VTIMEZONE",
+    "STANDARD" | "DAYLIGHT" => transitions
+);
+jcal_component!(
+    IcalCalendar,
+    "VCALENDAR",
+    "VTIMEZONE" => timezones,
+    "VEVENT" => events,
+    "VALARM" => alarms,
+    "VTODO" => todos,
+    "VJOURNAL" => journals,
+    "VFREEBUSY" => free_busys,
+    "VAVAILABILITY" => availabilities
+);
@@ -0,0 +1,114 @@
+//! jCard ([RFC7095](https://tools.ietf.org/html/rfc7095)), the JSON format of vCard.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["jcard"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use ical::json::Jcard;
+//! use ical::parser::vcard::component::VcardContact;
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/vcard_input.vcf")
+//!     .unwrap());
+//!
+//! for contact in ical::VcardParser::new(buf) {
+//!     let jcard = contact.unwrap().to_jcard_string().unwrap();
+//!
+//!     let contact = VcardContact::from_jcard_str(&jcard).unwrap();
+//!
+//!     assert_eq!(contact.to_jcard_string().unwrap(), jcard);
+//! }
+//! ```
+
+// Internal mods
+use super::{component_from_json, component_to_json, expect_component, JsonError};
+use crate::parser::ical::component::IcalUnknownComponent;
+use crate::parser::vcard::component::VcardContact;
+use crate::property::Format;
+use serde_json::Value;
+
+/// Converts a contact to and from jCard.
+pub trait Jcard: Sized {
+    /// Convert the contact into jCard.
+    fn to_jcard(&self) -> Result<Value, JsonError>;
+
+    /// Build a contact from its jCard representation.
+    fn from_jcard(jcard: &Value) -> Result<Self, JsonError>;
+
+    /// Convert the contact into a jCard string.
+    fn to_jcard_string(&self) -> Result<String, JsonError> {
+        Ok(serde_json::to_string(&self.to_jcard()?)?)
+    }
+
+    /// Build a contact from a jCard string.
+    fn from_jcard_str(jcard: &str) -> Result<Self, JsonError> {
+        Self::from_jcard(&serde_json::from_str(jcard)?)
+    }
+}
+
+/// Convert an unknown sub-component of a contact, which jCard doesn't define, as a jCal one
+/// holding vCard properties.
+fn unknown_to_json(component: &IcalUnknownComponent) -> Result<Value, JsonError> {
+    let components = component
+        .sub_components
+        .iter()
+        .map(unknown_to_json)
+        .collect::<Result<Vec<Value>, JsonError>>()?;
+
+    component_to_json(
+        &component.name,
+        &component.properties,
+        components,
+        Format::Vcard,
+    )
+}
+
+fn unknown_from_json(json: &Value) -> Result<IcalUnknownComponent, JsonError> {
+    let (name, properties, components) = component_from_json(json, Format::Vcard)?;
+
+    let mut component = IcalUnknownComponent::new(&name);
+    component.properties = properties;
+    component.sub_components = components
+        .iter()
+        .map(unknown_from_json)
+        .collect::<Result<Vec<IcalUnknownComponent>, JsonError>>()?;
+
+    Ok(component)
+}
+
+/// The unknown sub-components are written as a third element of the contact array, as for
+/// the jCal components, only if there are some.
+impl Jcard for VcardContact {
+    fn to_jcard(&self) -> Result<Value, JsonError> {
+        let components = self
+            .unknown_components
+            .iter()
+            .map(unknown_to_json)
+            .collect::<Result<Vec<Value>, JsonError>>()?;
+
+        component_to_json("VCARD", &self.properties, components, Format::Vcard)
+    }
+
+    fn from_jcard(jcard: &Value) -> Result<VcardContact, JsonError> {
+        let (name, properties, components) = component_from_json(jcard, Format::Vcard)?;
+        expect_component(&name, "VCARD")?;
+
+        let mut contact = VcardContact::new();
+        contact.properties = properties;
+        contact.unknown_components = components
+            .iter()
+            .map(unknown_from_json)
+            .collect::<Result<Vec<IcalUnknownComponent>, JsonError>>()?;
+
+        Ok(contact)
+    }
+}
@@ -0,0 +1,162 @@
+//! The `VALARM` components, as JSCalendar `Alert` objects.
+
+// Internal mods
+use super::{
+    check_params, local_from_json, new_property, text_property, unsupported_property,
+    unsupported_value, utc_to_json, Unsupported,
+};
+use crate::json::JsonError;
+use crate::parser::ical::component::IcalAlarm;
+use crate::property::Format;
+use crate::value::{DateTime, Value as TypedValue};
+use serde_json::{Map, Value};
+
+/// The text of an alarm, when the component has no title.
+const DEFAULT_TEXT: &str = "Reminder";
+
+/// Convert an alarm into an alert, with its `UID` as identifier if it has one.
+///
+/// JSCalendar has no text for an alert, so the `DESCRIPTION` and the `SUMMARY` are only
+/// dropped silently if they repeat the title of the component.
+pub(super) fn alert_to_json(
+    alarm: &IcalAlarm,
+    title: Option<&str>,
+    unsupported: &mut Vec<Unsupported>,
+) -> Result<(Option<String>, Value), JsonError> {
+    let mut id = None;
+    let mut alert = Map::new();
+    alert.insert(String::from("@type"), Value::from("Alert"));
+    alert.insert(String::from("action"), Value::from("display"));
+
+    for property in &alarm.properties {
+        let name = property.name.to_uppercase();
+        let mut known_params: &[&str] = &[];
+
+        match (name.as_str(), property.typed_value(Format::Ical)) {
+            ("UID", Ok(TypedValue::Text(uid))) => id = Some(uid),
+            ("TRIGGER", Ok(TypedValue::Duration(offset))) => {
+                known_params = &["RELATED"];
+                let mut trigger =
+                    serde_json::json!({"@type": "OffsetTrigger", "offset": offset.to_string()});
+                if super::param(property, "RELATED").is_some_and(|r| r.eq_ignore_ascii_case("END"))
+                {
+                    trigger["relativeTo"] = Value::from("end");
+                }
+                alert.insert(String::from("trigger"), trigger);
+            }
+            ("TRIGGER", Ok(TypedValue::DateTime(DateTime::Utc(utc)))) => {
+                alert.insert(
+                    String::from("trigger"),
+                    serde_json::json!({"@type": "AbsoluteTrigger", "when": utc_to_json(utc)}),
+                );
+            }
+            ("ACTION", Ok(TypedValue::Text(action))) => {
+                // The other actions, such as `AUDIO`, are kept as the default `display` one.
+                let action = action.to_lowercase();
+                if action != "display" && action != "email" {
+                    unsupported.push(unsupported_value("VALARM", property));
+                    continue;
+                }
+                alert.insert(String::from("action"), Value::String(action));
+            }
+            ("ACKNOWLEDGED", Ok(TypedValue::DateTime(DateTime::Utc(utc)))) => {
+                alert.insert(String::from("acknowledged"), utc_to_json(utc));
+            }
+            ("DESCRIPTION", Ok(TypedValue::Text(text)))
+            | ("SUMMARY", Ok(TypedValue::Text(text)))
+                if Some(text.as_str()) == title || text.eq_ignore_ascii_case(DEFAULT_TEXT) => {}
+            _ => {
+                unsupported.push(unsupported_property("VALARM", &name));
+                continue;
+            }
+        }
+
+        check_params(property, known_params, "VALARM", unsupported);
+    }
+
+    for unknown in &alarm.unknown_components {
+        unsupported.push(Unsupported::Component {
+            name: unknown.name.clone(),
+        });
+    }
+
+    Ok((id, Value::Object(alert)))
+}
+
+/// Build an alarm from an alert, its text being the title of the component.
+pub(super) fn alert_from_json(
+    id: &str,
+    json: &Value,
+    title: Option<&str>,
+    unsupported: &mut Vec<Unsupported>,
+) -> Result<IcalAlarm, JsonError> {
+    let invalid = || JsonError::invalid("alert", json);
+    let alert: &Map<String, Value> = json.as_object().ok_or_else(invalid)?;
+    let action = alert
+        .get("action")
+        .and_then(Value::as_str)
+        .unwrap_or("display");
+
+    let mut alarm = IcalAlarm::new();
+    // The alarms without a `UID` are numbered.
+    if !id.bytes().all(|b| b.is_ascii_digit()) {
+        alarm.properties.push(text_property("UID", id));
+    }
+    alarm
+        .properties
+        .push(new_property("ACTION", action.to_uppercase(), Vec::new()));
+
+    for (key, value) in alert {
+        match key.as_str() {
+            "@type" | "action" => {}
+            "trigger" => {
+                let trigger = value.as_object().ok_or_else(invalid)?;
+                let property = match trigger.get("@type").and_then(Value::as_str) {
+                    Some("AbsoluteTrigger") => {
+                        let when = local_from_json(trigger.get("when").ok_or_else(invalid)?)?;
+                        new_property(
+                            "TRIGGER",
+                            DateTime::Utc(when).to_string(),
+                            vec![(String::from("VALUE"), vec![String::from("DATE-TIME")])],
+                        )
+                    }
+                    Some("OffsetTrigger") => {
+                        let offset = trigger
+                            .get("offset")
+                            .and_then(Value::as_str)
+                            .ok_or_else(invalid)?;
+                        let params = match trigger.get("relativeTo").and_then(Value::as_str) {
+                            Some("end") => {
+                                vec![(String::from("RELATED"), vec![String::from("END")])]
+                            }
+                            _ => Vec::new(),
+                        };
+                        new_property("TRIGGER", offset.to_string(), params)
+                    }
+                    _ => {
+                        unsupported.push(unsupported_property("Alert", key));
+                        continue;
+                    }
+                };
+                alarm.properties.push(property);
+            }
+            "acknowledged" => {
+                let utc = local_from_json(value)?;
+                alarm.properties.push(new_property(
+                    "ACKNOWLEDGED",
+                    DateTime::Utc(utc).to_string(),
+                    Vec::new(),
+                ));
+            }
+            _ => unsupported.push(unsupported_property("Alert", key)),
+        }
+    }
+
+    let text = title.unwrap_or(DEFAULT_TEXT);
+    alarm.properties.push(text_property("DESCRIPTION", text));
+    if action == "email" {
+        alarm.properties.push(text_property("SUMMARY", text));
+    }
+
+    Ok(alarm)
+}
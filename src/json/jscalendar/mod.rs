@@ -0,0 +1,1498 @@
+//! JSCalendar ([RFC8984](https://tools.ietf.org/html/rfc8984)), the JSON format of the events
+//! and tasks used by JMAP.
+//!
+//! A `VEVENT` becomes an `Event` object and a `VTODO` a `Task` one, following the mapping of
+//! [draft-ietf-calext-jscalendar-icalendar](https://datatracker.ietf.org/doc/draft-ietf-calext-jscalendar-icalendar/):
+//!
+//! * the `DTSTART` gives the `start`, in the local time of its `timeZone`, and the `DTEND` or
+//!   the `DURATION` the `duration`. An all-day event starts at midnight with
+//!   `showWithoutTime`.
+//! * the `RRULE` and `EXRULE` are the `recurrenceRules` and `excludedRecurrenceRules`.
+//! * the `RDATE`, the `EXDATE` and the components of the calendar sharing the `UID` and
+//!   overriding an instance through a `RECURRENCE-ID` are the `recurrenceOverrides`, an
+//!   override only holding what differs from the main component.
+//! * the `ORGANIZER` and the `ATTENDEE` are the `participants` and the `replyTo`.
+//! * the `VALARM` are the `alerts`, the `LOCATION` and `GEO` the `locations`, the
+//!   `CONFERENCE` the `virtualLocations`.
+//!
+//! What JSCalendar can't represent, such as a `COMMENT`, the `REPEAT` of an alarm or an
+//! `X-` property, is left out of the conversion and reported in its `unsupported` list. The
+//! JSCalendar properties which can't be converted back are reported the same way.
+//!
+//! The date-times of a rule, an override or a due date are converted into the time zone of
+//! the start, using the `VTIMEZONE` components of the calendar or, with the `tzdb` feature,
+//! the IANA database. As JSCalendar only references the time zones by name, no `VTIMEZONE` is
+//! written back: the `UNTIL` of a zoned rule is converted to UTC with the IANA database when
+//! available, and is otherwise written in the local time of the start.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["jscalendar"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use ical::json::jscalendar::{from_group, to_group};
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/ical_example_1.ics")
+//!     .unwrap());
+//!
+//! let calendar = ical::IcalParser::new(buf).next().unwrap().unwrap();
+//! let group = to_group(&calendar).unwrap();
+//!
+//! for unsupported in &group.unsupported {
+//!     println!("{}", unsupported);
+//! }
+//!
+//! let calendar = from_group(&group.result).unwrap().result;
+//! assert_eq!(calendar.events.len(), 1);
+//! ```
+
+mod alerts;
+mod participants;
+mod recurrence;
+
+// Sys mods
+use std::fmt;
+
+// Internal mods
+use self::alerts::{alert_from_json, alert_to_json};
+use self::participants::{participants_from_json, participants_to_json};
+use self::recurrence::{rule_from_json, rule_to_json};
+use super::JsonError;
+use crate::parser::ical::component::{
+    IcalAlarm, IcalCalendar, IcalEvent, IcalTodo, IcalUnknownComponent,
+};
+use crate::parser::Component;
+use crate::property::{escape_text, Format, Property};
+use crate::timezone::TimeZones;
+use crate::value::{DateTime, Duration, PeriodEnd, Value as TypedValue};
+use chrono::{NaiveDateTime, NaiveTime};
+use serde_json::{Map, Value};
+
+/// A part of a component which the conversion can't represent, left out of its result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unsupported {
+    /// A component, such as a `VJOURNAL`.
+    Component { name: String },
+    /// A property of a component, or a JSCalendar property of an object.
+    Property { component: String, name: String },
+    /// A parameter of a property.
+    Parameter {
+        component: String,
+        property: String,
+        name: String,
+    },
+    /// A value which can't be converted.
+    Value {
+        component: String,
+        property: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Unsupported::Component { name } => write!(f, "Unsupported {} component.", name),
+            Unsupported::Property { component, name } => {
+                write!(f, "Unsupported {} property in {}.", name, component)
+            }
+            Unsupported::Parameter {
+                component,
+                property,
+                name,
+            } => write!(
+                f,
+                "Unsupported {} parameter of {} in {}.",
+                name, property, component
+            ),
+            Unsupported::Value {
+                component,
+                property,
+                value,
+            } => write!(
+                f,
+                "Unsupported value {:?} of {} in {}.",
+                value, property, component
+            ),
+        }
+    }
+}
+
+/// The result of a conversion, with what it left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Converted<T> {
+    pub result: T,
+    pub unsupported: Vec<Unsupported>,
+}
+
+/// Converts an event or a task to and from JSCalendar.
+pub trait JsCalendar: Sized {
+    /// Convert the component into a JSCalendar object, with the components of `calendar`
+    /// overriding some of its instances.
+    fn to_jscalendar(&self, calendar: &IcalCalendar) -> Result<Converted<Value>, JsonError>;
+
+    /// Build a component from a JSCalendar object, followed by the components overriding
+    /// some of its instances.
+    fn from_jscalendar(json: &Value) -> Result<Converted<Vec<Self>>, JsonError>;
+}
+
+/// The kind of a JSCalendar object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Event,
+    Task,
+}
+
+impl Kind {
+    fn component(self) -> &'static str {
+        match self {
+            Kind::Event => "VEVENT",
+            Kind::Task => "VTODO",
+        }
+    }
+
+    fn type_name(self) -> &'static str {
+        match self {
+            Kind::Event => "Event",
+            Kind::Task => "Task",
+        }
+    }
+}
+
+/// The time zone of a date-time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Zone {
+    Floating,
+    Utc,
+    Named(String),
+}
+
+/// The time zone name of the date-times in UTC.
+const UTC_TIME_ZONE: &str = "Etc/UTC";
+
+impl Zone {
+    fn from_json(time_zone: Option<&str>) -> Zone {
+        match time_zone {
+            None => Zone::Floating,
+            Some(UTC_TIME_ZONE) => Zone::Utc,
+            Some(name) => Zone::Named(name.to_string()),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Zone::Floating => Value::Null,
+            Zone::Utc => Value::from(UTC_TIME_ZONE),
+            Zone::Named(name) => Value::from(name.as_str()),
+        }
+    }
+}
+
+/// A `DATE` or `DATE-TIME` value, a date being at midnight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Instant {
+    local: NaiveDateTime,
+    zone: Zone,
+    date: bool,
+}
+
+impl Instant {
+    fn from_value(value: &TypedValue) -> Option<Instant> {
+        Some(match value {
+            TypedValue::Date(date) => Instant {
+                local: date.and_time(NaiveTime::MIN),
+                zone: Zone::Floating,
+                date: true,
+            },
+            TypedValue::DateTime(date_time) => Instant::from_date_time(date_time),
+            _ => return None,
+        })
+    }
+
+    fn from_date_time(date_time: &DateTime) -> Instant {
+        let zone = match date_time {
+            DateTime::Floating(_) => Zone::Floating,
+            DateTime::Utc(_) => Zone::Utc,
+            DateTime::Zoned { tzid, .. } => Zone::Named(tzid.clone()),
+        };
+
+        Instant {
+            local: date_time.naive(),
+            zone,
+            date: false,
+        }
+    }
+
+    fn to_date_time(&self) -> DateTime {
+        match &self.zone {
+            Zone::Floating => DateTime::Floating(self.local),
+            Zone::Utc => DateTime::Utc(self.local),
+            Zone::Named(tzid) => DateTime::Zoned {
+                date_time: self.local,
+                tzid: tzid.clone(),
+            },
+        }
+    }
+
+    /// Return the local time of the instant in `zone`, converting it with the time zones of
+    /// the calendar if needed. A floating time is the same in all the zones.
+    fn local_in(&self, zone: &Zone, zones: &TimeZones) -> Option<NaiveDateTime> {
+        if self.zone == *zone || self.zone == Zone::Floating || *zone == Zone::Floating {
+            return Some(self.local);
+        }
+
+        let utc = zones.to_utc(&self.to_date_time()).ok()?.naive_utc();
+        match zone {
+            Zone::Utc => Some(utc),
+            Zone::Named(tzid) => match zones.rules(tzid).ok()? {
+                Some(rules) => Some(rules.from_utc(utc)),
+                None => tzdb_from_utc(tzid, utc),
+            },
+            Zone::Floating => None,
+        }
+    }
+
+    /// Write the instant as the value of the property `name`.
+    fn to_property(&self, name: &str) -> Property {
+        let mut property = Property::new();
+        property.name = name.to_string();
+
+        let value = if self.date {
+            property.params = Some(vec![(String::from("VALUE"), vec![String::from("DATE")])]);
+            TypedValue::Date(self.local.date()).to_string()
+        } else {
+            if let Zone::Named(tzid) = &self.zone {
+                property.params = Some(vec![(String::from("TZID"), vec![tzid.clone()])]);
+            }
+            self.to_date_time().to_string()
+        };
+        property.value = Some(value);
+
+        property
+    }
+}
+
+/// Return the local time of `utc` in the IANA zone `tzid`.
+#[cfg(feature = "tzdb")]
+fn tzdb_from_utc(tzid: &str, utc: NaiveDateTime) -> Option<NaiveDateTime> {
+    use chrono::TimeZone;
+
+    let tz = crate::timezone::tzdb::find(tzid)?;
+    Some(tz.from_utc_datetime(&utc).naive_local())
+}
+
+#[cfg(not(feature = "tzdb"))]
+fn tzdb_from_utc(_tzid: &str, _utc: NaiveDateTime) -> Option<NaiveDateTime> {
+    None
+}
+
+/// Write a local date-time (`2024-01-02T12:00:00`).
+fn local_to_json(local: NaiveDateTime) -> Value {
+    Value::String(local.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// Read a local date-time, or a UTC one ending with `Z`.
+fn local_from_json(json: &Value) -> Result<NaiveDateTime, JsonError> {
+    json.as_str()
+        .map(|text| text.trim_end_matches('Z'))
+        .and_then(|text| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f").ok())
+        .ok_or_else(|| JsonError::invalid("date-time", json))
+}
+
+fn utc_to_json(utc: NaiveDateTime) -> Value {
+    Value::String(utc.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// Return the duration between two local times, in days if they are dates.
+fn duration_between(start: NaiveDateTime, end: NaiveDateTime) -> Duration {
+    let delta = end - start;
+    let seconds = delta.num_seconds().unsigned_abs();
+
+    Duration {
+        negative: delta.num_seconds() < 0,
+        weeks: 0,
+        days: (seconds / 86_400) as u32,
+        hours: (seconds % 86_400 / 3600) as u32,
+        minutes: (seconds % 3600 / 60) as u32,
+        seconds: (seconds % 60) as u32,
+    }
+}
+
+/// Return the first value of the parameter `name`.
+fn param<'p>(property: &'p Property, name: &str) -> Option<&'p str> {
+    property
+        .params
+        .as_ref()?
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .and_then(|(_, values)| values.first())
+        .map(String::as_str)
+}
+
+/// Report the parameters of `property` which aren't in `known`. The `VALUE` parameter is
+/// given by the converted value.
+fn check_params(
+    property: &Property,
+    known: &[&str],
+    component: &str,
+    unsupported: &mut Vec<Unsupported>,
+) {
+    for (name, _) in property.params.iter().flatten() {
+        let name = name.to_uppercase();
+        if name != "VALUE" && !known.contains(&name.as_str()) {
+            unsupported.push(Unsupported::Parameter {
+                component: component.to_string(),
+                property: property.name.to_uppercase(),
+                name,
+            });
+        }
+    }
+}
+
+fn unsupported_value(component: &str, property: &Property) -> Unsupported {
+    Unsupported::Value {
+        component: component.to_string(),
+        property: property.name.to_uppercase(),
+        value: property.value.clone().unwrap_or_default(),
+    }
+}
+
+fn unsupported_property(component: &str, name: &str) -> Unsupported {
+    Unsupported::Property {
+        component: component.to_string(),
+        name: name.to_string(),
+    }
+}
+
+/// Build a property holding `value`, with the given parameters.
+fn new_property(name: &str, value: String, params: Vec<(String, Vec<String>)>) -> Property {
+    let mut property = Property::new();
+    property.name = name.to_string();
+    property.value = Some(value);
+    if !params.is_empty() {
+        property.params = Some(params);
+    }
+
+    property
+}
+
+fn text_property(name: &str, text: &str) -> Property {
+    new_property(name, escape_text(text, Format::Ical), Vec::new())
+}
+
+/// Convert the `STATUS` of an event or of a task, `None` for an unknown one.
+fn status_to_json(kind: Kind, status: &str) -> Option<(&'static str, Value)> {
+    let status = status.to_uppercase();
+    let name = match (kind, status.as_str()) {
+        (Kind::Event, "TENTATIVE") | (Kind::Event, "CONFIRMED") => "status",
+        (Kind::Task, "NEEDS-ACTION") | (Kind::Task, "IN-PROCESS") | (Kind::Task, "COMPLETED") => {
+            "progress"
+        }
+        (_, "CANCELLED") => match kind {
+            Kind::Event => "status",
+            Kind::Task => "progress",
+        },
+        _ => return None,
+    };
+
+    Some((name, Value::String(status.to_lowercase())))
+}
+
+/// The overridden instances, and the keys an override can't change.
+const RECURRENCE_KEYS: &[&str] = &[
+    "@type",
+    "uid",
+    "method",
+    "recurrenceRules",
+    "excludedRecurrenceRules",
+    "recurrenceOverrides",
+    "recurrenceId",
+    "recurrenceIdTimeZone",
+];
+
+/// Convert the properties and the alarms of an event or a task into a JSCalendar object.
+fn component_to_json(
+    kind: Kind,
+    properties: &[Property],
+    alarms: &[IcalAlarm],
+    unknown_components: &[IcalUnknownComponent],
+    zones: &TimeZones,
+    unsupported: &mut Vec<Unsupported>,
+) -> Result<Map<String, Value>, JsonError> {
+    let component = kind.component();
+    let instant = |name: &str| -> Result<Option<Instant>, JsonError> {
+        match properties
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+        {
+            Some(property) => Ok(Instant::from_value(&property.typed_value(Format::Ical)?)),
+            None => Ok(None),
+        }
+    };
+
+    let start = instant("DTSTART")?;
+    let zone = match (&start, kind) {
+        (Some(start), _) => start.zone.clone(),
+        (None, Kind::Task) => instant("DUE")?.map_or(Zone::Floating, |due| due.zone),
+        (None, Kind::Event) => Zone::Floating,
+    };
+    let has_last_modified = properties
+        .iter()
+        .any(|p| p.name.eq_ignore_ascii_case("LAST-MODIFIED"));
+    let title = properties
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case("SUMMARY"))
+        .and_then(|p| p.typed_value(Format::Ical).ok());
+    let title = match &title {
+        Some(TypedValue::Text(title)) => Some(title.as_str()),
+        _ => None,
+    };
+
+    let mut object = Map::new();
+    object.insert(String::from("@type"), Value::from(kind.type_name()));
+
+    let mut keywords = Map::new();
+    let mut locations = Map::new();
+    let mut virtual_locations = Map::new();
+    let mut links = Map::new();
+    let mut related_to = Map::new();
+    let mut rules = Vec::new();
+    let mut excluded_rules = Vec::new();
+    let mut overrides = Map::new();
+
+    for property in properties {
+        let name = property.name.to_uppercase();
+        let values = match property.typed_values(Format::Ical) {
+            Ok(values) => values,
+            // An empty property holds nothing to convert.
+            Err(_) if property.value.is_none() => continue,
+            Err(_) => {
+                unsupported.push(unsupported_value(component, property));
+                continue;
+            }
+        };
+        let mut known_params: &[&str] = &[];
+
+        match (name.as_str(), values.as_slice()) {
+            ("UID", [TypedValue::Text(uid)]) => {
+                object.insert(String::from("uid"), Value::from(uid.as_str()));
+            }
+            ("SUMMARY", [TypedValue::Text(text)]) => {
+                object.insert(String::from("title"), Value::from(text.as_str()));
+            }
+            ("DESCRIPTION", [TypedValue::Text(text)]) => {
+                object.insert(String::from("description"), Value::from(text.as_str()));
+            }
+            ("COLOR", [TypedValue::Text(text)]) => {
+                object.insert(String::from("color"), Value::from(text.as_str()));
+            }
+            ("SEQUENCE", [TypedValue::Integer(integer)]) => {
+                object.insert(String::from("sequence"), Value::from(*integer));
+            }
+            ("PRIORITY", [TypedValue::Integer(integer)]) => {
+                object.insert(String::from("priority"), Value::from(*integer));
+            }
+            ("PERCENT-COMPLETE", [TypedValue::Integer(integer)]) if kind == Kind::Task => {
+                object.insert(String::from("percentComplete"), Value::from(*integer));
+            }
+            ("CREATED", [TypedValue::DateTime(DateTime::Utc(utc))]) => {
+                object.insert(String::from("created"), utc_to_json(*utc));
+            }
+            ("LAST-MODIFIED", [TypedValue::DateTime(DateTime::Utc(utc))]) => {
+                object.insert(String::from("updated"), utc_to_json(*utc));
+            }
+            ("DTSTAMP", [TypedValue::DateTime(DateTime::Utc(utc))]) if !has_last_modified => {
+                object.insert(String::from("updated"), utc_to_json(*utc));
+            }
+            ("COMPLETED", [TypedValue::DateTime(DateTime::Utc(utc))]) if kind == Kind::Task => {
+                object.insert(String::from("progressUpdated"), utc_to_json(*utc));
+            }
+            ("DTSTART", _) if start.is_some() => {
+                known_params = &["TZID"];
+                let start = start.as_ref().unwrap();
+                object.insert(String::from("start"), local_to_json(start.local));
+                if zone != Zone::Floating {
+                    object.insert(String::from("timeZone"), zone.to_json());
+                }
+                if start.date {
+                    object.insert(String::from("showWithoutTime"), Value::Bool(true));
+                }
+            }
+            ("DTEND", [value]) if kind == Kind::Event => {
+                known_params = &["TZID"];
+                let end = Instant::from_value(value)
+                    .and_then(|end| Some((start.as_ref()?.local, end.local_in(&zone, zones)?)));
+                match end {
+                    Some((start, end)) => {
+                        object.insert(
+                            String::from("duration"),
+                            Value::String(duration_between(start, end).to_string()),
+                        );
+                    }
+                    None => unsupported.push(unsupported_value(component, property)),
+                }
+            }
+            ("DURATION", [TypedValue::Duration(duration)]) => {
+                let key = match kind {
+                    Kind::Event => "duration",
+                    Kind::Task => "estimatedDuration",
+                };
+                object.insert(String::from(key), Value::String(duration.to_string()));
+            }
+            ("DUE", [value]) if kind == Kind::Task => {
+                known_params = &["TZID"];
+                match Instant::from_value(value).and_then(|due| due.local_in(&zone, zones)) {
+                    Some(due) => {
+                        object.insert(String::from("due"), local_to_json(due));
+                        if zone != Zone::Floating {
+                            object.insert(String::from("timeZone"), zone.to_json());
+                        }
+                    }
+                    None => unsupported.push(unsupported_value(component, property)),
+                }
+            }
+            ("RECURRENCE-ID", [value]) => {
+                known_params = &["TZID", "RANGE"];
+                match Instant::from_value(value).and_then(|id| id.local_in(&zone, zones)) {
+                    Some(id) if param(property, "RANGE").is_none() => {
+                        object.insert(String::from("recurrenceId"), local_to_json(id));
+                        if let Zone::Named(_) | Zone::Utc = zone {
+                            object.insert(String::from("recurrenceIdTimeZone"), zone.to_json());
+                        }
+                    }
+                    _ => unsupported.push(unsupported_value(component, property)),
+                }
+            }
+            ("STATUS", [TypedValue::Text(status)]) => match status_to_json(kind, status) {
+                Some((key, status)) => {
+                    object.insert(String::from(key), status);
+                }
+                None => unsupported.push(unsupported_value(component, property)),
+            },
+            ("CLASS", [TypedValue::Text(class)]) => {
+                let privacy = match class.to_uppercase().as_str() {
+                    "PUBLIC" => "public",
+                    "PRIVATE" => "private",
+                    "CONFIDENTIAL" => "secret",
+                    _ => {
+                        unsupported.push(unsupported_value(component, property));
+                        continue;
+                    }
+                };
+                object.insert(String::from("privacy"), Value::from(privacy));
+            }
+            ("TRANSP", [TypedValue::Text(transparency)]) => {
+                let status = match transparency.to_uppercase().as_str() {
+                    "OPAQUE" => "busy",
+                    "TRANSPARENT" => "free",
+                    _ => {
+                        unsupported.push(unsupported_value(component, property));
+                        continue;
+                    }
+                };
+                object.insert(String::from("freeBusyStatus"), Value::from(status));
+            }
+            ("CATEGORIES", values) => {
+                for value in values {
+                    if let TypedValue::Text(keyword) = value {
+                        keywords.insert(keyword.clone(), Value::Bool(true));
+                    }
+                }
+            }
+            ("LOCATION", [TypedValue::Text(text)]) => {
+                let id = (locations.len() + 1).to_string();
+                locations.insert(id, serde_json::json!({"@type": "Location", "name": text}));
+            }
+            ("GEO", [TypedValue::Float(latitude), TypedValue::Float(longitude)]) => {
+                let location = match locations.values_mut().next() {
+                    Some(Value::Object(location)) if !location.contains_key("coordinates") => {
+                        location
+                    }
+                    _ => {
+                        let id = (locations.len() + 1).to_string();
+                        locations.insert(id.clone(), serde_json::json!({"@type": "Location"}));
+                        locations[&id].as_object_mut().unwrap()
+                    }
+                };
+                location.insert(
+                    String::from("coordinates"),
+                    Value::String(format!("geo:{},{}", latitude, longitude)),
+                );
+            }
+            ("CONFERENCE", [TypedValue::Uri(uri)]) => {
+                known_params = &["LABEL"];
+                let mut location = serde_json::json!({"@type": "VirtualLocation", "uri": uri});
+                if let Some(label) = param(property, "LABEL") {
+                    location["name"] = Value::from(label);
+                }
+                let id = (virtual_locations.len() + 1).to_string();
+                virtual_locations.insert(id, location);
+            }
+            ("URL", [TypedValue::Uri(uri)]) => {
+                let id = (links.len() + 1).to_string();
+                links.insert(id, serde_json::json!({"@type": "Link", "href": uri}));
+            }
+            ("ATTACH", [TypedValue::Uri(uri)]) => {
+                known_params = &["FMTTYPE"];
+                let mut link =
+                    serde_json::json!({"@type": "Link", "href": uri, "rel": "enclosure"});
+                if let Some(content_type) = param(property, "FMTTYPE") {
+                    link["contentType"] = Value::from(content_type);
+                }
+                let id = (links.len() + 1).to_string();
+                links.insert(id, link);
+            }
+            ("RELATED-TO", [TypedValue::Text(uid)]) => {
+                known_params = &["RELTYPE"];
+                let relation = param(property, "RELTYPE")
+                    .unwrap_or("PARENT")
+                    .to_lowercase();
+                related_to.insert(
+                    uid.clone(),
+                    serde_json::json!({"@type": "Relation", "relation": {relation: true}}),
+                );
+            }
+            ("RRULE", [TypedValue::Recur(rule)]) | ("EXRULE", [TypedValue::Recur(rule)]) => {
+                let rule = rule_to_json(rule, &zone, zones, component, property, unsupported);
+                if name == "RRULE" {
+                    rules.push(rule);
+                } else {
+                    excluded_rules.push(rule);
+                }
+            }
+            ("RDATE", values) | ("EXDATE", values) => {
+                known_params = &["TZID"];
+                for value in values {
+                    let (instant, patch) = match value {
+                        TypedValue::Period(period) if name == "RDATE" => {
+                            let start = Instant::from_date_time(&period.start);
+                            let duration = match &period.end {
+                                PeriodEnd::Duration(duration) => *duration,
+                                PeriodEnd::DateTime(end) => {
+                                    duration_between(start.local, end.naive())
+                                }
+                            };
+                            (
+                                Some(start),
+                                serde_json::json!({"duration": duration.to_string()}),
+                            )
+                        }
+                        _ if name == "RDATE" => (Instant::from_value(value), serde_json::json!({})),
+                        _ => (
+                            Instant::from_value(value),
+                            serde_json::json!({"excluded": true}),
+                        ),
+                    };
+
+                    match instant.and_then(|instant| instant.local_in(&zone, zones)) {
+                        Some(local) => {
+                            overrides.insert(local.format("%Y-%m-%dT%H:%M:%S").to_string(), patch);
+                        }
+                        None => unsupported.push(Unsupported::Value {
+                            component: component.to_string(),
+                            property: name.clone(),
+                            value: value.to_string(),
+                        }),
+                    }
+                }
+            }
+            ("ORGANIZER", _) | ("ATTENDEE", _) => continue,
+            _ => {
+                unsupported.push(unsupported_property(component, &name));
+                continue;
+            }
+        }
+
+        check_params(property, known_params, component, unsupported);
+    }
+
+    if !keywords.is_empty() {
+        object.insert(String::from("keywords"), Value::Object(keywords));
+    }
+    if !locations.is_empty() {
+        object.insert(String::from("locations"), Value::Object(locations));
+    }
+    if !virtual_locations.is_empty() {
+        object.insert(
+            String::from("virtualLocations"),
+            Value::Object(virtual_locations),
+        );
+    }
+    if !links.is_empty() {
+        object.insert(String::from("links"), Value::Object(links));
+    }
+    if !related_to.is_empty() {
+        object.insert(String::from("relatedTo"), Value::Object(related_to));
+    }
+
+    let (participants, reply_to) = participants_to_json(properties, component, unsupported);
+    if !participants.is_empty() {
+        object.insert(String::from("participants"), Value::Object(participants));
+    }
+    if !reply_to.is_empty() {
+        object.insert(String::from("replyTo"), Value::Object(reply_to));
+    }
+
+    let mut alerts = Map::new();
+    for alarm in alarms {
+        let (id, alert) = alert_to_json(alarm, title, unsupported)?;
+        let id = id.unwrap_or_else(|| (alerts.len() + 1).to_string());
+        alerts.insert(id, alert);
+    }
+    if !alerts.is_empty() {
+        object.insert(String::from("alerts"), Value::Object(alerts));
+    }
+
+    if !rules.is_empty() {
+        object.insert(String::from("recurrenceRules"), Value::Array(rules));
+    }
+    if !excluded_rules.is_empty() {
+        object.insert(
+            String::from("excludedRecurrenceRules"),
+            Value::Array(excluded_rules),
+        );
+    }
+    if !overrides.is_empty() {
+        object.insert(
+            String::from("recurrenceOverrides"),
+            Value::Object(overrides),
+        );
+    }
+
+    for unknown in unknown_components {
+        unsupported.push(Unsupported::Component {
+            name: unknown.name.clone(),
+        });
+    }
+
+    Ok(object)
+}
+
+/// Return the properties of `object` which differ from the ones of `main`, removing the ones
+/// it doesn't have.
+fn patch(main: &Map<String, Value>, object: &Map<String, Value>) -> Map<String, Value> {
+    let mut patch = Map::new();
+
+    for (key, value) in object {
+        if !RECURRENCE_KEYS.contains(&key.as_str()) && main.get(key) != Some(value) {
+            patch.insert(key.clone(), value.clone());
+        }
+    }
+    for key in main.keys() {
+        if !RECURRENCE_KEYS.contains(&key.as_str()) && !object.contains_key(key) {
+            patch.insert(key.clone(), Value::Null);
+        }
+    }
+
+    patch
+}
+
+/// Apply a patch to a copy of `main`, following the JSON pointers of its keys.
+fn apply_patch(
+    main: &Map<String, Value>,
+    patch: &Map<String, Value>,
+) -> Result<Map<String, Value>, JsonError> {
+    let mut object = main.clone();
+    object.retain(|key, _| !RECURRENCE_KEYS.contains(&key.as_str()));
+
+    for (pointer, value) in patch {
+        let path: Vec<&str> = pointer.trim_start_matches('/').split('/').collect();
+        let (last, parents) = path.split_last().unwrap_or((&"", &[]));
+
+        let mut target = &mut object;
+        for parent in parents {
+            target = target
+                .entry(parent.to_string())
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .ok_or_else(|| JsonError::Invalid {
+                    kind: "patch",
+                    found: pointer.clone(),
+                })?;
+        }
+
+        if value.is_null() {
+            target.remove(*last);
+        } else {
+            target.insert(last.to_string(), value.clone());
+        }
+    }
+
+    Ok(object)
+}
+
+/// Convert an event or a task, with the components overriding some of its instances.
+fn to_jscalendar(
+    kind: Kind,
+    parts: (&[Property], &[IcalAlarm], &[IcalUnknownComponent]),
+    overrides: Vec<(&[Property], &[IcalAlarm], &[IcalUnknownComponent])>,
+    calendar: &IcalCalendar,
+) -> Result<Converted<Value>, JsonError> {
+    let mut unsupported = Vec::new();
+    let zones = TimeZones::new(calendar);
+    let (properties, alarms, unknown_components) = parts;
+    let mut object = component_to_json(
+        kind,
+        properties,
+        alarms,
+        unknown_components,
+        &zones,
+        &mut unsupported,
+    )?;
+
+    if let Some(method) = calendar
+        .get_property("METHOD")
+        .and_then(|p| p.value.as_ref())
+    {
+        object.insert(String::from("method"), Value::String(method.to_lowercase()));
+    }
+
+    if object.contains_key("recurrenceId") {
+        return Ok(Converted {
+            result: Value::Object(object),
+            unsupported,
+        });
+    }
+
+    let mut recurrence_overrides = match object.remove("recurrenceOverrides") {
+        Some(Value::Object(overrides)) => overrides,
+        _ => Map::new(),
+    };
+    for (properties, alarms, unknown_components) in overrides {
+        let instance = component_to_json(
+            kind,
+            properties,
+            alarms,
+            unknown_components,
+            &zones,
+            &mut unsupported,
+        )?;
+        if let Some(Value::String(id)) = instance.get("recurrenceId") {
+            recurrence_overrides.insert(id.clone(), Value::Object(patch(&object, &instance)));
+        }
+    }
+    if !recurrence_overrides.is_empty() {
+        object.insert(
+            String::from("recurrenceOverrides"),
+            Value::Object(recurrence_overrides),
+        );
+    }
+
+    Ok(Converted {
+        result: Value::Object(object),
+        unsupported,
+    })
+}
+
+/// Convert a JSCalendar object into the properties and the alarms of a component.
+fn component_from_json(
+    kind: Kind,
+    object: &Map<String, Value>,
+    unsupported: &mut Vec<Unsupported>,
+) -> Result<(Vec<Property>, Vec<IcalAlarm>), JsonError> {
+    let type_name = kind.type_name();
+    let invalid = |key: &str| JsonError::Invalid {
+        kind: "property",
+        found: format!("{}: {}", key, object[key]),
+    };
+    let text = |key: &str| object[key].as_str().ok_or_else(|| invalid(key));
+
+    let zone = Zone::from_json(object.get("timeZone").and_then(Value::as_str));
+    let date = object.get("showWithoutTime") == Some(&Value::Bool(true));
+    let instant = |key: &str| -> Result<Instant, JsonError> {
+        let local = local_from_json(&object[key])?;
+        Ok(Instant {
+            local,
+            zone: zone.clone(),
+            date: date && local.time() == NaiveTime::MIN,
+        })
+    };
+    let title = object.get("title").and_then(Value::as_str);
+
+    let mut properties = Vec::new();
+    let mut alarms = Vec::new();
+
+    for (key, value) in object {
+        match key.as_str() {
+            "@type" | "timeZone" | "showWithoutTime" | "method" | "recurrenceIdTimeZone" => {}
+            "uid" => properties.push(text_property("UID", text(key)?)),
+            "title" => properties.push(text_property("SUMMARY", text(key)?)),
+            "description" => properties.push(text_property("DESCRIPTION", text(key)?)),
+            "color" => properties.push(text_property("COLOR", text(key)?)),
+            "sequence" | "priority" | "percentComplete" => {
+                let name = match key.as_str() {
+                    "sequence" => "SEQUENCE",
+                    "priority" => "PRIORITY",
+                    _ => "PERCENT-COMPLETE",
+                };
+                let integer = value.as_i64().ok_or_else(|| invalid(key))?;
+                properties.push(new_property(name, integer.to_string(), Vec::new()));
+            }
+            "created" | "updated" | "progressUpdated" => {
+                let name = match key.as_str() {
+                    "created" => "CREATED",
+                    "updated" => "DTSTAMP",
+                    _ => "COMPLETED",
+                };
+                let utc = local_from_json(value)?;
+                properties.push(new_property(
+                    name,
+                    DateTime::Utc(utc).to_string(),
+                    Vec::new(),
+                ));
+            }
+            "start" => properties.push(instant(key)?.to_property("DTSTART")),
+            "due" if kind == Kind::Task => properties.push(instant(key)?.to_property("DUE")),
+            "recurrenceId" => properties.push(instant(key)?.to_property("RECURRENCE-ID")),
+            "duration" | "estimatedDuration" => {
+                properties.push(new_property("DURATION", text(key)?.to_string(), Vec::new()))
+            }
+            "status" | "progress" => {
+                properties.push(new_property(
+                    "STATUS",
+                    text(key)?.to_uppercase(),
+                    Vec::new(),
+                ));
+            }
+            "privacy" => {
+                let class = match text(key)? {
+                    "public" => "PUBLIC",
+                    "private" => "PRIVATE",
+                    "secret" => "CONFIDENTIAL",
+                    _ => {
+                        unsupported.push(unsupported_property(type_name, key));
+                        continue;
+                    }
+                };
+                properties.push(new_property("CLASS", class.to_string(), Vec::new()));
+            }
+            "freeBusyStatus" => {
+                let transparency = if text(key)? == "free" {
+                    "TRANSPARENT"
+                } else {
+                    "OPAQUE"
+                };
+                properties.push(new_property("TRANSP", transparency.to_string(), Vec::new()));
+            }
+            "keywords" => {
+                let keywords: Vec<String> = value
+                    .as_object()
+                    .ok_or_else(|| invalid(key))?
+                    .keys()
+                    .map(|keyword| escape_text(keyword, Format::Ical))
+                    .collect();
+                properties.push(new_property("CATEGORIES", keywords.join(","), Vec::new()));
+            }
+            "locations" => {
+                let locations = value.as_object().ok_or_else(|| invalid(key))?;
+                for (index, location) in locations.values().enumerate() {
+                    if index > 0 {
+                        unsupported.push(unsupported_property(type_name, "locations"));
+                        break;
+                    }
+                    if let Some(name) = location.get("name").and_then(Value::as_str) {
+                        properties.push(text_property("LOCATION", name));
+                    }
+                    if let Some(coordinates) = location.get("coordinates").and_then(Value::as_str) {
+                        match coordinates.strip_prefix("geo:") {
+                            Some(geo) => properties.push(new_property(
+                                "GEO",
+                                geo.split(';')
+                                    .next()
+                                    .unwrap_or_default()
+                                    .replacen(',', ";", 1),
+                                Vec::new(),
+                            )),
+                            None => {
+                                unsupported.push(unsupported_property("Location", "coordinates"))
+                            }
+                        }
+                    }
+                }
+            }
+            "virtualLocations" => {
+                for location in value.as_object().ok_or_else(|| invalid(key))?.values() {
+                    let uri = location
+                        .get("uri")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| invalid(key))?;
+                    let mut params = vec![(String::from("VALUE"), vec![String::from("URI")])];
+                    if let Some(name) = location.get("name").and_then(Value::as_str) {
+                        params.push((String::from("LABEL"), vec![name.to_string()]));
+                    }
+                    properties.push(new_property("CONFERENCE", uri.to_string(), params));
+                }
+            }
+            "links" => {
+                for link in value.as_object().ok_or_else(|| invalid(key))?.values() {
+                    let href = link
+                        .get("href")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| invalid(key))?;
+                    if link.get("rel").and_then(Value::as_str) == Some("enclosure") {
+                        let params = match link.get("contentType").and_then(Value::as_str) {
+                            Some(content_type) => {
+                                vec![(String::from("FMTTYPE"), vec![content_type.to_string()])]
+                            }
+                            None => Vec::new(),
+                        };
+                        properties.push(new_property("ATTACH", href.to_string(), params));
+                    } else {
+                        properties.push(new_property("URL", href.to_string(), Vec::new()));
+                    }
+                }
+            }
+            "relatedTo" => {
+                for (uid, relation) in value.as_object().ok_or_else(|| invalid(key))? {
+                    let relations: Vec<&String> = relation
+                        .get("relation")
+                        .and_then(Value::as_object)
+                        .map(|relations| relations.keys().collect())
+                        .unwrap_or_default();
+                    let params = match relations.first() {
+                        Some(relation) if *relation != "parent" => {
+                            vec![(String::from("RELTYPE"), vec![relation.to_uppercase()])]
+                        }
+                        _ => Vec::new(),
+                    };
+                    properties.push(new_property(
+                        "RELATED-TO",
+                        escape_text(uid, Format::Ical),
+                        params,
+                    ));
+                }
+            }
+            "participants" | "replyTo" => {
+                if key == "participants" || !object.contains_key("participants") {
+                    properties.extend(participants_from_json(object, unsupported)?);
+                }
+            }
+            "alerts" => {
+                for (id, alert) in value.as_object().ok_or_else(|| invalid(key))? {
+                    alarms.push(alert_from_json(id, alert, title, unsupported)?);
+                }
+            }
+            "recurrenceRules" | "excludedRecurrenceRules" => {
+                let name = if key == "recurrenceRules" {
+                    "RRULE"
+                } else {
+                    "EXRULE"
+                };
+                for rule in value.as_array().ok_or_else(|| invalid(key))? {
+                    let rule = rule_from_json(rule, &zone, date)?;
+                    properties.push(new_property(name, rule.to_string(), Vec::new()));
+                }
+            }
+            "recurrenceOverrides" => {
+                for (id, patch) in value.as_object().ok_or_else(|| invalid(key))? {
+                    let name = match patch.as_object().map(|patch| patch.len()) {
+                        Some(0) => "RDATE",
+                        Some(1) if patch.get("excluded") == Some(&Value::Bool(true)) => "EXDATE",
+                        _ => continue,
+                    };
+                    let local = local_from_json(&Value::String(id.clone()))?;
+                    let instant = Instant {
+                        local,
+                        zone: zone.clone(),
+                        date: date && local.time() == NaiveTime::MIN,
+                    };
+                    properties.push(instant.to_property(name));
+                }
+            }
+            _ => unsupported.push(unsupported_property(type_name, key)),
+        }
+    }
+
+    Ok((properties, alarms))
+}
+
+/// Convert a JSCalendar object into a component, followed by its overrides.
+fn from_jscalendar<T>(
+    kind: Kind,
+    json: &Value,
+    build: impl Fn(Vec<Property>, Vec<IcalAlarm>) -> T,
+) -> Result<Converted<Vec<T>>, JsonError> {
+    let object = json
+        .as_object()
+        .ok_or_else(|| JsonError::invalid("object", json))?;
+    match object.get("@type").and_then(Value::as_str) {
+        Some(found) if found == kind.type_name() => {}
+        found => {
+            return Err(JsonError::UnexpectedComponent {
+                expected: kind.type_name().to_string(),
+                found: found.unwrap_or_default().to_string(),
+            })
+        }
+    }
+
+    let mut unsupported = Vec::new();
+    let (properties, alarms) = component_from_json(kind, object, &mut unsupported)?;
+    let mut result = vec![build(properties, alarms)];
+
+    let overrides = object.get("recurrenceOverrides").and_then(Value::as_object);
+    for (id, patch) in overrides.into_iter().flatten() {
+        let patch = patch
+            .as_object()
+            .ok_or_else(|| JsonError::invalid("patch", patch))?;
+        if patch.is_empty() || patch.get("excluded") == Some(&Value::Bool(true)) {
+            continue;
+        }
+
+        let mut instance = Map::new();
+        if let Some(uid) = object.get("uid") {
+            instance.insert(String::from("uid"), uid.clone());
+        }
+        instance.insert(String::from("recurrenceId"), Value::String(id.clone()));
+        instance.extend(apply_patch(object, patch)?);
+
+        let (properties, alarms) = component_from_json(kind, &instance, &mut unsupported)?;
+        result.push(build(properties, alarms));
+    }
+
+    Ok(Converted {
+        result,
+        unsupported,
+    })
+}
+
+/// Return the components of `siblings` overriding an instance of the component `uid`.
+fn overrides_of<'c, T>(
+    uid: Option<&str>,
+    siblings: &'c [T],
+    properties: impl Fn(&T) -> &[Property],
+) -> Vec<&'c T> {
+    siblings
+        .iter()
+        .filter(|sibling| {
+            let properties = properties(sibling);
+            let find = |name: &str| properties.iter().find(|p| p.name == name);
+
+            uid.is_some()
+                && find("UID").and_then(|p| p.value.as_deref()) == uid
+                && find("RECURRENCE-ID").is_some()
+        })
+        .collect()
+}
+
+impl JsCalendar for IcalEvent {
+    fn to_jscalendar(&self, calendar: &IcalCalendar) -> Result<Converted<Value>, JsonError> {
+        let uid = self.get_property("UID").and_then(|p| p.value.as_deref());
+        let overrides = if self.get_property("RECURRENCE-ID").is_some() {
+            Vec::new()
+        } else {
+            overrides_of(uid, &calendar.events, |event| &event.properties)
+        };
+
+        to_jscalendar(
+            Kind::Event,
+            (&self.properties, &self.alarms, &self.unknown_components),
+            overrides
+                .into_iter()
+                .map(|o| (&o.properties[..], &o.alarms[..], &o.unknown_components[..]))
+                .collect(),
+            calendar,
+        )
+    }
+
+    fn from_jscalendar(json: &Value) -> Result<Converted<Vec<IcalEvent>>, JsonError> {
+        from_jscalendar(Kind::Event, json, |properties, alarms| {
+            let mut event = IcalEvent::new();
+            event.properties = properties;
+            event.alarms = alarms;
+            event
+        })
+    }
+}
+
+impl JsCalendar for IcalTodo {
+    fn to_jscalendar(&self, calendar: &IcalCalendar) -> Result<Converted<Value>, JsonError> {
+        let uid = self.get_property("UID").and_then(|p| p.value.as_deref());
+        let overrides = if self.get_property("RECURRENCE-ID").is_some() {
+            Vec::new()
+        } else {
+            overrides_of(uid, &calendar.todos, |todo| &todo.properties)
+        };
+
+        to_jscalendar(
+            Kind::Task,
+            (&self.properties, &self.alarms, &self.unknown_components),
+            overrides
+                .into_iter()
+                .map(|o| (&o.properties[..], &o.alarms[..], &o.unknown_components[..]))
+                .collect(),
+            calendar,
+        )
+    }
+
+    fn from_jscalendar(json: &Value) -> Result<Converted<Vec<IcalTodo>>, JsonError> {
+        from_jscalendar(Kind::Task, json, |properties, alarms| {
+            let mut todo = IcalTodo::new();
+            todo.properties = properties;
+            todo.alarms = alarms;
+            todo
+        })
+    }
+}
+
+/// Return whether the component overrides an instance of another component of `siblings`.
+fn is_override<T>(
+    properties: &[Property],
+    siblings: &[T],
+    sibling: impl Fn(&T) -> &[Property],
+) -> bool {
+    let find = |properties: &[Property], name: &str| {
+        properties
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.value.clone())
+    };
+    let uid = find(properties, "UID");
+
+    find(properties, "RECURRENCE-ID").is_some()
+        && siblings.iter().any(|other| {
+            find(sibling(other), "RECURRENCE-ID").is_none() && find(sibling(other), "UID") == uid
+        })
+}
+
+/// Convert a calendar into a JSCalendar `Group`, holding its events and tasks.
+///
+/// The `PRODID`, `UID`, `NAME`, `DESCRIPTION` and `COLOR` of the calendar are kept, its
+/// `METHOD` is copied in each entry. The components overriding an instance are held by their
+/// main component.
+pub fn to_group(calendar: &IcalCalendar) -> Result<Converted<Value>, JsonError> {
+    let mut unsupported = Vec::new();
+    let mut group = Map::new();
+    group.insert(String::from("@type"), Value::from("Group"));
+
+    for property in &calendar.properties {
+        let name = property.name.to_uppercase();
+        let key = match name.as_str() {
+            "VERSION" | "METHOD" => continue,
+            "CALSCALE" if property.value.as_deref() == Some("GREGORIAN") => continue,
+            "PRODID" => "prodId",
+            "UID" => "uid",
+            "NAME" => "title",
+            "DESCRIPTION" => "description",
+            "COLOR" => "color",
+            _ => {
+                unsupported.push(unsupported_property("VCALENDAR", &name));
+                continue;
+            }
+        };
+        match property.typed_value(Format::Ical) {
+            Ok(TypedValue::Text(text)) => {
+                group.insert(String::from(key), Value::String(text));
+            }
+            _ => unsupported.push(unsupported_value("VCALENDAR", property)),
+        }
+    }
+
+    let mut entries = Vec::new();
+    for event in &calendar.events {
+        if !is_override(&event.properties, &calendar.events, |e| &e.properties) {
+            let converted = event.to_jscalendar(calendar)?;
+            entries.push(converted.result);
+            unsupported.extend(converted.unsupported);
+        }
+    }
+    for todo in &calendar.todos {
+        if !is_override(&todo.properties, &calendar.todos, |t| &t.properties) {
+            let converted = todo.to_jscalendar(calendar)?;
+            entries.push(converted.result);
+            unsupported.extend(converted.unsupported);
+        }
+    }
+    group.insert(String::from("entries"), Value::Array(entries));
+
+    let others = calendar
+        .timezones
+        .iter()
+        .map(|_| "VTIMEZONE")
+        .chain(calendar.alarms.iter().map(|_| "VALARM"))
+        .chain(calendar.journals.iter().map(|_| "VJOURNAL"))
+        .chain(calendar.free_busys.iter().map(|_| "VFREEBUSY"))
+        .chain(calendar.availabilities.iter().map(|_| "VAVAILABILITY"))
+        .map(String::from)
+        .chain(calendar.unknown_components.iter().map(|c| c.name.clone()));
+    unsupported.extend(others.map(|name| Unsupported::Component { name }));
+
+    Ok(Converted {
+        result: Value::Object(group),
+        unsupported,
+    })
+}
+
+/// Build a calendar from a JSCalendar `Group`, or from a single `Event` or `Task`.
+pub fn from_group(json: &Value) -> Result<Converted<IcalCalendar>, JsonError> {
+    let mut calendar = IcalCalendar::new();
+    let mut unsupported = Vec::new();
+    calendar
+        .properties
+        .push(new_property("VERSION", String::from("2.0"), Vec::new()));
+
+    let entries = match json.get("@type").and_then(Value::as_str) {
+        Some("Group") => {
+            let group = json
+                .as_object()
+                .ok_or_else(|| JsonError::invalid("group", json))?;
+            for (key, value) in group {
+                let name = match key.as_str() {
+                    "@type" | "entries" => continue,
+                    "prodId" => "PRODID",
+                    "uid" => "UID",
+                    "title" => "NAME",
+                    "description" => "DESCRIPTION",
+                    "color" => "COLOR",
+                    _ => {
+                        unsupported.push(unsupported_property("Group", key));
+                        continue;
+                    }
+                };
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| JsonError::invalid("group", value))?;
+                calendar.properties.push(text_property(name, text));
+            }
+
+            group
+                .get("entries")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+        }
+        _ => std::slice::from_ref(json),
+    };
+
+    let method = entries
+        .iter()
+        .find_map(|entry| entry.get("method").and_then(Value::as_str));
+    if let Some(method) = method {
+        calendar
+            .properties
+            .push(new_property("METHOD", method.to_uppercase(), Vec::new()));
+    }
+
+    for entry in entries {
+        match entry.get("@type").and_then(Value::as_str) {
+            Some("Event") => {
+                let converted = IcalEvent::from_jscalendar(entry)?;
+                calendar.events.extend(converted.result);
+                unsupported.extend(converted.unsupported);
+            }
+            Some("Task") => {
+                let converted = IcalTodo::from_jscalendar(entry)?;
+                calendar.todos.extend(converted.result);
+                unsupported.extend(converted.unsupported);
+            }
+            found => {
+                return Err(JsonError::UnexpectedComponent {
+                    expected: String::from("Event or Task"),
+                    found: found.unwrap_or_default().to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(Converted {
+        result: calendar,
+        unsupported,
+    })
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+    use serde_json::json;
+
+    fn object(json: Value) -> Map<String, Value> {
+        json.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn patch_the_overrides() {
+        let main = object(json!({
+            "@type": "Event",
+            "uid": "event@example.com",
+            "title": "Meeting",
+            "description": "Weekly",
+            "locations": {"1": {"@type": "Location", "name": "Room 1"}},
+            "recurrenceRules": [{"@type": "RecurrenceRule", "frequency": "weekly"}],
+        }));
+        let instance = object(json!({
+            "@type": "Event",
+            "uid": "event@example.com",
+            "title": "Meeting (moved)",
+            "locations": {"1": {"@type": "Location", "name": "Room 1"}},
+            "recurrenceId": "2024-01-08T10:00:00",
+        }));
+
+        let diff = patch(&main, &instance);
+        assert_eq!(
+            Value::Object(diff.clone()),
+            json!({"title": "Meeting (moved)", "description": null})
+        );
+
+        let mut expected = instance;
+        expected.remove("@type");
+        expected.remove("uid");
+        expected.remove("recurrenceId");
+        assert_eq!(apply_patch(&main, &diff).unwrap(), expected);
+    }
+
+    #[test]
+    fn follow_the_patch_pointers() {
+        let main = object(json!({
+            "title": "Meeting",
+            "locations": {"1": {"@type": "Location", "name": "Room 1"}},
+        }));
+        let diff = object(json!({"locations/1/name": "Room 2", "/title": null}));
+
+        assert_eq!(
+            apply_patch(&main, &diff).unwrap(),
+            object(json!({"locations": {"1": {"@type": "Location", "name": "Room 2"}}}))
+        );
+    }
+
+    #[test]
+    fn convert_the_all_day_events() {
+        let mut event = IcalEvent::new();
+        event.properties = vec![
+            new_property("UID", String::from("day@example.com"), Vec::new()),
+            Instant {
+                local: NaiveDateTime::parse_from_str("20240102T000000", "%Y%m%dT%H%M%S").unwrap(),
+                zone: Zone::Floating,
+                date: true,
+            }
+            .to_property("DTSTART"),
+            new_property(
+                "DTEND",
+                String::from("20240104"),
+                vec![(String::from("VALUE"), vec![String::from("DATE")])],
+            ),
+        ];
+
+        let json = event.to_jscalendar(&IcalCalendar::new()).unwrap().result;
+        assert_eq!(
+            json,
+            json!({
+                "@type": "Event",
+                "uid": "day@example.com",
+                "start": "2024-01-02T00:00:00",
+                "showWithoutTime": true,
+                "duration": "P2D",
+            })
+        );
+
+        let events = IcalEvent::from_jscalendar(&json).unwrap().result;
+        assert_eq!(events[0].properties[1], event.properties[1]);
+    }
+}
@@ -0,0 +1,425 @@
+//! The `ORGANIZER` and `ATTENDEE` properties, as JSCalendar `Participant` objects.
+
+// Sys mods
+use std::collections::HashMap;
+
+// Internal mods
+use super::{check_params, new_property, param, unsupported_property, Unsupported};
+use crate::json::JsonError;
+use crate::property::Property;
+use serde_json::{Map, Value};
+
+/// The parameters of an `ATTENDEE`, in the order they are written back.
+const ATTENDEE_PARAMS: &[&str] = &[
+    "CN",
+    "CUTYPE",
+    "ROLE",
+    "PARTSTAT",
+    "RSVP",
+    "DELEGATED-TO",
+    "DELEGATED-FROM",
+    "MEMBER",
+    "SENT-BY",
+    "LANGUAGE",
+    "SCHEDULE-AGENT",
+    "EMAIL",
+];
+
+/// The parameters of an `ORGANIZER`.
+const ORGANIZER_PARAMS: &[&str] = &["CN", "SENT-BY", "LANGUAGE", "SCHEDULE-AGENT", "EMAIL"];
+
+/// The roles of an attendee in JSCalendar, as opposed to the `owner` role of the organizer.
+const ATTENDEE_ROLES: &[&str] = &["attendee", "chair", "optional", "informational"];
+
+/// The `CUTYPE` values, and the JSCalendar kinds.
+const KINDS: &[(&str, &str)] = &[
+    ("INDIVIDUAL", "individual"),
+    ("GROUP", "group"),
+    ("RESOURCE", "resource"),
+    ("ROOM", "location"),
+];
+
+/// Return all the values of the parameter `name`.
+fn params<'p>(property: &'p Property, name: &str) -> Vec<&'p str> {
+    property
+        .params
+        .iter()
+        .flatten()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+        .flat_map(|(_, values)| values.iter().map(String::as_str))
+        .collect()
+}
+
+fn strip_mailto(address: &str) -> Option<&str> {
+    let (scheme, email) = address.split_once(':')?;
+    if scheme.eq_ignore_ascii_case("mailto") {
+        Some(email)
+    } else {
+        None
+    }
+}
+
+/// Return the `sendTo` or `replyTo` methods reaching `address`.
+fn methods_to_json(address: &str) -> Map<String, Value> {
+    let method = if strip_mailto(address).is_some() {
+        "imip"
+    } else {
+        "other"
+    };
+
+    let mut methods = Map::new();
+    methods.insert(String::from(method), Value::from(address));
+    methods
+}
+
+fn set_flags(participant: &mut Map<String, Value>, key: &str, flags: &[&str]) {
+    let entry = participant
+        .entry(key.to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if let Value::Object(map) = entry {
+        for flag in flags {
+            map.insert(flag.to_string(), Value::Bool(true));
+        }
+    }
+}
+
+/// Convert the `ORGANIZER` and the `ATTENDEE` of a component into its `participants` and its
+/// `replyTo`, an organizer attending the event being a single participant.
+pub(super) fn participants_to_json(
+    properties: &[Property],
+    component: &str,
+    unsupported: &mut Vec<Unsupported>,
+) -> (Map<String, Value>, Map<String, Value>) {
+    // The lowercased addresses of the participants, the identifier of each one being its
+    // index plus one, and the people with the index of their address.
+    let mut ids: Vec<String> = Vec::new();
+    let mut people: Vec<(&Property, &str, usize)> = Vec::new();
+    for person in properties.iter().filter(|p| {
+        p.name.eq_ignore_ascii_case("ORGANIZER") || p.name.eq_ignore_ascii_case("ATTENDEE")
+    }) {
+        let address = match person.value.as_deref() {
+            Some(address) => address,
+            None => continue,
+        };
+
+        let key = address.to_lowercase();
+        let index = match ids.iter().position(|known| *known == key) {
+            Some(index) => index,
+            None => {
+                ids.push(key);
+                ids.len() - 1
+            }
+        };
+        people.push((person, address, index));
+    }
+    let id_of = |address: &str| {
+        let key = address.to_lowercase();
+        ids.iter()
+            .position(|known| *known == key)
+            .map(|index| (index + 1).to_string())
+    };
+
+    let mut new_participant = Map::new();
+    new_participant.insert(String::from("@type"), Value::from("Participant"));
+    let mut participants = vec![new_participant; ids.len()];
+    let mut reply_to = Map::new();
+    for (person, address, index) in people {
+        let organizer = person.name.eq_ignore_ascii_case("ORGANIZER");
+
+        if organizer {
+            reply_to = methods_to_json(address);
+            check_params(person, ORGANIZER_PARAMS, component, unsupported);
+        } else {
+            check_params(person, ATTENDEE_PARAMS, component, unsupported);
+        }
+
+        let participant = &mut participants[index];
+
+        if let Some(name) = param(person, "CN") {
+            participant.insert(String::from("name"), Value::from(name));
+        }
+        if let Some(email) = param(person, "EMAIL").or_else(|| strip_mailto(address)) {
+            participant.insert(String::from("email"), Value::from(email));
+        }
+        participant.insert(
+            String::from("sendTo"),
+            Value::Object(methods_to_json(address)),
+        );
+
+        if organizer {
+            set_flags(participant, "roles", &["owner"]);
+        } else {
+            let role = param(person, "ROLE");
+            let roles: &[&str] = match role.map(str::to_uppercase).as_deref() {
+                None | Some("REQ-PARTICIPANT") => &["attendee"],
+                Some("CHAIR") => &["chair", "attendee"],
+                Some("OPT-PARTICIPANT") => &["attendee", "optional"],
+                Some("NON-PARTICIPANT") => &["informational"],
+                Some(_) => {
+                    unsupported.push(Unsupported::Value {
+                        component: component.to_string(),
+                        property: String::from("ATTENDEE"),
+                        value: format!("ROLE={}", role.unwrap_or_default()),
+                    });
+                    &["attendee"]
+                }
+            };
+            set_flags(participant, "roles", roles);
+        }
+
+        if let Some(cutype) = param(person, "CUTYPE") {
+            match KINDS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(cutype))
+            {
+                Some((_, kind)) => {
+                    participant.insert(String::from("kind"), Value::from(*kind));
+                }
+                None if cutype.eq_ignore_ascii_case("UNKNOWN") => {}
+                None => unsupported.push(Unsupported::Value {
+                    component: component.to_string(),
+                    property: String::from("ATTENDEE"),
+                    value: format!("CUTYPE={}", cutype),
+                }),
+            }
+        }
+        if let Some(status) = param(person, "PARTSTAT") {
+            let status = status.to_lowercase();
+            let key = match status.as_str() {
+                "needs-action" | "accepted" | "declined" | "tentative" | "delegated" => {
+                    Some("participationStatus")
+                }
+                "completed" | "in-process" => Some("progress"),
+                _ => None,
+            };
+            match key {
+                Some(key) => {
+                    participant.insert(String::from(key), Value::String(status));
+                }
+                None => unsupported.push(Unsupported::Value {
+                    component: component.to_string(),
+                    property: String::from("ATTENDEE"),
+                    value: format!("PARTSTAT={}", status.to_uppercase()),
+                }),
+            }
+        }
+        if param(person, "RSVP").is_some_and(|rsvp| rsvp.eq_ignore_ascii_case("TRUE")) {
+            participant.insert(String::from("expectReply"), Value::Bool(true));
+        }
+
+        for (name, key) in &[
+            ("DELEGATED-TO", "delegatedTo"),
+            ("DELEGATED-FROM", "delegatedFrom"),
+            ("MEMBER", "memberOf"),
+        ] {
+            let mut others = Map::new();
+            for other in params(person, name) {
+                match id_of(other) {
+                    Some(id) => {
+                        others.insert(id, Value::Bool(true));
+                    }
+                    None => unsupported.push(Unsupported::Value {
+                        component: component.to_string(),
+                        property: person.name.to_uppercase(),
+                        value: format!("{}={}", name, other),
+                    }),
+                }
+            }
+            if !others.is_empty() {
+                participant.insert(key.to_string(), Value::Object(others));
+            }
+        }
+
+        if let Some(sent_by) = param(person, "SENT-BY") {
+            let sent_by = strip_mailto(sent_by).unwrap_or(sent_by);
+            participant.insert(String::from("sentBy"), Value::from(sent_by));
+        }
+        if let Some(language) = param(person, "LANGUAGE") {
+            participant.insert(String::from("language"), Value::from(language));
+        }
+        if let Some(agent) = param(person, "SCHEDULE-AGENT") {
+            participant.insert(
+                String::from("scheduleAgent"),
+                Value::String(agent.to_lowercase()),
+            );
+        }
+    }
+
+    let participants = participants
+        .into_iter()
+        .enumerate()
+        .map(|(index, participant)| ((index + 1).to_string(), Value::Object(participant)))
+        .collect();
+
+    (participants, reply_to)
+}
+
+/// Return the address reaching a participant, or the organizer for its `replyTo` methods.
+fn address_from_json(methods: Option<&Value>, email: Option<&str>) -> Option<String> {
+    let methods = methods.and_then(Value::as_object);
+    let method = methods.and_then(|methods| {
+        methods
+            .get("imip")
+            .or_else(|| methods.get("other"))
+            .or_else(|| methods.values().next())
+    });
+
+    match (method.and_then(Value::as_str), email) {
+        (Some(address), _) => Some(address.to_string()),
+        (None, Some(email)) => Some(format!("mailto:{}", email)),
+        (None, None) => None,
+    }
+}
+
+/// Return the flags set in the object `key`.
+fn flags<'o>(participant: &'o Map<String, Value>, key: &str) -> Vec<&'o str> {
+    participant
+        .get(key)
+        .and_then(Value::as_object)
+        .map(|flags| {
+            flags
+                .iter()
+                .filter(|(_, set)| **set == Value::Bool(true))
+                .map(|(flag, _)| flag.as_str())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Build the `ORGANIZER` and the `ATTENDEE` from the `participants` and the `replyTo` of a
+/// JSCalendar object.
+pub(super) fn participants_from_json(
+    object: &Map<String, Value>,
+    unsupported: &mut Vec<Unsupported>,
+) -> Result<Vec<Property>, JsonError> {
+    let invalid = |json: &Value| JsonError::invalid("participant", json);
+    let empty = Map::new();
+    let participants = match object.get("participants") {
+        Some(json) => json.as_object().ok_or_else(|| invalid(json))?,
+        None => &empty,
+    };
+
+    // The participants with their address, and the addresses by identifier.
+    let mut people = Vec::new();
+    let mut addresses = HashMap::new();
+    for (id, json) in participants {
+        let participant = json.as_object().ok_or_else(|| invalid(json))?;
+        let email = participant.get("email").and_then(Value::as_str);
+        let address =
+            address_from_json(participant.get("sendTo"), email).ok_or_else(|| invalid(json))?;
+        addresses.insert(id.as_str(), address.clone());
+        people.push((participant, address));
+    }
+    let reply_to = address_from_json(object.get("replyTo"), None);
+
+    let mut organizer = None;
+    let mut attendees = Vec::new();
+    for (participant, address) in people {
+        let roles = flags(participant, "roles");
+        let email = participant.get("email").and_then(Value::as_str);
+        let text = |key: &str| participant.get(key).and_then(Value::as_str);
+
+        for key in participant.keys() {
+            match key.as_str() {
+                "@type"
+                | "name"
+                | "email"
+                | "sendTo"
+                | "roles"
+                | "kind"
+                | "participationStatus"
+                | "progress"
+                | "expectReply"
+                | "delegatedTo"
+                | "delegatedFrom"
+                | "memberOf"
+                | "sentBy"
+                | "language"
+                | "scheduleAgent" => {}
+                _ => unsupported.push(unsupported_property("Participant", key)),
+            }
+        }
+
+        let mut common = Vec::new();
+        if let Some(name) = text("name") {
+            common.push((String::from("CN"), vec![name.to_string()]));
+        }
+
+        if roles.contains(&"owner") {
+            let mut params = common.clone();
+            if let Some(sent_by) = text("sentBy") {
+                params.push((String::from("SENT-BY"), vec![format!("mailto:{}", sent_by)]));
+            }
+            if let Some(language) = text("language") {
+                params.push((String::from("LANGUAGE"), vec![language.to_string()]));
+            }
+            let address = reply_to.clone().unwrap_or_else(|| address.clone());
+            organizer = Some(new_property("ORGANIZER", address, params));
+        }
+        if !roles.is_empty() && !roles.iter().any(|role| ATTENDEE_ROLES.contains(role)) {
+            continue;
+        }
+
+        let mut params = common;
+        if let Some(kind) = text("kind") {
+            match KINDS.iter().find(|(_, name)| *name == kind) {
+                Some((cutype, _)) => {
+                    params.push((String::from("CUTYPE"), vec![cutype.to_string()]))
+                }
+                None => unsupported.push(unsupported_property("Participant", "kind")),
+            }
+        }
+        let role = if roles.contains(&"chair") {
+            "CHAIR"
+        } else if roles.contains(&"informational") {
+            "NON-PARTICIPANT"
+        } else if roles.contains(&"optional") {
+            "OPT-PARTICIPANT"
+        } else {
+            "REQ-PARTICIPANT"
+        };
+        params.push((String::from("ROLE"), vec![role.to_string()]));
+        if let Some(status) = text("participationStatus").or_else(|| text("progress")) {
+            params.push((String::from("PARTSTAT"), vec![status.to_uppercase()]));
+        }
+        if participant.get("expectReply") == Some(&Value::Bool(true)) {
+            params.push((String::from("RSVP"), vec![String::from("TRUE")]));
+        }
+        for (key, name) in &[
+            ("delegatedTo", "DELEGATED-TO"),
+            ("delegatedFrom", "DELEGATED-FROM"),
+            ("memberOf", "MEMBER"),
+        ] {
+            let others: Vec<String> = flags(participant, key)
+                .into_iter()
+                .filter_map(|other| addresses.get(other).cloned())
+                .collect();
+            if !others.is_empty() {
+                params.push((name.to_string(), others));
+            }
+        }
+        if let Some(sent_by) = text("sentBy") {
+            params.push((String::from("SENT-BY"), vec![format!("mailto:{}", sent_by)]));
+        }
+        if let Some(language) = text("language") {
+            params.push((String::from("LANGUAGE"), vec![language.to_string()]));
+        }
+        if let Some(agent) = text("scheduleAgent") {
+            params.push((String::from("SCHEDULE-AGENT"), vec![agent.to_uppercase()]));
+        }
+        match email {
+            Some(email) if Some(email) != strip_mailto(&address) => {
+                params.push((String::from("EMAIL"), vec![email.to_string()]));
+            }
+            _ => {}
+        }
+
+        attendees.push(new_property("ATTENDEE", address, params));
+    }
+
+    if organizer.is_none() {
+        organizer = reply_to.map(|address| new_property("ORGANIZER", address, Vec::new()));
+    }
+
+    Ok(organizer.into_iter().chain(attendees).collect())
+}
@@ -0,0 +1,361 @@
+//! The `RRULE` and `EXRULE` properties, as JSCalendar `RecurrenceRule` objects.
+
+// Sys mods
+use std::convert::TryFrom;
+
+// Internal mods
+use super::{local_to_json, Instant, Unsupported, Zone};
+use crate::json::JsonError;
+use crate::parser::ical::component::IcalCalendar;
+use crate::property::Property;
+use crate::timezone::TimeZones;
+use crate::value::recur::{Frequency, Recur, RecurEnd, Until, WeekdayNum};
+use crate::value::DateTime;
+use chrono::{NaiveTime, Weekday};
+use serde_json::{Map, Value};
+
+/// The JSCalendar names of the weekdays.
+const WEEKDAYS: &[(Weekday, &str)] = &[
+    (Weekday::Mon, "mo"),
+    (Weekday::Tue, "tu"),
+    (Weekday::Wed, "we"),
+    (Weekday::Thu, "th"),
+    (Weekday::Fri, "fr"),
+    (Weekday::Sat, "sa"),
+    (Weekday::Sun, "su"),
+];
+
+fn weekday_to_json(weekday: Weekday) -> Value {
+    let (_, name) = WEEKDAYS.iter().find(|(day, _)| *day == weekday).unwrap();
+    Value::from(*name)
+}
+
+fn weekday_from_json(json: &Value) -> Result<Weekday, JsonError> {
+    WEEKDAYS
+        .iter()
+        .find(|(_, name)| json.as_str() == Some(name))
+        .map(|(day, _)| *day)
+        .ok_or_else(|| JsonError::invalid("weekday", json))
+}
+
+/// Insert the list `values` as `key` unless it's empty.
+fn insert_list<T: Into<Value> + Copy>(object: &mut Map<String, Value>, key: &str, values: &[T]) {
+    if !values.is_empty() {
+        let values = values.iter().map(|value| (*value).into()).collect();
+        object.insert(key.to_string(), Value::Array(values));
+    }
+}
+
+/// Read the list `key` of integers, empty if missing.
+fn list_from_json<T: TryFrom<i64>>(
+    object: &Map<String, Value>,
+    key: &str,
+) -> Result<Vec<T>, JsonError> {
+    let values = match object.get(key) {
+        Some(values) => values
+            .as_array()
+            .ok_or_else(|| JsonError::invalid("recurrence rule", values))?,
+        None => return Ok(Vec::new()),
+    };
+
+    values
+        .iter()
+        .map(|value| {
+            value
+                .as_i64()
+                .and_then(|integer| T::try_from(integer).ok())
+                .ok_or_else(|| JsonError::invalid("recurrence rule", value))
+        })
+        .collect()
+}
+
+/// Convert a rule, its `UNTIL` being converted into the local time of `zone`.
+pub(super) fn rule_to_json(
+    rule: &Recur,
+    zone: &Zone,
+    zones: &TimeZones,
+    component: &str,
+    property: &Property,
+    unsupported: &mut Vec<Unsupported>,
+) -> Value {
+    let mut object = Map::new();
+    object.insert(String::from("@type"), Value::from("RecurrenceRule"));
+    object.insert(
+        String::from("frequency"),
+        Value::String(rule.freq.to_string().to_lowercase()),
+    );
+
+    if let Some(interval) = rule.interval {
+        object.insert(String::from("interval"), Value::from(interval));
+    }
+    for (name, value) in &rule.extensions {
+        match name.to_uppercase().as_str() {
+            "RSCALE" => {
+                object.insert(String::from("rscale"), Value::String(value.to_lowercase()));
+            }
+            "SKIP" => {
+                object.insert(String::from("skip"), Value::String(value.to_lowercase()));
+            }
+            _ => unsupported.push(Unsupported::Value {
+                component: component.to_string(),
+                property: property.name.to_uppercase(),
+                value: format!("{}={}", name, value),
+            }),
+        }
+    }
+    if let Some(wkst) = rule.wkst {
+        object.insert(String::from("firstDayOfWeek"), weekday_to_json(wkst));
+    }
+
+    if !rule.by_day.is_empty() {
+        let days = rule
+            .by_day
+            .iter()
+            .map(|day| {
+                let mut nday =
+                    serde_json::json!({"@type": "NDay", "day": weekday_to_json(day.weekday)});
+                if let Some(ordinal) = day.ordinal {
+                    nday["nthOfPeriod"] = Value::from(ordinal);
+                }
+                nday
+            })
+            .collect();
+        object.insert(String::from("byDay"), Value::Array(days));
+    }
+    insert_list(&mut object, "byMonthDay", &rule.by_month_day);
+    if !rule.by_month.is_empty() {
+        let months = rule
+            .by_month
+            .iter()
+            .map(|month| Value::String(month.to_string()))
+            .collect();
+        object.insert(String::from("byMonth"), Value::Array(months));
+    }
+    insert_list(&mut object, "byYearDay", &rule.by_year_day);
+    insert_list(&mut object, "byWeekNo", &rule.by_week_no);
+    insert_list(&mut object, "byHour", &rule.by_hour);
+    insert_list(&mut object, "byMinute", &rule.by_minute);
+    insert_list(&mut object, "bySecond", &rule.by_second);
+    insert_list(&mut object, "bySetPosition", &rule.by_set_pos);
+
+    match &rule.end {
+        Some(RecurEnd::Count(count)) => {
+            object.insert(String::from("count"), Value::from(*count));
+        }
+        Some(RecurEnd::Until(Until::Date(date))) => {
+            object.insert(
+                String::from("until"),
+                local_to_json(date.and_time(NaiveTime::MIN)),
+            );
+        }
+        Some(RecurEnd::Until(Until::DateTime(date_time))) => {
+            match Instant::from_date_time(date_time).local_in(zone, zones) {
+                Some(until) => {
+                    object.insert(String::from("until"), local_to_json(until));
+                }
+                None => unsupported.push(Unsupported::Value {
+                    component: component.to_string(),
+                    property: property.name.to_uppercase(),
+                    value: format!("UNTIL={}", date_time),
+                }),
+            }
+        }
+        None => {}
+    }
+
+    Value::Object(object)
+}
+
+/// Build a rule from a `RecurrenceRule` object, its `until` being in the local time of `zone`
+/// or a date if the start is one.
+pub(super) fn rule_from_json(json: &Value, zone: &Zone, date: bool) -> Result<Recur, JsonError> {
+    let invalid = || JsonError::invalid("recurrence rule", json);
+    let object = json.as_object().ok_or_else(invalid)?;
+
+    let freq = object
+        .get("frequency")
+        .and_then(Value::as_str)
+        .and_then(|freq| freq.parse::<Frequency>().ok())
+        .ok_or_else(invalid)?;
+    let mut rule = Recur::new(freq);
+
+    if let Some(interval) = object.get("interval") {
+        rule.interval = Some(interval.as_u64().ok_or_else(invalid)? as u32);
+    }
+    for (key, name) in &[("rscale", "RSCALE"), ("skip", "SKIP")] {
+        if let Some(value) = object.get(*key) {
+            let value = value.as_str().ok_or_else(invalid)?;
+            rule.extensions
+                .push((name.to_string(), value.to_uppercase()));
+        }
+    }
+    if let Some(wkst) = object.get("firstDayOfWeek") {
+        rule.wkst = Some(weekday_from_json(wkst)?);
+    }
+
+    for day in object
+        .get("byDay")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let weekday = weekday_from_json(day.get("day").unwrap_or(&Value::Null))?;
+        rule.by_day.push(match day.get("nthOfPeriod") {
+            Some(ordinal) => WeekdayNum::nth(ordinal.as_i64().ok_or_else(invalid)? as i32, weekday),
+            None => WeekdayNum::every(weekday),
+        });
+    }
+    rule.by_month_day = list_from_json(object, "byMonthDay")?;
+    for month in object
+        .get("byMonth")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let month = month.as_str().and_then(|month| month.parse().ok());
+        rule.by_month.push(month.ok_or_else(invalid)?);
+    }
+    rule.by_year_day = list_from_json(object, "byYearDay")?;
+    rule.by_week_no = list_from_json(object, "byWeekNo")?;
+    rule.by_hour = list_from_json(object, "byHour")?;
+    rule.by_minute = list_from_json(object, "byMinute")?;
+    rule.by_second = list_from_json(object, "bySecond")?;
+    rule.by_set_pos = list_from_json(object, "bySetPosition")?;
+
+    if let Some(count) = object.get("count") {
+        rule.end = Some(RecurEnd::Count(count.as_u64().ok_or_else(invalid)? as u32));
+    }
+    if let Some(until) = object.get("until") {
+        let until = super::local_from_json(until)?;
+        rule.end = Some(RecurEnd::Until(match zone {
+            _ if date => Until::Date(until.date()),
+            Zone::Floating => Until::DateTime(DateTime::Floating(until)),
+            Zone::Utc => Until::DateTime(DateTime::Utc(until)),
+            // Without its definition, a zone is only known with the `tzdb` feature.
+            Zone::Named(tzid) => {
+                let zoned = DateTime::Zoned {
+                    date_time: until,
+                    tzid: tzid.clone(),
+                };
+                match IcalCalendar::new().to_utc(&zoned) {
+                    Ok(utc) => Until::DateTime(DateTime::Utc(utc.naive_utc())),
+                    Err(_) => Until::DateTime(DateTime::Floating(until)),
+                }
+            }
+        }));
+    }
+
+    rule.validate().map_err(|_| invalid())?;
+
+    Ok(rule)
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(rule: &str, zone: Zone, expected: Value) {
+        let rule: Recur = rule.parse().unwrap();
+        let mut unsupported = Vec::new();
+        let json = rule_to_json(
+            &rule,
+            &zone,
+            &TimeZones::new(&IcalCalendar::new()),
+            "VEVENT",
+            &Property::new(),
+            &mut unsupported,
+        );
+
+        assert_eq!(json, expected);
+        assert!(unsupported.is_empty());
+        assert_eq!(rule_from_json(&json, &zone, false).unwrap(), rule);
+    }
+
+    #[test]
+    fn convert_the_rules() {
+        round_trip(
+            "FREQ=MONTHLY;COUNT=10;BYDAY=-1FR,MO",
+            Zone::Floating,
+            json!({
+                "@type": "RecurrenceRule",
+                "frequency": "monthly",
+                "byDay": [
+                    {"@type": "NDay", "day": "fr", "nthOfPeriod": -1},
+                    {"@type": "NDay", "day": "mo"},
+                ],
+                "count": 10,
+            }),
+        );
+        round_trip(
+            "FREQ=YEARLY;INTERVAL=2;BYMONTH=1,3;BYSETPOS=1;BYMONTHDAY=1,2;WKST=SU",
+            Zone::Floating,
+            json!({
+                "@type": "RecurrenceRule",
+                "frequency": "yearly",
+                "interval": 2,
+                "firstDayOfWeek": "su",
+                "byMonthDay": [1, 2],
+                "byMonth": ["1", "3"],
+                "bySetPosition": [1],
+            }),
+        );
+        round_trip(
+            "FREQ=DAILY;UNTIL=20240102T100000Z",
+            Zone::Utc,
+            json!({
+                "@type": "RecurrenceRule",
+                "frequency": "daily",
+                "until": "2024-01-02T10:00:00",
+            }),
+        );
+        round_trip(
+            "FREQ=MONTHLY;RSCALE=GREGORIAN;SKIP=FORWARD",
+            Zone::Floating,
+            json!({
+                "@type": "RecurrenceRule",
+                "frequency": "monthly",
+                "rscale": "gregorian",
+                "skip": "forward",
+            }),
+        );
+    }
+
+    #[test]
+    fn report_the_unknown_extensions() {
+        let rule: Recur = "FREQ=DAILY;X-NAME=VALUE".parse().unwrap();
+        let mut property = Property::new();
+        property.name = String::from("RRULE");
+        let mut unsupported = Vec::new();
+        rule_to_json(
+            &rule,
+            &Zone::Floating,
+            &TimeZones::new(&IcalCalendar::new()),
+            "VEVENT",
+            &property,
+            &mut unsupported,
+        );
+
+        assert_eq!(
+            unsupported,
+            vec![Unsupported::Value {
+                component: String::from("VEVENT"),
+                property: String::from("RRULE"),
+                value: String::from("X-NAME=VALUE"),
+            }]
+        );
+    }
+
+    #[test]
+    fn reject_the_invalid_rules() {
+        let zone = Zone::Floating;
+
+        assert!(rule_from_json(&json!({"frequency": "often"}), &zone, false).is_err());
+        assert!(
+            rule_from_json(&json!({"frequency": "daily", "byDay": [{}]}), &zone, false).is_err()
+        );
+        assert!(
+            rule_from_json(&json!({"frequency": "daily", "interval": 0}), &zone, false).is_err()
+        );
+    }
+}
@@ -0,0 +1,963 @@
+//! JSContact ([RFC9553](https://tools.ietf.org/html/rfc9553)), the JSON format of the contacts
+//! used by JMAP, converted following [RFC9555](https://tools.ietf.org/html/rfc9555).
+//!
+//! | vCard                 | JSContact                                       |
+//! |-----------------------|-------------------------------------------------|
+//! | `UID`, `KIND`         | `uid`, `kind`                                   |
+//! | `PRODID`, `REV`       | `prodId`, `updated`                             |
+//! | `FN`, `N`             | `name` (`full`, `components` and `sortAs`)      |
+//! | `NICKNAME`            | `nicknames`                                     |
+//! | `ORG`                 | `organizations`, with their `units`             |
+//! | `TITLE`, `ROLE`       | `titles`, of kind `title` or `role`             |
+//! | `EMAIL`               | `emails`                                        |
+//! | `TEL`                 | `phones`, the `TYPE` giving their `features`    |
+//! | `ADR`                 | `addresses`                                     |
+//!
+//! The `TYPE` parameter gives the `contexts` of the objects (`home` being `private`), the `PREF`
+//! parameter their `pref` and the `PROP-ID` parameter their identifier in the card. The other
+//! properties are kept as jCard in the `vCardProps` of the card, and the other parameters in
+//! the `vCardParams` of their object.
+//!
+//! The JSContact properties which vCard can't hold are written in `JSPROP` properties, whose
+//! `JSPTR` parameter is the JSON pointer of the property in the card, and restored from them.
+//! A card converted to vCard and back is thus identical, while a contact converted to JSContact
+//! and back keeps its properties but not their order. The `VERSION` of a contact isn't
+//! converted, the contacts always coming back in vCard 4.0. The sub-components of a contact,
+//! which vCard doesn't define, aren't converted.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["jscontact"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use ical::json::JsContact;
+//! use ical::parser::vcard::component::VcardContact;
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/jcard_example.vcf")
+//!     .unwrap());
+//!
+//! for contact in ical::VcardParser::new(buf) {
+//!     let card = contact.unwrap().to_jscontact().unwrap();
+//!     assert_eq!(card["name"]["full"], "Simon Perreault");
+//!
+//!     let contact = VcardContact::from_jscontact(&card).unwrap();
+//!     assert_eq!(contact.to_jscontact().unwrap(), card);
+//! }
+//! ```
+
+// Internal mods
+use super::{property_from_json, property_to_json, JsonError};
+use crate::parser::vcard::component::VcardContact;
+use crate::property::{
+    escape_component, escape_text, split_unescaped, unescape_text, Format, Property,
+};
+use crate::value::extended::{from_extended, to_extended};
+use crate::value::ValueType;
+use serde_json::{Map, Value};
+
+/// Converts a contact to and from a JSContact card.
+pub trait JsContact: Sized {
+    /// Convert the contact into a JSContact card.
+    fn to_jscontact(&self) -> Result<Value, JsonError>;
+
+    /// Build a contact from a JSContact card.
+    fn from_jscontact(card: &Value) -> Result<Self, JsonError>;
+
+    /// Convert the contact into a JSContact string.
+    fn to_jscontact_string(&self) -> Result<String, JsonError> {
+        Ok(serde_json::to_string(&self.to_jscontact()?)?)
+    }
+
+    /// Build a contact from a JSContact string.
+    fn from_jscontact_str(card: &str) -> Result<Self, JsonError> {
+        Self::from_jscontact(&serde_json::from_str(card)?)
+    }
+}
+
+/// The kinds of the components of a `N` value, by position.
+const NAME_COMPONENTS: &[&str] = &[
+    "surname",
+    "given",
+    "given2",
+    "title",
+    "credential",
+    "surname2",
+    "generation",
+];
+
+/// The kinds of the components of an `ADR` value, by position.
+const ADDRESS_COMPONENTS: &[&str] = &[
+    "postOfficeBox",
+    "apartment",
+    "name",
+    "locality",
+    "region",
+    "postcode",
+    "country",
+];
+
+/// The `ADR` parameters, and the address properties holding them.
+const ADDRESS_PARAMS: &[(&str, &str)] = &[
+    ("LABEL", "full"),
+    ("GEO", "coordinates"),
+    ("TZ", "timeZone"),
+    ("CC", "countryCode"),
+];
+
+/// The `TYPE` values giving the contexts of an object.
+const CONTEXTS: &[(&str, &str)] = &[("home", "private"), ("work", "work")];
+
+/// The `TYPE` values of a `TEL` giving the features of a phone.
+const PHONE_FEATURES: &[(&str, &str)] = &[
+    ("voice", "voice"),
+    ("fax", "fax"),
+    ("cell", "mobile"),
+    ("video", "video"),
+    ("pager", "pager"),
+    ("textphone", "textphone"),
+    ("text", "text"),
+    ("main-number", "main-number"),
+];
+
+/// The collections of the card holding the objects converted from properties.
+const COLLECTIONS: &[&str] = &[
+    "nicknames",
+    "organizations",
+    "titles",
+    "emails",
+    "phones",
+    "addresses",
+];
+
+/// Return the values of the parameter `name`, the lists being split.
+fn param_values(property: &Property, name: &str) -> Vec<String> {
+    property
+        .params
+        .iter()
+        .flatten()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+        .flat_map(|(_, values)| values.iter().flat_map(|value| value.split(',')))
+        .map(String::from)
+        .collect()
+}
+
+fn first_param(property: &Property, name: &str) -> Option<String> {
+    param_values(property, name).into_iter().next()
+}
+
+fn flags(names: &[&str]) -> Value {
+    Value::Object(
+        names
+            .iter()
+            .map(|name| (name.to_string(), Value::Bool(true)))
+            .collect(),
+    )
+}
+
+/// Return the parameters of `property` which aren't in `known`, and its group, as the
+/// `vCardParams` of an object. The unknown `TYPE` values are given by `types`.
+fn vcard_params(property: &Property, known: &[&str], types: &[String]) -> Map<String, Value> {
+    let mut params = Map::new();
+    if let Some(group) = &property.group {
+        params.insert(String::from("group"), Value::String(group.clone()));
+    }
+
+    for (name, values) in property.params.iter().flatten() {
+        let name = name.to_uppercase();
+        if name == "VALUE" || name == "TYPE" || known.contains(&name.as_str()) {
+            continue;
+        }
+
+        let value = match values.as_slice() {
+            [value] => Value::String(value.clone()),
+            values => Value::Array(values.iter().cloned().map(Value::String).collect()),
+        };
+        params.insert(name.to_lowercase(), value);
+    }
+
+    match types {
+        [] => {}
+        [value] => {
+            params.insert(String::from("type"), Value::String(value.clone()));
+        }
+        values => {
+            let values = values.iter().cloned().map(Value::String).collect();
+            params.insert(String::from("type"), Value::Array(values));
+        }
+    }
+
+    params
+}
+
+/// Set the `TYPE`, `PREF` and unknown parameters of an object on `property`.
+fn params_from_object(
+    property: &mut Property,
+    object: &Map<String, Value>,
+    mut types: Vec<String>,
+) -> Result<(), JsonError> {
+    let mut params = Vec::new();
+
+    if let Some(contexts) = object.get("contexts").and_then(Value::as_object) {
+        let names = CONTEXTS
+            .iter()
+            .filter(|(_, context)| contexts.get(*context) == Some(&Value::Bool(true)))
+            .map(|(name, _)| name.to_string());
+        types.splice(0..0, names);
+    }
+
+    if let Some(json) = object.get("vCardParams") {
+        // The parameters are read as the ones of a jCard property.
+        let jcard = serde_json::json!(["x", json, "unknown", ""]);
+        let parsed = property_from_json(&jcard, Format::Vcard)?;
+        property.group = parsed.group;
+        for (name, values) in parsed.params.into_iter().flatten() {
+            if name == "TYPE" {
+                types.extend(values);
+            } else {
+                params.push((name, values));
+            }
+        }
+    }
+
+    if !types.is_empty() {
+        params.insert(0, (String::from("TYPE"), types));
+    }
+    if let Some(pref) = object.get("pref").and_then(Value::as_u64) {
+        params.push((String::from("PREF"), vec![pref.to_string()]));
+    }
+
+    if !params.is_empty() {
+        property.params.get_or_insert_with(Vec::new).extend(params);
+    }
+
+    Ok(())
+}
+
+/// Convert a property held by a collection of the card into its object, `None` if it can't be.
+fn property_to_object(property: &Property) -> Option<(&'static str, Map<String, Value>)> {
+    let raw = property.value.as_deref()?;
+    let text = || unescape_text(raw);
+    let mut known = vec!["PREF", "PROP-ID"];
+    let mut types: Vec<String> = param_values(property, "TYPE")
+        .iter()
+        .map(|value| value.to_lowercase())
+        .collect();
+
+    let mut object = Map::new();
+    let collection = match property.name.to_uppercase().as_str() {
+        // The lists of nicknames are kept as they are.
+        "NICKNAME" if split_unescaped(raw, ',').len() == 1 => {
+            object.insert(String::from("name"), Value::String(text()));
+            "nicknames"
+        }
+        "ORG" => {
+            let mut names = split_unescaped(raw, ';').into_iter().map(unescape_text);
+            let name = names.next().unwrap_or_default();
+            if !name.is_empty() {
+                object.insert(String::from("name"), Value::String(name));
+            }
+            let units: Vec<Value> = names
+                .filter(|unit| !unit.is_empty())
+                .map(|unit| serde_json::json!({ "name": unit }))
+                .collect();
+            if !units.is_empty() {
+                object.insert(String::from("units"), Value::Array(units));
+            }
+            if let Some(sort_as) = first_param(property, "SORT-AS") {
+                known.push("SORT-AS");
+                object.insert(String::from("sortAs"), Value::String(sort_as));
+            }
+            "organizations"
+        }
+        name @ "TITLE" | name @ "ROLE" => {
+            object.insert(String::from("name"), Value::String(text()));
+            object.insert(String::from("kind"), Value::String(name.to_lowercase()));
+            "titles"
+        }
+        "EMAIL" => {
+            object.insert(String::from("address"), Value::String(text()));
+            "emails"
+        }
+        "TEL" => {
+            object.insert(String::from("number"), Value::String(raw.to_string()));
+            let features: Vec<&str> = PHONE_FEATURES
+                .iter()
+                .filter(|(name, _)| types.iter().any(|t| t == name))
+                .map(|(_, feature)| *feature)
+                .collect();
+            types.retain(|t| !PHONE_FEATURES.iter().any(|(name, _)| t == name));
+            if !features.is_empty() {
+                object.insert(String::from("features"), flags(&features));
+            }
+            "phones"
+        }
+        "ADR" => {
+            let components: Vec<Value> = split_unescaped(raw, ';')
+                .into_iter()
+                .zip(ADDRESS_COMPONENTS)
+                .flat_map(|(field, kind)| {
+                    split_unescaped(field, ',')
+                        .into_iter()
+                        .filter(|value| !value.is_empty())
+                        .map(move |value| {
+                            serde_json::json!({"kind": kind, "value": unescape_text(value)})
+                        })
+                })
+                .collect();
+            if !components.is_empty() {
+                object.insert(String::from("components"), Value::Array(components));
+            }
+            for (name, key) in ADDRESS_PARAMS {
+                if let Some(value) = property
+                    .params
+                    .iter()
+                    .flatten()
+                    .find(|(param, _)| param.eq_ignore_ascii_case(name))
+                    .and_then(|(_, values)| values.first())
+                {
+                    known.push(name);
+                    object.insert(key.to_string(), Value::String(value.clone()));
+                }
+            }
+            "addresses"
+        }
+        _ => return None,
+    };
+
+    let contexts: Vec<&str> = CONTEXTS
+        .iter()
+        .filter(|(name, _)| types.iter().any(|t| t == name))
+        .map(|(_, context)| *context)
+        .collect();
+    types.retain(|t| !CONTEXTS.iter().any(|(name, _)| t == name));
+    if !contexts.is_empty() {
+        object.insert(String::from("contexts"), flags(&contexts));
+    }
+    if let Some(pref) = first_param(property, "PREF") {
+        object.insert(String::from("pref"), Value::from(pref.parse::<u64>().ok()?));
+    }
+
+    let params = vcard_params(property, &known, &types);
+    if !params.is_empty() {
+        object.insert(String::from("vCardParams"), Value::Object(params));
+    }
+
+    Some((collection, object))
+}
+
+/// Build the property of an object of the collection `collection`.
+fn property_from_object(
+    collection: &'static str,
+    object: &Map<String, Value>,
+) -> Result<Property, JsonError> {
+    let invalid = || JsonError::invalid(collection, &Value::Object(object.clone()));
+    let text = |key: &str| object.get(key).and_then(Value::as_str).ok_or_else(invalid);
+
+    let mut property = Property::new();
+    let mut types = Vec::new();
+    let (name, value) = match collection {
+        "nicknames" => ("NICKNAME", escape_text(text("name")?, Format::Vcard)),
+        "organizations" => {
+            let mut names = vec![escape_component(
+                object
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default(),
+            )];
+            for unit in object
+                .get("units")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let unit = unit
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(invalid)?;
+                names.push(escape_component(unit));
+            }
+            if let Some(sort_as) = object.get("sortAs").and_then(Value::as_str) {
+                property.params = Some(vec![(String::from("SORT-AS"), vec![sort_as.to_string()])]);
+            }
+            ("ORG", names.join(";"))
+        }
+        "titles" => {
+            let name = match object.get("kind").and_then(Value::as_str) {
+                Some("role") => "ROLE",
+                _ => "TITLE",
+            };
+            (name, escape_text(text("name")?, Format::Vcard))
+        }
+        "emails" => ("EMAIL", escape_text(text("address")?, Format::Vcard)),
+        "phones" => {
+            let number = text("number")?;
+            // A number with a scheme, such as `tel:`, is an URI.
+            let scheme = number.split_once(':').map(|(scheme, _)| scheme);
+            if scheme.is_some_and(|scheme| {
+                scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            }) {
+                property.params = Some(vec![(String::from("VALUE"), vec![String::from("uri")])]);
+            }
+            if let Some(features) = object.get("features").and_then(Value::as_object) {
+                for (name, feature) in PHONE_FEATURES {
+                    if features.get(*feature) == Some(&Value::Bool(true)) {
+                        types.push(name.to_string());
+                    }
+                }
+            }
+            ("TEL", number.to_string())
+        }
+        "addresses" => {
+            let mut fields = vec![Vec::new(); ADDRESS_COMPONENTS.len()];
+            for component in object
+                .get("components")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let kind = component.get("kind").and_then(Value::as_str);
+                let index = ADDRESS_COMPONENTS.iter().position(|k| Some(*k) == kind);
+                let value = component.get("value").and_then(Value::as_str);
+                if let (Some(index), Some(value)) = (index, value) {
+                    fields[index].push(escape_component(value));
+                }
+            }
+
+            let mut params = Vec::new();
+            for (name, key) in ADDRESS_PARAMS {
+                if let Some(value) = object.get(*key).and_then(Value::as_str) {
+                    params.push((name.to_string(), vec![value.to_string()]));
+                }
+            }
+            if !params.is_empty() {
+                property.params = Some(params);
+            }
+
+            let fields: Vec<String> = fields.iter().map(|values| values.join(",")).collect();
+            ("ADR", fields.join(";"))
+        }
+        _ => return Err(invalid()),
+    };
+
+    property.name = name.to_string();
+    property.value = Some(value);
+    params_from_object(&mut property, object, types)?;
+
+    Ok(property)
+}
+
+/// Convert the `N` and `FN` of a contact into its name, `None` if it has neither.
+fn name_to_json(n: Option<&Property>, full_name: Option<&Property>) -> Option<Value> {
+    let mut name = Map::new();
+
+    if let Some(n) = n {
+        let raw = n.value.as_deref().unwrap_or_default();
+        let components: Vec<Value> = split_unescaped(raw, ';')
+            .into_iter()
+            .zip(NAME_COMPONENTS)
+            .flat_map(|(field, kind)| {
+                split_unescaped(field, ',')
+                    .into_iter()
+                    .filter(|value| !value.is_empty())
+                    .map(move |value| serde_json::json!({"kind": kind, "value": unescape_text(value)}))
+            })
+            .collect();
+        if !components.is_empty() {
+            name.insert(String::from("components"), Value::Array(components));
+        }
+
+        let sort_as = param_values(n, "SORT-AS");
+        if !sort_as.is_empty() {
+            let sort_as: Map<String, Value> = ["surname", "given"]
+                .iter()
+                .zip(sort_as)
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(kind, value)| (kind.to_string(), Value::String(value)))
+                .collect();
+            name.insert(String::from("sortAs"), Value::Object(sort_as));
+        }
+
+        let params = vcard_params(n, &["SORT-AS"], &[]);
+        if !params.is_empty() {
+            name.insert(String::from("vCardParams"), Value::Object(params));
+        }
+    }
+    if let Some(full_name) = full_name {
+        let full = unescape_text(full_name.value.as_deref().unwrap_or_default());
+        name.insert(String::from("full"), Value::String(full));
+    }
+
+    if n.is_none() && full_name.is_none() {
+        return None;
+    }
+
+    Some(Value::Object(name))
+}
+
+/// Build the `FN` and `N` of a contact from its name.
+fn name_from_json(name: &Map<String, Value>) -> Result<Vec<Property>, JsonError> {
+    let mut properties = Vec::new();
+
+    if let Some(full) = name.get("full").and_then(Value::as_str) {
+        let mut property = Property::new();
+        property.name = String::from("FN");
+        property.value = Some(escape_text(full, Format::Vcard));
+        properties.push(property);
+    }
+
+    if name.contains_key("components") || name.contains_key("sortAs") {
+        let mut fields = vec![Vec::new(); 5];
+        for component in name
+            .get("components")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let kind = component.get("kind").and_then(Value::as_str);
+            let index = NAME_COMPONENTS.iter().position(|k| Some(*k) == kind);
+            let value = component.get("value").and_then(Value::as_str);
+            if let (Some(index), Some(value)) = (index, value) {
+                if index >= fields.len() {
+                    fields.resize(index + 1, Vec::new());
+                }
+                fields[index].push(escape_component(value));
+            }
+        }
+
+        let mut property = Property::new();
+        property.name = String::from("N");
+        let fields: Vec<String> = fields.iter().map(|values| values.join(",")).collect();
+        property.value = Some(fields.join(";"));
+
+        if let Some(sort_as) = name.get("sortAs").and_then(Value::as_object) {
+            let values: Vec<String> = ["surname", "given"]
+                .iter()
+                .map(|kind| {
+                    let value = sort_as.get(*kind).and_then(Value::as_str);
+                    value.unwrap_or_default().to_string()
+                })
+                .collect();
+            let values = match values[1].is_empty() {
+                true => values[..1].to_vec(),
+                false => values,
+            };
+            property.params = Some(vec![(String::from("SORT-AS"), values)]);
+        }
+        params_from_object(&mut property, name, Vec::new())?;
+
+        properties.push(property);
+    }
+
+    Ok(properties)
+}
+
+/// Return the path of a JSON pointer, with or without its leading solidus.
+fn pointer_path(pointer: &str) -> Vec<String> {
+    pointer
+        .strip_prefix('/')
+        .unwrap_or(pointer)
+        .split('/')
+        .map(|key| key.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+fn pointer_from_path(path: &[&str]) -> String {
+    let keys: Vec<String> = path
+        .iter()
+        .map(|key| key.replace('~', "~0").replace('/', "~1"))
+        .collect();
+
+    keys.join("/")
+}
+
+/// Set the value at `pointer` in the card, a `null` value removing it. Return `false` if the
+/// pointer goes through a value which isn't an object.
+fn set_pointer(card: &mut Map<String, Value>, pointer: &str, value: Value) -> bool {
+    let path = pointer_path(pointer);
+    let (last, parents) = match path.split_last() {
+        Some(split) => split,
+        None => return false,
+    };
+
+    let mut target = card;
+    for parent in parents {
+        target = match target
+            .entry(parent.clone())
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            Value::Object(object) => object,
+            _ => return false,
+        };
+    }
+
+    if value.is_null() {
+        target.remove(last);
+    } else {
+        target.insert(last.clone(), value);
+    }
+
+    true
+}
+
+/// Add to `jsprops` the values of `expected` which differ from the ones `found`, with their
+/// pointers.
+fn diff(path: &[&str], expected: &Value, found: Option<&Value>, jsprops: &mut Vec<Property>) {
+    match (expected, found) {
+        (expected, Some(found)) if expected == found => {}
+        (Value::Object(expected), Some(Value::Object(found))) => {
+            for (key, value) in expected {
+                let path: Vec<&str> = path.iter().copied().chain(Some(key.as_str())).collect();
+                diff(&path, value, found.get(key), jsprops);
+            }
+            for key in found.keys().filter(|key| !expected.contains_key(*key)) {
+                let path: Vec<&str> = path.iter().copied().chain(Some(key.as_str())).collect();
+                diff(&path, &Value::Null, found.get(key), jsprops);
+            }
+        }
+        (expected, _) => {
+            let mut property = Property::new();
+            property.name = String::from("JSPROP");
+            property.params = Some(vec![(String::from("JSPTR"), vec![pointer_from_path(path)])]);
+            property.value = Some(escape_text(&expected.to_string(), Format::Vcard));
+            jsprops.push(property);
+        }
+    }
+}
+
+/// Return whether the property has no parameter and no group, to be held by the card itself.
+fn is_plain(property: &Property) -> bool {
+    property.group.is_none() && property.params.as_ref().is_none_or(Vec::is_empty)
+}
+
+impl JsContact for VcardContact {
+    fn to_jscontact(&self) -> Result<Value, JsonError> {
+        let mut card = Map::new();
+        card.insert(String::from("@type"), Value::from("Card"));
+        card.insert(String::from("version"), Value::from("1.0"));
+
+        let find = |name: &str, plain: bool| {
+            self.properties
+                .iter()
+                .find(|p| p.name.eq_ignore_ascii_case(name) && (!plain || is_plain(p)))
+        };
+        let n = find("N", false);
+        let full_name = find("FN", true);
+
+        let mut vcard_props = Vec::new();
+        let mut jsprops = Vec::new();
+        for property in &self.properties {
+            let name = property.name.to_uppercase();
+            let raw = property.value.as_deref().unwrap_or_default();
+            let first = find(&name, false).is_some_and(|p| std::ptr::eq(p, property));
+            let scalar = match name.as_str() {
+                "UID" | "KIND" | "PRODID" | "REV" if !first || !is_plain(property) => None,
+                "VERSION" => continue,
+                "UID" => Some(("uid", Value::from(raw))),
+                "KIND" => Some(("kind", Value::String(raw.to_lowercase()))),
+                "PRODID" => Some(("prodId", Value::String(unescape_text(raw)))),
+                "REV" => to_extended(raw, &ValueType::Timestamp, Format::Vcard)
+                    .map(|updated| ("updated", Value::String(updated))),
+                _ => None,
+            };
+            if let Some((key, value)) = scalar {
+                card.insert(String::from(key), value);
+                continue;
+            }
+
+            if (name == "N" || name == "FN") && (n == Some(property) || full_name == Some(property))
+            {
+                if !card.contains_key("name") {
+                    card.insert(String::from("name"), name_to_json(n, full_name).unwrap());
+                }
+                continue;
+            }
+
+            if name == "JSPROP" {
+                let pointer = first_param(property, "JSPTR");
+                let value = serde_json::from_str::<Value>(&unescape_text(raw));
+                if let (Some(pointer), Ok(value)) = (pointer, value) {
+                    jsprops.push((pointer, value));
+                    continue;
+                }
+            }
+
+            match property_to_object(property) {
+                Some((collection, object)) => {
+                    let objects = card
+                        .entry(collection.to_string())
+                        .or_insert_with(|| Value::Object(Map::new()))
+                        .as_object_mut()
+                        .unwrap();
+                    let id = first_param(property, "PROP-ID")
+                        .unwrap_or_else(|| (objects.len() + 1).to_string());
+                    objects.insert(id, Value::Object(object));
+                }
+                None => vcard_props.push(property_to_json(property, Format::Vcard)?),
+            }
+        }
+
+        if !vcard_props.is_empty() {
+            card.insert(String::from("vCardProps"), Value::Array(vcard_props));
+        }
+        for (pointer, value) in jsprops {
+            if !set_pointer(&mut card, &pointer, value) {
+                return Err(JsonError::Invalid {
+                    kind: "JSPTR",
+                    found: pointer,
+                });
+            }
+        }
+
+        Ok(Value::Object(card))
+    }
+
+    fn from_jscontact(card: &Value) -> Result<VcardContact, JsonError> {
+        let object = card
+            .as_object()
+            .ok_or_else(|| JsonError::invalid("card", card))?;
+        match object.get("@type").and_then(Value::as_str) {
+            Some("Card") => {}
+            found => {
+                return Err(JsonError::UnexpectedComponent {
+                    expected: String::from("Card"),
+                    found: found.unwrap_or_default().to_string(),
+                })
+            }
+        }
+
+        let mut contact = VcardContact::new();
+        let mut add = |name: &str, value: String| {
+            let mut property = Property::new();
+            property.name = name.to_string();
+            property.value = Some(value);
+            contact.properties.push(property);
+        };
+        add("VERSION", String::from("4.0"));
+
+        for (key, value) in object {
+            let text = value.as_str();
+            match (key.as_str(), text) {
+                ("uid", Some(uid)) => add("UID", uid.to_string()),
+                ("kind", Some(kind)) => add("KIND", kind.to_string()),
+                ("prodId", Some(prod_id)) => add("PRODID", escape_text(prod_id, Format::Vcard)),
+                ("updated", Some(updated)) => {
+                    if let Some(rev) = from_extended(updated, &ValueType::Timestamp, Format::Vcard)
+                    {
+                        add("REV", rev);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (key, value) in object {
+            let invalid = || JsonError::invalid("card", value);
+            match key.as_str() {
+                "name" => {
+                    let name = value.as_object().ok_or_else(invalid)?;
+                    contact.properties.extend(name_from_json(name)?);
+                }
+                "vCardProps" => {
+                    for property in value.as_array().ok_or_else(invalid)? {
+                        contact
+                            .properties
+                            .push(property_from_json(property, Format::Vcard)?);
+                    }
+                }
+                key if COLLECTIONS.contains(&key) => {
+                    let collection = COLLECTIONS.iter().find(|name| **name == key).unwrap();
+                    let objects = value.as_object().ok_or_else(invalid)?;
+                    for (index, (id, object)) in objects.iter().enumerate() {
+                        let object = object.as_object().ok_or_else(invalid)?;
+                        let mut property = property_from_object(collection, object)?;
+                        if *id != (index + 1).to_string() {
+                            property
+                                .params
+                                .get_or_insert_with(Vec::new)
+                                .push((String::from("PROP-ID"), vec![id.clone()]));
+                        }
+                        contact.properties.push(property);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // What the properties couldn't hold is restored by the `JSPROP` ones.
+        let converted = contact.to_jscontact()?;
+        let mut jsprops = Vec::new();
+        diff(&[], card, Some(&converted), &mut jsprops);
+        contact.properties.extend(jsprops);
+
+        Ok(contact)
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+    use serde_json::json;
+
+    /// A property, as its name, its value and its parameters.
+    type Line<'a> = (&'a str, &'a str, &'a [(&'a str, &'a str)]);
+
+    fn contact(lines: &[Line]) -> VcardContact {
+        let mut contact = VcardContact::new();
+        for (name, value, params) in lines {
+            let mut property = Property::new();
+            property.name = name.to_string();
+            property.value = Some(value.to_string());
+            if !params.is_empty() {
+                property.params = Some(
+                    params
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
+                        .collect(),
+                );
+            }
+            contact.properties.push(property);
+        }
+
+        contact
+    }
+
+    #[test]
+    fn convert_the_names() {
+        let contact = contact(&[
+            ("FN", "Mr. John Q. Public\\, Esq.", &[]),
+            (
+                "N",
+                "Public;John;Quinlan,Q.;Mr.;Esq.",
+                &[("SORT-AS", "Public,John")],
+            ),
+        ]);
+        let card = contact.to_jscontact().unwrap();
+
+        assert_eq!(
+            card["name"],
+            json!({
+                "components": [
+                    {"kind": "surname", "value": "Public"},
+                    {"kind": "given", "value": "John"},
+                    {"kind": "given2", "value": "Quinlan"},
+                    {"kind": "given2", "value": "Q."},
+                    {"kind": "title", "value": "Mr."},
+                    {"kind": "credential", "value": "Esq."},
+                ],
+                "sortAs": {"surname": "Public", "given": "John"},
+                "full": "Mr. John Q. Public, Esq.",
+            })
+        );
+        assert_eq!(
+            VcardContact::from_jscontact(&card)
+                .unwrap()
+                .to_jscontact()
+                .unwrap(),
+            card
+        );
+    }
+
+    #[test]
+    fn convert_the_phones() {
+        let contact = contact(&[
+            ("UID", "urn:uuid:1", &[]),
+            (
+                "TEL",
+                "tel:+1-555-555-5555",
+                &[("VALUE", "uri"), ("TYPE", "home,cell,x-car"), ("PREF", "1")],
+            ),
+        ]);
+        let card = contact.to_jscontact().unwrap();
+
+        assert_eq!(
+            card["phones"],
+            json!({"1": {
+                "number": "tel:+1-555-555-5555",
+                "features": {"mobile": true},
+                "contexts": {"private": true},
+                "pref": 1,
+                "vCardParams": {"type": "x-car"},
+            }})
+        );
+
+        let converted = VcardContact::from_jscontact(&card).unwrap();
+        assert_eq!(
+            converted.properties[2].params,
+            Some(vec![
+                (String::from("VALUE"), vec![String::from("uri")]),
+                (
+                    String::from("TYPE"),
+                    vec![
+                        String::from("home"),
+                        String::from("cell"),
+                        String::from("x-car")
+                    ]
+                ),
+                (String::from("PREF"), vec![String::from("1")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn keep_the_unknown_properties() {
+        let card = json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "urn:uuid:1",
+            "emails": {
+                "work": {
+                    "address": "jane@example.com",
+                    "label": "Office",
+                },
+            },
+            "speakToAs": {"grammaticalGender": "feminine"},
+            "vCardProps": [["x-custom", {}, "text", "value"]],
+        });
+        let contact = VcardContact::from_jscontact(&card).unwrap();
+
+        let jsprops: Vec<(String, Option<String>)> = contact
+            .properties
+            .iter()
+            .filter(|p| p.name == "JSPROP")
+            .map(|p| (first_param(p, "JSPTR").unwrap(), p.value.clone()))
+            .collect();
+        assert_eq!(
+            jsprops,
+            vec![
+                (
+                    String::from("emails/work/label"),
+                    Some(String::from("\"Office\""))
+                ),
+                (
+                    String::from("speakToAs"),
+                    Some(String::from("{\"grammaticalGender\":\"feminine\"}"))
+                ),
+            ]
+        );
+        assert!(contact.properties.iter().any(|p| p.name == "X-CUSTOM"));
+        assert_eq!(contact.to_jscontact().unwrap(), card);
+    }
+
+    #[test]
+    fn follow_the_pointers() {
+        let mut card = Map::new();
+
+        assert!(set_pointer(&mut card, "/a~1b/c", json!(1)));
+        assert_eq!(Value::Object(card.clone()), json!({"a/b": {"c": 1}}));
+        assert!(!set_pointer(&mut card, "a~1b/c/d", json!(2)));
+        assert!(set_pointer(&mut card, "a~1b/c", Value::Null));
+        assert_eq!(Value::Object(card), json!({"a/b": {}}));
+    }
+}
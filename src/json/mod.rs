@@ -0,0 +1,798 @@
+//! Convert the components to and from their JSON representation.
+//!
+//! * jCal ([RFC7265](https://tools.ietf.org/html/rfc7265)) for the iCalendar components, with
+//!   the `jcal` feature.
+//! * jCard ([RFC7095](https://tools.ietf.org/html/rfc7095)) for the vCard contacts, with the
+//!   `jcard` feature.
+//! * JSCalendar ([RFC8984](https://tools.ietf.org/html/rfc8984)) for the events and tasks, with
+//!   the `jscalendar` feature. Unlike jCal, it maps the properties to their own JSON model, see
+//!   the [`jscalendar`](jscalendar/index.html) module.
+//! * JSContact ([RFC9553](https://tools.ietf.org/html/rfc9553)) for the contacts, with the
+//!   `jscontact` feature, see the [`jscontact`](jscontact/index.html) module.
+//!
+//! A component is an array holding its name, its properties and its sub-components. A
+//! property is an array holding its name, its parameters, the type of its value and the value
+//! itself, converted to the matching JSON type:
+//!
+//! ```json
+//! ["vevent",
+//!   [
+//!     ["dtstart", {"tzid": "Europe/Paris"}, "date-time", "2024-01-02T12:00:00"],
+//!     ["rrule", {}, "recur", {"freq": "WEEKLY", "byday": ["MO", "TH"]}],
+//!     ["summary", {}, "text", "Meeting"]
+//!   ],
+//!   []
+//! ]
+//! ```
+//!
+//! The `TEXT` values are unescaped, the dates and times written in their extended format
+//! (`2024-01-02T12:00:00`), the `INTEGER`, `FLOAT` and `BOOLEAN` values as JSON numbers and
+//! booleans and the `RECUR` ones as objects. The structured values (`N`, `ADR`,
+//! `REQUEST-STATUS`...) are arrays of components, a component holding several values being
+//! itself an array. The `VALUE` parameter becomes the type of the property and is only written
+//! back, as the last parameter, when it differs from the default type of the property. The
+//! order of the other parameters and of the rule parts is kept, so that a calendar converted
+//! back and forth holds the same values. It isn't always generated identically though: the
+//! `VALUE` parameter may move (`CONFERENCE;LABEL=Call;VALUE=URI`), a `VALUE` parameter naming
+//! the default type is dropped and the special characters of the `TEXT` values come back
+//! escaped (`SUMMARY:a\, b`).
+//!
+//! A jCard contact has no sub-components and the group of a property becomes its `group`
+//! parameter. The truncated and reduced vCard dates and times (`--0203`) are kept as such in
+//! their extended format (`--02-03`). The quoted vCard lists (`TYPE="work,voice"`) come back as
+//! plain lists (`TYPE=work,voice`), which are equivalent.
+
+#[cfg(feature = "jcal")]
+pub mod jcal;
+#[cfg(feature = "jcard")]
+pub mod jcard;
+#[cfg(feature = "jscalendar")]
+pub mod jscalendar;
+#[cfg(feature = "jscontact")]
+pub mod jscontact;
+
+#[cfg(feature = "jcal")]
+pub use self::jcal::Jcal;
+#[cfg(feature = "jcard")]
+pub use self::jcard::Jcard;
+#[cfg(feature = "jscalendar")]
+pub use self::jscalendar::JsCalendar;
+#[cfg(feature = "jscontact")]
+pub use self::jscontact::JsContact;
+
+// Internal mods
+use crate::property::{
+    escape_component, escape_text, split_unescaped, Format, Property, VCARD_LIST_PARAMS,
+};
+use crate::value::extended::{
+    add_value_param, from_extended, recur_from_parts, recur_to_parts, to_extended,
+};
+use crate::value::{
+    decode, parse_duration, Value as TypedValue, ValueError, ValueType, TEXT_LISTS,
+    VCARD_STRUCTURED,
+};
+use serde_json::{Map, Number, Value};
+
+#[derive(Debug, Error)]
+pub enum JsonError {
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Value(#[from] ValueError),
+    #[error("Invalid {kind}: {found}.")]
+    Invalid { kind: &'static str, found: String },
+    #[error("Expected a {expected} component, found {found}.")]
+    UnexpectedComponent { expected: String, found: String },
+}
+
+impl JsonError {
+    fn invalid(kind: &'static str, found: &Value) -> JsonError {
+        JsonError::Invalid {
+            kind,
+            found: found.to_string(),
+        }
+    }
+}
+
+/// The rule parts of a `RECUR` value holding integers.
+const RECUR_INTEGERS: &[&str] = &[
+    "COUNT",
+    "INTERVAL",
+    "BYSECOND",
+    "BYMINUTE",
+    "BYHOUR",
+    "BYMONTHDAY",
+    "BYYEARDAY",
+    "BYWEEKNO",
+    "BYMONTH",
+    "BYSETPOS",
+];
+
+/// Return whether the property holds a structured value, whose components are separated by
+/// `;`.
+fn is_structured(name: &str, value_type: &ValueType, format: Format) -> bool {
+    match (format, value_type) {
+        (Format::Ical, ValueType::Float) => name == "GEO",
+        (Format::Ical, ValueType::Text) => name == "REQUEST-STATUS",
+        (Format::Vcard, ValueType::Text) => VCARD_STRUCTURED.contains(&name),
+        _ => false,
+    }
+}
+
+/// Convert a single value from its iCalendar or vCard form.
+fn value_to_json(raw: &str, value_type: &ValueType, format: Format) -> Option<Value> {
+    if let Some(extended) = to_extended(raw, value_type, format) {
+        return Some(Value::String(extended));
+    }
+
+    Some(match decode(raw, value_type, None)? {
+        TypedValue::Boolean(boolean) => Value::Bool(boolean),
+        TypedValue::Float(float) => Value::Number(Number::from_f64(float)?),
+        TypedValue::Integer(integer) => Value::from(integer),
+        TypedValue::Recur(_) => recur_to_json(raw)?,
+        TypedValue::Text(text) => Value::String(text),
+        TypedValue::Binary(_)
+        | TypedValue::CalAddress(_)
+        | TypedValue::Date(_)
+        | TypedValue::DateTime(_)
+        | TypedValue::Duration(_)
+        | TypedValue::PartialDateTime(_)
+        | TypedValue::Period(_)
+        | TypedValue::Time { .. }
+        | TypedValue::Uri(_)
+        | TypedValue::UtcOffset(_)
+        | TypedValue::Unknown(_) => Value::String(raw.to_string()),
+    })
+}
+
+/// Convert a single value back to its iCalendar or vCard form.
+fn value_from_json(json: &Value, value_type: &ValueType, format: Format) -> Option<String> {
+    let text = json.as_str();
+
+    Some(match value_type {
+        ValueType::Date | ValueType::DateTime | ValueType::Period | ValueType::Time => {
+            from_extended(text?, value_type, format)?
+        }
+        ValueType::DateAndOrTime | ValueType::Timestamp if format == Format::Vcard => {
+            from_extended(text?, value_type, format)?
+        }
+        ValueType::UtcOffset => from_extended(text?, value_type, format)?,
+        ValueType::Boolean => String::from(if json.as_bool()? { "TRUE" } else { "FALSE" }),
+        ValueType::Float => json.as_f64()?.to_string(),
+        ValueType::Integer => json.as_i64()?.to_string(),
+        ValueType::Recur => recur_from_json(json)?,
+        ValueType::Text => escape_text(text?, format),
+        ValueType::Duration => {
+            parse_duration(text?)?;
+            text?.to_string()
+        }
+        ValueType::Binary
+        | ValueType::CalAddress
+        | ValueType::DateAndOrTime
+        | ValueType::LanguageTag
+        | ValueType::Timestamp
+        | ValueType::Uri
+        | ValueType::Unknown(_) => text?.to_string(),
+    })
+}
+
+/// Convert a `RECUR` value into an object, holding the lists of several values as arrays.
+fn recur_to_json(raw: &str) -> Option<Value> {
+    let mut rule = Map::new();
+
+    for (name, items) in recur_to_parts(raw)? {
+        let mut values = items
+            .into_iter()
+            .map(|item| {
+                if RECUR_INTEGERS.contains(&name.as_str()) {
+                    item.parse::<i64>().ok().map(Value::from)
+                } else {
+                    Some(Value::String(item))
+                }
+            })
+            .collect::<Option<Vec<Value>>>()?;
+
+        let value = if values.len() == 1 {
+            values.remove(0)
+        } else {
+            Value::Array(values)
+        };
+        rule.insert(name.to_lowercase(), value);
+    }
+
+    Some(Value::Object(rule))
+}
+
+fn recur_from_json(json: &Value) -> Option<String> {
+    let parts = json
+        .as_object()?
+        .iter()
+        .map(|(name, value)| {
+            let items = match value {
+                Value::Array(items) => items.iter().collect(),
+                item => vec![item],
+            };
+            let items = items
+                .into_iter()
+                .map(|item| match item {
+                    Value::String(text) => Some(text.clone()),
+                    Value::Number(number) => Some(number.to_string()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<String>>>()?;
+
+            Some((name.clone(), items))
+        })
+        .collect::<Option<Vec<(String, Vec<String>)>>>()?;
+
+    recur_from_parts(&parts)
+}
+
+/// Convert the value of `property`, as a `value_type`, into its JSON values.
+fn values_to_json(
+    property: &Property,
+    value_type: &ValueType,
+    format: Format,
+) -> Result<Vec<Value>, ValueError> {
+    let name = property.name.to_uppercase();
+    let value = property.value.as_deref().unwrap_or_default();
+    let invalid = |raw: &str| ValueError::InvalidValue {
+        line: property.line(),
+        property: property.name.clone(),
+        value_type: value_type.clone(),
+        value: raw.to_string(),
+    };
+    let convert = |raw: &str| value_to_json(raw, value_type, format).ok_or_else(|| invalid(raw));
+
+    if value.is_empty() {
+        return Ok(vec![Value::String(String::new())]);
+    }
+
+    // The structured values are an array of components, unless they have a single one.
+    if is_structured(&name, value_type, format) {
+        let mut components = split_unescaped(value, ';')
+            .into_iter()
+            .map(|component| {
+                let mut values = match format {
+                    Format::Ical => vec![convert(component)?],
+                    Format::Vcard => split_unescaped(component, ',')
+                        .into_iter()
+                        .map(convert)
+                        .collect::<Result<Vec<Value>, ValueError>>()?,
+                };
+
+                Ok(if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    Value::Array(values)
+                })
+            })
+            .collect::<Result<Vec<Value>, ValueError>>()?;
+
+        return Ok(vec![if components.len() == 1 {
+            components.remove(0)
+        } else {
+            Value::Array(components)
+        }]);
+    }
+
+    let raws = match value_type {
+        ValueType::Text if TEXT_LISTS.contains(&name.as_str()) => split_unescaped(value, ','),
+        ValueType::Binary
+        | ValueType::CalAddress
+        | ValueType::LanguageTag
+        | ValueType::Recur
+        | ValueType::Text
+        | ValueType::Unknown(_)
+        | ValueType::Uri => vec![value],
+        _ => value.split(',').collect(),
+    };
+
+    raws.into_iter().map(convert).collect()
+}
+
+/// Convert the JSON values of a property, as a `value_type`, into its value.
+fn values_from_json(
+    property: &Property,
+    values: &[Value],
+    value_type: &ValueType,
+    format: Format,
+) -> Result<String, ValueError> {
+    let invalid = |json: &Value| ValueError::InvalidValue {
+        line: None,
+        property: property.name.clone(),
+        value_type: value_type.clone(),
+        value: json.to_string(),
+    };
+    let convert = |json: &Value| match json {
+        Value::String(text) if text.is_empty() => Ok(String::new()),
+        json => value_from_json(json, value_type, format).ok_or_else(|| invalid(json)),
+    };
+
+    if !is_structured(&property.name, value_type, format) {
+        return values
+            .iter()
+            .map(convert)
+            .collect::<Result<Vec<String>, ValueError>>()
+            .map(|values| values.join(","));
+    }
+
+    // The `;` and `,` separating the components and their values are escaped in each value.
+    let convert_component = |json: &Value| match (json, value_type) {
+        (Value::String(text), ValueType::Text) => Ok(escape_component(text)),
+        (json, _) => convert(json),
+    };
+    let components = match values {
+        [Value::Array(components)] => components.as_slice(),
+        components => components,
+    };
+
+    components
+        .iter()
+        .map(|component| match component {
+            Value::Array(values) => values
+                .iter()
+                .map(convert_component)
+                .collect::<Result<Vec<String>, ValueError>>()
+                .map(|values| values.join(",")),
+            value => convert_component(value),
+        })
+        .collect::<Result<Vec<String>, ValueError>>()
+        .map(|components| components.join(";"))
+}
+
+/// Convert a property into a JSON array.
+pub(crate) fn property_to_json(property: &Property, format: Format) -> Result<Value, JsonError> {
+    let value_type = property.value_type(format);
+
+    let mut params = Map::new();
+    if let Some(group) = &property.group {
+        params.insert(String::from("group"), Value::String(group.clone()));
+    }
+    for (name, values) in property.params.iter().flatten() {
+        if name.eq_ignore_ascii_case("VALUE") {
+            continue;
+        }
+
+        let values = values
+            .iter()
+            .flat_map(|value| match format {
+                Format::Vcard if VCARD_LIST_PARAMS.contains(&name.to_uppercase().as_str()) => {
+                    value.split(',').collect()
+                }
+                _ => vec![value.as_str()],
+            })
+            .map(|value| Value::String(value.to_string()));
+
+        // A parameter found several times is merged.
+        if let Value::Array(list) = params
+            .entry(name.to_lowercase())
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            list.extend(values);
+        }
+    }
+    for value in params.values_mut() {
+        let single = match value {
+            Value::Array(list) if list.len() == 1 => list.pop(),
+            _ => None,
+        };
+        if let Some(single) = single {
+            *value = single;
+        }
+    }
+
+    let mut json = vec![
+        Value::String(property.name.to_lowercase()),
+        Value::Object(params),
+        Value::String(value_type.to_string().to_lowercase()),
+    ];
+    json.extend(values_to_json(property, &value_type, format)?);
+
+    Ok(Value::Array(json))
+}
+
+/// Convert a JSON array into a property.
+///
+/// The `VALUE` parameter is added when the type of the value isn't the default one or when
+/// it's required.
+pub(crate) fn property_from_json(json: &Value, format: Format) -> Result<Property, JsonError> {
+    let invalid = || JsonError::invalid("property", json);
+    let elements = json
+        .as_array()
+        .filter(|e| e.len() >= 4)
+        .ok_or_else(invalid)?;
+
+    let mut property = Property::new();
+    property.name = elements[0].as_str().ok_or_else(invalid)?.to_uppercase();
+
+    let mut params = Vec::new();
+    for (name, value) in elements[1].as_object().ok_or_else(invalid)? {
+        if name.eq_ignore_ascii_case("VALUE") {
+            continue;
+        }
+
+        let values = match value {
+            Value::String(value) => vec![value.clone()],
+            Value::Array(values) => values
+                .iter()
+                .map(|value| value.as_str().map(String::from))
+                .collect::<Option<Vec<String>>>()
+                .ok_or_else(|| JsonError::invalid("parameter", value))?,
+            value => return Err(JsonError::invalid("parameter", value)),
+        };
+
+        if format == Format::Vcard && name.eq_ignore_ascii_case("GROUP") {
+            property.group = values.into_iter().next();
+        } else {
+            params.push((name.to_uppercase(), values));
+        }
+    }
+    if !params.is_empty() {
+        property.params = Some(params);
+    }
+
+    let type_name = elements[2].as_str().ok_or_else(invalid)?;
+    let value = if type_name.eq_ignore_ascii_case("unknown") {
+        elements[3].as_str().ok_or_else(invalid)?.to_string()
+    } else {
+        let value_type = ValueType::from_name(type_name);
+        let value = values_from_json(&property, &elements[3..], &value_type, format)?;
+        add_value_param(&mut property, &value_type, format);
+
+        value
+    };
+
+    // As when parsing, an empty value is missing.
+    if !value.is_empty() {
+        property.value = Some(value);
+    }
+
+    Ok(property)
+}
+
+/// Convert a component into a JSON array.
+///
+/// The sub-components of a vCard contact are only written if it has some.
+pub(crate) fn component_to_json(
+    name: &str,
+    properties: &[Property],
+    components: Vec<Value>,
+    format: Format,
+) -> Result<Value, JsonError> {
+    let properties = properties
+        .iter()
+        .map(|property| property_to_json(property, format))
+        .collect::<Result<Vec<Value>, JsonError>>()?;
+
+    let mut json = vec![Value::String(name.to_lowercase()), Value::Array(properties)];
+    if format == Format::Ical || !components.is_empty() {
+        json.push(Value::Array(components));
+    }
+
+    Ok(Value::Array(json))
+}
+
+/// Return the name of a JSON component, in uppercase.
+pub(crate) fn component_name(json: &Value) -> Result<String, JsonError> {
+    json.get(0)
+        .and_then(Value::as_str)
+        .map(str::to_uppercase)
+        .ok_or_else(|| JsonError::invalid("component", json))
+}
+
+/// Split a JSON component into its name, in uppercase, its properties and its
+/// sub-components, which may be missing.
+pub(crate) fn component_from_json(
+    json: &Value,
+    format: Format,
+) -> Result<(String, Vec<Property>, &[Value]), JsonError> {
+    let invalid = || JsonError::invalid("component", json);
+    let elements = json
+        .as_array()
+        .filter(|e| e.len() == 2 || e.len() == 3)
+        .ok_or_else(invalid)?;
+
+    let properties = elements[1]
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|property| property_from_json(property, format))
+        .collect::<Result<Vec<Property>, JsonError>>()?;
+    let components = match elements.get(2) {
+        Some(components) => components.as_array().ok_or_else(invalid)?,
+        None => &[][..],
+    };
+
+    Ok((component_name(json)?, properties, components))
+}
+
+/// Return an error if the component is named `found` instead of `expected`.
+pub(crate) fn expect_component(found: &str, expected: &str) -> Result<(), JsonError> {
+    if found == expected {
+        Ok(())
+    } else {
+        Err(JsonError::UnexpectedComponent {
+            expected: expected.to_string(),
+            found: found.to_string(),
+        })
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+    use serde_json::json;
+
+    fn property(name: &str, value: &str, params: &[(&str, &str)]) -> Property {
+        let mut property = Property::new();
+        property.name = name.to_string();
+        property.value = Some(value.to_string());
+        if !params.is_empty() {
+            property.params = Some(
+                params
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
+                    .collect(),
+            );
+        }
+
+        property
+    }
+
+    /// Convert `property` into JSON, check the result and convert it back.
+    fn round_trip_in(format: Format, property: Property, expected: Value) {
+        let json = property_to_json(&property, format).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(property_from_json(&json, format).unwrap(), property);
+    }
+
+    fn round_trip(property: Property, expected: Value) {
+        round_trip_in(Format::Ical, property, expected)
+    }
+
+    #[test]
+    fn convert_the_value_types() {
+        round_trip(
+            property("GEO", "37.386013;-122.082932", &[]),
+            json!(["geo", {}, "float", [37.386013, -122.082932]]),
+        );
+        round_trip(
+            property("REQUEST-STATUS", "2.0;Success", &[]),
+            json!(["request-status", {}, "text", ["2.0", "Success"]]),
+        );
+        round_trip(
+            property("CATEGORIES", "Work,Rock\\, Paper", &[]),
+            json!(["categories", {}, "text", "Work", "Rock, Paper"]),
+        );
+        round_trip(
+            property("PERCENT-COMPLETE", "39", &[]),
+            json!(["percent-complete", {}, "integer", 39]),
+        );
+        round_trip(
+            property("X-BOOL", "TRUE", &[("VALUE", "BOOLEAN")]),
+            json!(["x-bool", {}, "boolean", true]),
+        );
+        round_trip(
+            property("EXDATE", "20240102,20240103", &[("VALUE", "DATE")]),
+            json!(["exdate", {}, "date", "2024-01-02", "2024-01-03"]),
+        );
+        round_trip(
+            property("X-TIME", "123000Z", &[("VALUE", "TIME")]),
+            json!(["x-time", {}, "time", "12:30:00Z"]),
+        );
+        round_trip(
+            property("TZOFFSETTO", "+013045", &[]),
+            json!(["tzoffsetto", {}, "utc-offset", "+01:30:45"]),
+        );
+        round_trip(
+            property(
+                "FREEBUSY",
+                "19970308T160000Z/19970308T163000Z",
+                &[("FBTYPE", "BUSY")],
+            ),
+            json!(["freebusy", {"fbtype": "BUSY"}, "period", "1997-03-08T16:00:00Z/1997-03-08T16:30:00Z"]),
+        );
+        round_trip(
+            property(
+                "RRULE",
+                "FREQ=MONTHLY;UNTIL=20241231T235959Z;BYDAY=1MO,-1FR",
+                &[],
+            ),
+            json!(["rrule", {}, "recur", {
+                "freq": "MONTHLY",
+                "until": "2024-12-31T23:59:59Z",
+                "byday": ["1MO", "-1FR"]
+            }]),
+        );
+        round_trip(
+            property("X-UNTYPED", "a\\,b", &[("VALUE", "X-NAME")]),
+            json!(["x-untyped", {}, "x-name", "a\\,b"]),
+        );
+    }
+
+    #[test]
+    fn keep_the_params() {
+        let mut attendee = property("ATTENDEE", "mailto:b@example.com", &[("CN", "B")]);
+        attendee.params.as_mut().unwrap().push((
+            String::from("DELEGATED-FROM"),
+            vec![
+                String::from("mailto:a@example.com"),
+                String::from("mailto:c@example.com"),
+            ],
+        ));
+
+        round_trip(
+            attendee,
+            json!(["attendee", {
+                "cn": "B",
+                "delegated-from": ["mailto:a@example.com", "mailto:c@example.com"]
+            }, "cal-address", "mailto:b@example.com"]),
+        );
+    }
+
+    #[test]
+    fn write_the_required_value_params() {
+        round_trip(
+            property("REFRESH-INTERVAL", "P1W", &[("VALUE", "DURATION")]),
+            json!(["refresh-interval", {}, "duration", "P1W"]),
+        );
+
+        let json = json!(["x-foo", {}, "unknown", "a\\,b"]);
+        assert_eq!(
+            property_from_json(&json, Format::Ical).unwrap(),
+            property("X-FOO", "a\\,b", &[])
+        );
+    }
+
+    #[test]
+    fn keep_the_same_values() {
+        let url = "https://chat.example.com/audio?id=123456";
+        let cases = [
+            (
+                property("CONFERENCE", url, &[("VALUE", "URI"), ("LABEL", "Call")]),
+                property("CONFERENCE", url, &[("LABEL", "Call"), ("VALUE", "URI")]),
+            ),
+            (
+                property("ATTACH", url, &[("VALUE", "URI")]),
+                property("ATTACH", url, &[]),
+            ),
+            (
+                property("SUMMARY", "a, b", &[]),
+                property("SUMMARY", "a\\, b", &[]),
+            ),
+        ];
+
+        for (property, expected) in cases.iter() {
+            let json = property_to_json(property, Format::Ical).unwrap();
+            assert_eq!(property_from_json(&json, Format::Ical).unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn reject_the_invalid_values() {
+        let error = property_to_json(&property("DTSTART", "tomorrow", &[]), Format::Ical);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Invalid DATE-TIME value \"tomorrow\" for DTSTART."
+        );
+
+        let error = property_from_json(&json!(["priority", {}, "integer", "1"]), Format::Ical);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Invalid INTEGER value \"\\\"1\\\"\" for PRIORITY."
+        );
+
+        let error = property_from_json(&json!(["priority", {}]), Format::Ical);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Invalid property: [\"priority\",{}]."
+        );
+    }
+
+    #[test]
+    fn convert_the_vcard_values() {
+        round_trip_in(
+            Format::Vcard,
+            property("N", "Stevenson;John;Philip,Paul;Dr.;Jr.,M.D.,A.C.P.", &[]),
+            json!([
+                "n",
+                {},
+                "text",
+                [
+                    "Stevenson",
+                    "John",
+                    ["Philip", "Paul"],
+                    "Dr.",
+                    ["Jr.", "M.D.", "A.C.P."]
+                ]
+            ]),
+        );
+        round_trip_in(
+            Format::Vcard,
+            property("ORG", "ABC\\, Inc.;North American Division", &[]),
+            json!(["org", {}, "text", ["ABC, Inc.", "North American Division"]]),
+        );
+        round_trip_in(
+            Format::Vcard,
+            property("NOTE", "Mon;Fri", &[]),
+            json!(["note", {}, "text", "Mon;Fri"]),
+        );
+
+        let mut name = property("FN", "Mr. John Q. Public\\, Esq.", &[]);
+        name.group = Some(String::from("CONTACT"));
+        round_trip_in(
+            Format::Vcard,
+            name,
+            json!(["fn", {"group": "CONTACT"}, "text", "Mr. John Q. Public, Esq."]),
+        );
+    }
+
+    #[test]
+    fn keep_the_reduced_vcard_dates() {
+        let cases = [
+            ("BDAY", "--0203", "", "date-and-or-time", "--02-03"),
+            ("BDAY", "19850412", "", "date-and-or-time", "1985-04-12"),
+            ("BDAY", "T102200Z", "", "date-and-or-time", "T10:22:00Z"),
+            ("BDAY", "---12T10", "", "date-and-or-time", "---12T10"),
+            ("X-DATE", "1985-04", "date", "date", "1985-04"),
+            ("X-TIME", "-2200", "time", "time", "-22:00"),
+            ("X-TIME", "102200-0800", "time", "time", "10:22:00-08:00"),
+            (
+                "REV",
+                "19951031T222710Z",
+                "",
+                "timestamp",
+                "1995-10-31T22:27:10Z",
+            ),
+            (
+                "ANNIVERSARY",
+                "20090808T1430-0500",
+                "",
+                "date-and-or-time",
+                "2009-08-08T14:30-05:00",
+            ),
+        ];
+
+        for (name, value, value_type, json_type, expected) in cases.iter() {
+            let params: &[(&str, &str)] = if value_type.is_empty() {
+                &[]
+            } else {
+                &[("VALUE", value_type)]
+            };
+
+            round_trip_in(
+                Format::Vcard,
+                property(name, value, params),
+                json!([name.to_lowercase(), {}, json_type, expected]),
+            );
+        }
+    }
+
+    #[test]
+    fn merge_the_vcard_list_params() {
+        let mut email = property("EMAIL", "home@example.com", &[("TYPE", "work,voice")]);
+        email
+            .params
+            .as_mut()
+            .unwrap()
+            .push((String::from("TYPE"), vec![String::from("HOME")]));
+
+        let json = property_to_json(&email, Format::Vcard).unwrap();
+        assert_eq!(
+            json,
+            json!(["email", {"type": ["work", "voice", "HOME"]}, "text", "home@example.com"])
+        );
+        assert_eq!(
+            property_from_json(&json, Format::Vcard).unwrap().params,
+            Some(vec![(
+                String::from("TYPE"),
+                vec![
+                    String::from("work"),
+                    String::from("voice"),
+                    String::from("HOME")
+                ]
+            )])
+        );
+    }
+}
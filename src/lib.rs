@@ -34,6 +34,22 @@
 #[macro_use]
 extern crate thiserror;
 
+#[cfg(feature = "chrono")]
+extern crate chrono;
+
+#[cfg(feature = "chrono-tz")]
+extern crate chrono_tz;
+
+#[cfg(feature = "encoding_rs")]
+extern crate encoding_rs;
+
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
+
+#[cfg(feature = "quick-xml")]
+extern crate quick_xml;
+
+#[cfg(any(feature = "property", feature = "generator"))]
 const PARAM_VALUE_DELIMITER: char = ',';
 const VALUE_DELIMITER: char = ':';
 const PARAM_DELIMITER: char = ';';
@@ -49,6 +65,9 @@ pub use crate::parser::ical::IcalParser;
 #[cfg(feature = "vcard")]
 pub use crate::parser::vcard::VcardParser;
 
+#[cfg(any(feature = "ical", feature = "vcard"))]
+pub use crate::parser::document::DocumentParser;
+
 #[cfg(feature = "property")]
 pub mod property;
 #[cfg(feature = "property")]
@@ -61,3 +80,18 @@ pub use crate::line::LineReader;
 
 #[cfg(feature = "generator")]
 pub mod generator;
+
+#[cfg(feature = "value")]
+pub mod value;
+
+#[cfg(feature = "recurrence")]
+pub mod recurrence;
+
+#[cfg(feature = "timezone")]
+pub mod timezone;
+
+#[cfg(any(feature = "jcal", feature = "jcard"))]
+pub mod json;
+
+#[cfg(feature = "xcal")]
+pub mod xml;
@@ -36,13 +36,69 @@
 //! let reader = ical::LineReader::new(buf);
 //!
 //! for line in reader {
-//!     println!("{}", line);
+//!     println!("{}", line.unwrap());
 //! }
 //! ```
 
 use std::fmt;
-use std::io::BufRead;
+use std::io::{self, BufRead};
 use std::iter::Iterator;
+use std::string::FromUtf8Error;
+
+#[cfg(feature = "charset")]
+use encoding_rs::Encoding;
+
+#[cfg(feature = "serde-derive")]
+extern crate serde;
+
+#[derive(Debug, Error)]
+pub enum LineError {
+    #[error("Line {}: {}", line, source)]
+    Io { line: usize, source: io::Error },
+    #[error("Line {}: Invalid UTF-8 sequence at octet {}.", line, source.utf8_error().valid_up_to() + 1)]
+    InvalidUtf8 { line: usize, source: FromUtf8Error },
+    #[error("Line {}: Invalid {} sequence.", line, encoding)]
+    InvalidEncoding { line: usize, encoding: &'static str },
+    #[error("Line {}: Unknown charset {:?}.", line, charset)]
+    UnknownCharset { line: usize, charset: String },
+}
+
+impl LineError {
+    /// Return the number of the physical line which couldn't be read.
+    pub fn line(&self) -> usize {
+        match self {
+            LineError::Io { line, .. }
+            | LineError::InvalidUtf8 { line, .. }
+            | LineError::InvalidEncoding { line, .. }
+            | LineError::UnknownCharset { line, .. } => *line,
+        }
+    }
+}
+
+/// The location of a line, or of a component, in the stream.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    /// Number of the first physical line.
+    pub start_line: usize,
+    /// Number of the last physical line, blank lines excluded.
+    pub end_line: usize,
+    /// Offset of the first octet, from the start of the stream.
+    pub start: usize,
+    /// Offset following the last octet, line break excluded.
+    pub end: usize,
+}
+
+impl Span {
+    /// Return the span going from the start of `self` to the end of `end`.
+    pub fn to(self, end: Span) -> Span {
+        Span {
+            end_line: end.end_line,
+            end: end.end,
+            ..self
+        }
+    }
+}
 
 /// An unfolded raw line.
 ///
@@ -52,6 +108,7 @@ use std::iter::Iterator;
 pub struct Line {
     inner: String,
     number: usize,
+    span: Span,
 }
 
 impl Line {
@@ -60,6 +117,11 @@ impl Line {
         Line {
             inner: line,
             number: line_number,
+            span: Span {
+                start_line: line_number,
+                end_line: line_number,
+                ..Span::default()
+            },
         }
     }
 
@@ -72,6 +134,11 @@ impl Line {
     pub fn number(&self) -> usize {
         self.number
     }
+
+    /// Return the location of the line, from its first physical line to its last one.
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
 
 impl fmt::Display for Line {
@@ -83,15 +150,51 @@ impl fmt::Display for Line {
 /// A trait generic for implementing line reading use crate::by `PropertyParser`.
 pub trait LineRead {
     /// Return the next line unwrapped and formated.
-    fn next_line(&mut self) -> Option<Line>;
+    fn next_line(&mut self) -> Option<Result<Line, LineError>>;
 }
 
-#[derive(Debug, Clone, Default)]
+/// The encoding of the whole stream, detected from its first octets.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+enum StreamEncoding {
+    #[default]
+    Unknown,
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+#[derive(Debug, Default)]
 /// Take a `BufRead` and return the unfolded `Line`.
+///
+/// The stream is read as UTF-8, after its byte order mark if any. A stream starting with
+/// an UTF-16 byte order mark, or with an ASCII character on two octets, is read as UTF-16.
+///
+/// With the `charset` feature, the values of the properties having a `CHARSET` parameter
+/// (vCard 2.1) are transcoded to UTF-8, see also `LineReader::with_fallback`.
+///
+/// A read error or an invalid sequence is returned with the line number. The reading goes
+/// on with the next line after an invalid sequence but stops after an I/O error.
 pub struct LineReader<B> {
     reader: B,
-    saved: Option<String>,
+    saved: Option<Vec<u8>>,
+    /// The location of the saved line and of the last physical line read.
+    saved_span: Span,
+    last_span: Span,
+    /// The number of octets read.
+    offset: usize,
     number: usize,
+    encoding: StreamEncoding,
+    #[cfg(feature = "charset")]
+    fallback: Option<&'static Encoding>,
+    /// An error met after the start of a line, returned once the line is.
+    pending: Option<LineError>,
+    failed: bool,
+    /// Whether the octets of each line are kept, and the ones of the current line, of the
+    /// saved line and of the last physical line read.
+    keep_raw: bool,
+    raw: Vec<u8>,
+    saved_raw: Vec<u8>,
+    last_raw: Vec<u8>,
 }
 
 impl<B: BufRead> LineReader<B> {
@@ -100,67 +203,404 @@ impl<B: BufRead> LineReader<B> {
         LineReader {
             reader,
             saved: None,
+            saved_span: Span::default(),
+            last_span: Span::default(),
+            offset: 0,
             number: 0,
+            encoding: StreamEncoding::Unknown,
+            #[cfg(feature = "charset")]
+            fallback: None,
+            pending: None,
+            failed: false,
+            keep_raw: false,
+            raw: Vec::new(),
+            saved_raw: Vec::new(),
+            last_raw: Vec::new(),
+        }
+    }
+
+    /// Return a new `LineReader` decoding with `encoding` the lines which are neither
+    /// valid UTF-8 nor have a `CHARSET` parameter, as a legacy export would hold.
+    ///
+    /// ```rust
+    /// extern crate encoding_rs;
+    /// extern crate ical;
+    ///
+    /// let input: &[u8] = b"BEGIN:VCARD\r\nFN:Fran\xe7oise\r\nEND:VCARD\r\n";
+    /// let mut reader = ical::LineReader::with_fallback(input, encoding_rs::WINDOWS_1252);
+    ///
+    /// reader.next();
+    /// assert_eq!(reader.next().unwrap().unwrap().as_str(), "FN:Françoise");
+    /// ```
+    #[cfg(feature = "charset")]
+    pub fn with_fallback(reader: B, encoding: &'static Encoding) -> LineReader<B> {
+        LineReader {
+            fallback: Some(encoding),
+            ..LineReader::new(reader)
+        }
+    }
+
+    /// Keep the octets each line is read from, see `take_raw`.
+    #[cfg(any(feature = "ical", feature = "vcard"))]
+    pub(crate) fn keep_raw(&mut self) {
+        self.keep_raw = true;
+    }
+
+    /// Return the octets the last line was read from, with its line breaks and the blank
+    /// lines following it.
+    #[cfg(any(feature = "ical", feature = "vcard"))]
+    pub(crate) fn take_raw(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.raw)
+    }
+
+    /// Detect the encoding of the stream and skip its byte order mark.
+    fn detect_encoding(&mut self) -> io::Result<()> {
+        let (encoding, bom) = match self.reader.fill_buf()? {
+            [0xEF, 0xBB, 0xBF, ..] => (StreamEncoding::Utf8, 3),
+            [0xFF, 0xFE, ..] => (StreamEncoding::Utf16Le, 2),
+            [0xFE, 0xFF, ..] => (StreamEncoding::Utf16Be, 2),
+            [0, c, ..] if *c != 0 && c.is_ascii() => (StreamEncoding::Utf16Be, 0),
+            [c, 0, ..] if *c != 0 && c.is_ascii() => (StreamEncoding::Utf16Le, 0),
+            _ => (StreamEncoding::Utf8, 0),
+        };
+
+        // The emitters write UTF-8, only its byte order mark can be written back.
+        if self.keep_raw && encoding == StreamEncoding::Utf8 {
+            self.raw.extend_from_slice(&self.reader.fill_buf()?[..bom]);
+        }
+
+        self.reader.consume(bom);
+        self.offset += bom;
+        self.encoding = encoding;
+        Ok(())
+    }
+
+    /// Read the next physical line, without its line break.
+    fn read_line(&mut self) -> Result<Option<Vec<u8>>, LineError> {
+        let line = self.number + 1;
+
+        match self.read_raw_line() {
+            Ok(Some(Ok(buffer))) => Ok(Some(buffer)),
+            Ok(Some(Err(()))) => {
+                // The line is consumed, count it to keep the following numbers right.
+                self.number += 1;
+                Err(LineError::InvalidEncoding {
+                    line,
+                    encoding: "UTF-16",
+                })
+            }
+            Ok(None) => Ok(None),
+            Err(source) => {
+                self.failed = true;
+                Err(LineError::Io { line, source })
+            }
+        }
+    }
+
+    /// Read the next physical line, returning an UTF-16 one as UTF-8 or `Err(())` if it
+    /// isn't valid.
+    fn read_raw_line(&mut self) -> io::Result<Option<Result<Vec<u8>, ()>>> {
+        if self.encoding == StreamEncoding::Unknown {
+            self.detect_encoding()?;
+        }
+
+        let start = self.offset;
+        let (line, unit) = match self.encoding {
+            StreamEncoding::Utf16Le | StreamEncoding::Utf16Be => (self.read_utf16_line()?, 2),
+            _ => (self.read_utf8_line()?.map(Ok), 1),
+        };
+
+        Ok(line.map(|line| {
+            line.map(|mut buffer| {
+                if self.keep_raw {
+                    self.last_raw.clone_from(&buffer);
+                }
+
+                let mut line_break = 0;
+                if buffer.ends_with(b"\n") {
+                    buffer.pop();
+                    line_break += unit;
+                    if buffer.ends_with(b"\r") {
+                        buffer.pop();
+                        line_break += unit;
+                    }
+                }
+
+                self.last_span = Span {
+                    start_line: self.number + 1,
+                    end_line: self.number + 1,
+                    start,
+                    end: self.offset - line_break,
+                };
+                buffer
+            })
+        }))
+    }
+
+    fn read_utf8_line(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut buffer = Vec::new();
+        if self.reader.read_until(b'\n', &mut buffer)? == 0 {
+            return Ok(None);
+        }
+        self.offset += buffer.len();
+
+        Ok(Some(buffer))
+    }
+
+    fn read_utf16_line(&mut self) -> io::Result<Option<Result<Vec<u8>, ()>>> {
+        let big_endian = self.encoding == StreamEncoding::Utf16Be;
+
+        // Read up to a line feed octet which is a whole unit.
+        let mut buffer = Vec::new();
+        while self.reader.read_until(b'\n', &mut buffer)? > 0 {
+            if big_endian {
+                if buffer.len() % 2 == 0 && buffer[buffer.len() - 2] == 0 {
+                    break;
+                }
+            } else if buffer.len() % 2 == 1 {
+                // The line feed is the low octet, the unit is one if the next octet is 0.
+                let next = self.reader.fill_buf()?.first().cloned();
+                if let Some(next) = next {
+                    self.reader.consume(1);
+                    buffer.push(next);
+                    if next == 0 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if buffer.is_empty() {
+            return Ok(None);
+        }
+        self.offset += buffer.len();
+        if buffer.len() % 2 == 1 {
+            return Ok(Some(Err(())));
+        }
+
+        let units = buffer.chunks(2).map(|pair| match big_endian {
+            true => u16::from_be_bytes([pair[0], pair[1]]),
+            false => u16::from_le_bytes([pair[0], pair[1]]),
+        });
+
+        Ok(Some(
+            char::decode_utf16(units)
+                .collect::<Result<String, _>>()
+                .map(String::into_bytes)
+                .map_err(|_| ()),
+        ))
+    }
+
+    /// Decode an unfolded line to UTF-8.
+    fn decode(&self, buffer: Vec<u8>, line: usize) -> Result<String, LineError> {
+        #[cfg(feature = "charset")]
+        {
+            if self.encoding == StreamEncoding::Utf8 {
+                if let Some((charset, value)) = charset(&buffer) {
+                    let encoding = Encoding::for_label(charset.as_bytes()).ok_or_else(|| {
+                        LineError::UnknownCharset {
+                            line,
+                            charset: charset.to_string(),
+                        }
+                    })?;
+
+                    return match encoding
+                        .decode_without_bom_handling_and_without_replacement(&buffer[value..])
+                    {
+                        Some(text) => Ok(String::from_utf8_lossy(&buffer[..value]) + text),
+                        None => Err(LineError::InvalidEncoding {
+                            line,
+                            encoding: encoding.name(),
+                        }),
+                    }
+                    .map(|text| text.into_owned());
+                }
+            }
+        }
+
+        match String::from_utf8(buffer) {
+            Ok(text) => Ok(text),
+            #[cfg(feature = "charset")]
+            Err(err) if self.fallback.is_some() => {
+                let encoding = self.fallback.unwrap();
+                encoding
+                    .decode_without_bom_handling_and_without_replacement(err.as_bytes())
+                    .map(|text| text.into_owned())
+                    .ok_or(LineError::InvalidEncoding {
+                        line,
+                        encoding: encoding.name(),
+                    })
+            }
+            Err(source) => Err(LineError::InvalidUtf8 { line, source }),
         }
     }
 }
 
+/// Return the parameters of a raw line, with their value if any, and the position of the
+/// line value.
+fn params(line: &[u8]) -> Option<(impl Iterator<Item = (&str, Option<&str>)>, usize)> {
+    let mut in_quotes = false;
+    let end = line.iter().position(|&c| {
+        if c == ::PARAM_QUOTE as u8 {
+            in_quotes = !in_quotes;
+        }
+        c == ::VALUE_DELIMITER as u8 && !in_quotes
+    })?;
+
+    let header = std::str::from_utf8(&line[..end]).ok()?;
+    let params = header.split(::PARAM_DELIMITER).skip(1).map(|param| {
+        let mut parts = param.splitn(2, ::PARAM_NAME_DELIMITER);
+        let key = parts.next().unwrap_or_default().trim();
+        (
+            key,
+            parts
+                .next()
+                .map(|value| value.trim().trim_matches(::PARAM_QUOTE)),
+        )
+    });
+
+    Some((params, end + 1))
+}
+
+/// Return the `CHARSET` parameter of a raw line, if any, and the position of its value.
+#[cfg(feature = "charset")]
+fn charset(line: &[u8]) -> Option<(&str, usize)> {
+    let (mut params, value) = params(line)?;
+
+    params
+        .find_map(|(key, charset)| charset.filter(|_| key.eq_ignore_ascii_case("CHARSET")))
+        .map(|charset| (charset, value))
+}
+
+/// Whether a raw line holds a quoted-printable value, written `ENCODING=QUOTED-PRINTABLE` or
+/// only `QUOTED-PRINTABLE` in vCard 2.1.
+fn quoted_printable(line: &[u8]) -> bool {
+    let is_qp = |value: &str| value.eq_ignore_ascii_case("QUOTED-PRINTABLE");
+
+    params(line).is_some_and(|(mut params, _)| {
+        params.any(|(key, value)| match value {
+            Some(value) => key.eq_ignore_ascii_case("ENCODING") && is_qp(value),
+            None => is_qp(key),
+        })
+    })
+}
+
 impl<B: BufRead> LineRead for LineReader<B> {
-    fn next_line(&mut self) -> Option<Line> {
-        let mut next_line = String::new();
+    fn next_line(&mut self) -> Option<Result<Line, LineError>> {
+        if let Some(err) = self.pending.take() {
+            return Some(Err(err));
+        }
+        if self.failed {
+            return None;
+        }
+
+        let mut next_line = Vec::new();
         let mut line_number: usize = 0;
+        let mut span = Span::default();
+        self.raw.clear();
 
         if let Some(start) = self.saved.take() {
             // If during the last iteration a new line have been saved, start with.
-            next_line.push_str(start.as_str());
+            next_line = start;
             self.number += 1;
             line_number = self.number;
+            span = self.saved_span;
+            self.raw.append(&mut self.saved_raw);
         } else {
             // This is the first iteration, next_start isn't been filled yet.
-            for line in self.reader.by_ref().lines() {
-                let line = line.ok()?;
+            loop {
+                let line = match self.read_line() {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(err) => return Some(Err(err)),
+                };
                 self.number += 1;
+                self.raw.append(&mut self.last_raw);
 
                 if !line.is_empty() {
-                    next_line = line.trim_end().to_string();
+                    next_line = line;
                     line_number = self.number;
+                    span = self.last_span;
                     break;
                 }
             }
         }
 
-        for line in self.reader.by_ref().lines() {
-            let mut line = line.ok()?;
+        // A quoted-printable value goes on with the next line after a trailing "=".
+        let soft_breaks = quoted_printable(&next_line);
+
+        loop {
+            let line = match self.read_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) if next_line.is_empty() => return Some(Err(err)),
+                Err(err) => {
+                    self.pending = Some(err);
+                    break;
+                }
+            };
 
-            if line.is_empty() {
+            if soft_breaks && trim_end(&next_line).ends_with(b"=") {
+                // This is a soft line break, the whole line is part of the value.
+                next_line.truncate(trim_end(&next_line).len() - 1);
+                next_line.extend_from_slice(&line);
+                self.number += 1;
+                span = span.to(self.last_span);
+            } else if line.is_empty() {
                 self.number += 1;
-            } else if line.starts_with(' ') || line.starts_with('\t') {
+            } else if line.starts_with(b" ") || line.starts_with(b"\t") {
                 // This is a multi-lines attribute.
 
                 // Remove the whitespace character and join with the current line.
-                line.remove(0);
-                next_line.push_str(line.trim_end());
+                next_line.extend_from_slice(&line[1..]);
                 self.number += 1;
+                span = span.to(self.last_span);
             } else {
                 // This is a new attribute so it need to be saved it for
                 // the next iteration.
-                self.saved = Some(line.trim().to_string());
+                self.saved = Some(trim_start(&line).to_vec());
+                self.saved_span = self.last_span;
+                self.saved_raw.append(&mut self.last_raw);
                 break;
             }
+
+            self.raw.append(&mut self.last_raw);
         }
 
+        // Only the end of the unfolded line is trimmed, the folding may happen anywhere.
+        next_line.truncate(trim_end(&next_line).len());
+
         if next_line.is_empty() {
             None
         } else {
-            Some(Line::new(next_line, line_number))
+            Some(self.decode(next_line, line_number).map(|line| Line {
+                span,
+                ..Line::new(line, line_number)
+            }))
         }
     }
 }
 
+fn trim_start(line: &[u8]) -> &[u8] {
+    let start = line
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(line.len());
+    &line[start..]
+}
+
+fn trim_end(line: &[u8]) -> &[u8] {
+    let end = line
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(0, |end| end + 1);
+    &line[..end]
+}
+
 impl<B: BufRead> Iterator for LineReader<B> {
-    type Item = Line;
+    type Item = Result<Line, LineError>;
 
-    fn next(&mut self) -> Option<Line> {
+    fn next(&mut self) -> Option<Result<Line, LineError>> {
         self.next_line()
     }
 }
@@ -0,0 +1,293 @@
+//! Parse a calendar or an address book without losing anything of its text.
+//!
+//! The `IcalParser` and the `VcardParser` sort the sub-components by type and set to
+//! uppercase the case-insensitive fields, and the emitters fold the lines anew, so a parsed
+//! object isn't written back as it was read.
+//!
+//! The `DocumentParser` returns a tree of `DocumentComponent`, holding the properties and the
+//! sub-components in their order, each line with the octets it was read from. With the
+//! `generator` feature, a `DocumentComponent` is emitted by writing back these octets for the
+//! lines which weren't changed since: an unmodified document is written byte-for-byte, but
+//! for an UTF-16 one written in UTF-8, and only the edited properties are formatted anew,
+//! which avoids the gratuitous changes a synchronization would see as conflicts.
+//!
+//! It works for both the Vcard and Ical format.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/ical_input.ics")
+//! .unwrap());
+//!
+//! for calendar in ical::DocumentParser::new(buf) {
+//!     let calendar = calendar.unwrap();
+//!
+//!     for component in calendar.components() {
+//!         println!("{}: {:?}", component.name(), component.get_property("SUMMARY"));
+//!     }
+//! }
+//! ```
+
+// Sys mods
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::BufRead;
+
+// Internal mods
+use crate::line::{LineReader, Span};
+use crate::parser::ParserError;
+use crate::property::{Property, PropertyParser};
+
+/// Return a digest of a property, to tell if it was changed since it was read.
+fn digest(property: &Property) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    property.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A property with the octets it was read from.
+#[derive(Debug, Clone)]
+pub struct DocumentProperty {
+    pub property: Property,
+    raw: Vec<u8>,
+    digest: u64,
+}
+
+impl DocumentProperty {
+    /// Return a new `DocumentProperty`, which wasn't read from anything.
+    pub fn new(property: Property) -> DocumentProperty {
+        DocumentProperty {
+            property,
+            raw: Vec::new(),
+            digest: 0,
+        }
+    }
+
+    fn read(property: Property, raw: Vec<u8>) -> DocumentProperty {
+        DocumentProperty {
+            digest: digest(&property),
+            property,
+            raw,
+        }
+    }
+
+    /// Return the octets the property was read from, with the line breaks and the blank
+    /// lines following it, unless the property was changed since.
+    ///
+    /// The lines of an UTF-16 stream are returned transcoded to UTF-8.
+    pub fn raw(&self) -> Option<&[u8]> {
+        if self.raw.is_empty() || digest(&self.property) != self.digest {
+            return None;
+        }
+
+        Some(&self.raw)
+    }
+}
+
+impl From<Property> for DocumentProperty {
+    fn from(property: Property) -> DocumentProperty {
+        DocumentProperty::new(property)
+    }
+}
+
+/// A property or a sub-component of a `DocumentComponent`.
+#[derive(Debug, Clone)]
+pub enum DocumentEntry {
+    Property(DocumentProperty),
+    Component(DocumentComponent),
+}
+
+/// A component keeping its properties and sub-components in their order.
+#[derive(Debug, Clone)]
+pub struct DocumentComponent {
+    begin: DocumentProperty,
+    pub entries: Vec<DocumentEntry>,
+    end: DocumentProperty,
+}
+
+impl DocumentComponent {
+    /// Return a new empty component, `VEVENT` for instance.
+    pub fn new(name: &str) -> DocumentComponent {
+        let delimiter = |key: &str| {
+            DocumentProperty::new(Property {
+                name: String::from(key),
+                value: Some(String::from(name)),
+                ..Property::new()
+            })
+        };
+
+        DocumentComponent {
+            begin: delimiter("BEGIN"),
+            entries: Vec::new(),
+            end: delimiter("END"),
+        }
+    }
+
+    /// Return the name of the component as it was written, `VEVENT` for instance.
+    pub fn name(&self) -> &str {
+        self.begin.property.value.as_deref().unwrap_or_default()
+    }
+
+    /// Return the `BEGIN` line of the component.
+    pub fn begin(&self) -> &DocumentProperty {
+        &self.begin
+    }
+
+    /// Return the `END` line of the component.
+    pub fn end(&self) -> &DocumentProperty {
+        &self.end
+    }
+
+    /// Return the location of the component, from its `BEGIN` line to its `END` one, if it
+    /// was parsed.
+    pub fn span(&self) -> Option<Span> {
+        Some(self.begin.property.span?.to(self.end.property.span?))
+    }
+
+    /// Return the properties of the component, in their order.
+    pub fn properties(&self) -> impl Iterator<Item = &Property> {
+        self.entries.iter().filter_map(|entry| match entry {
+            DocumentEntry::Property(property) => Some(&property.property),
+            DocumentEntry::Component(_) => None,
+        })
+    }
+
+    /// Return the sub-components of the component, in their order.
+    pub fn components(&self) -> impl Iterator<Item = &DocumentComponent> {
+        self.entries.iter().filter_map(|entry| match entry {
+            DocumentEntry::Property(_) => None,
+            DocumentEntry::Component(component) => Some(component),
+        })
+    }
+
+    /// Return the sub-components of the component, in their order.
+    pub fn components_mut(&mut self) -> impl Iterator<Item = &mut DocumentComponent> {
+        self.entries.iter_mut().filter_map(|entry| match entry {
+            DocumentEntry::Property(_) => None,
+            DocumentEntry::Component(component) => Some(component),
+        })
+    }
+
+    /// Find a given property, the names being compared in any case.
+    pub fn get_property<'c>(&'c self, name: &str) -> Option<&'c Property> {
+        self.properties()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Find a given property, to change it, the names being compared in any case.
+    pub fn get_property_mut<'c>(&'c mut self, name: &str) -> Option<&'c mut Property> {
+        self.entries.iter_mut().find_map(|entry| match entry {
+            DocumentEntry::Property(property)
+                if property.property.name.eq_ignore_ascii_case(name) =>
+            {
+                Some(&mut property.property)
+            }
+            _ => None,
+        })
+    }
+
+    /// Add the given property after the other entries.
+    pub fn add_property(&mut self, property: Property) {
+        self.entries.push(DocumentEntry::Property(property.into()));
+    }
+
+    /// Add the given sub-component after the other entries.
+    pub fn add_component(&mut self, component: DocumentComponent) {
+        self.entries.push(DocumentEntry::Component(component));
+    }
+}
+
+/// Reader returning `DocumentComponent` objects from a `BufRead`.
+///
+/// The octets kept for each line are the ones given by the `LineReader`: an UTF-16 stream is
+/// transcoded to UTF-8, so it's written back in UTF-8 and not as it was read. The other
+/// streams are written back byte-for-byte.
+pub struct DocumentParser<B> {
+    line_parser: PropertyParser<B>,
+}
+
+impl<B: BufRead> DocumentParser<B> {
+    /// Return a new `DocumentParser` from a `Reader`.
+    pub fn new(reader: B) -> DocumentParser<B> {
+        DocumentParser::from_line_reader(LineReader::new(reader))
+    }
+
+    /// Create a new `DocumentParser` from a `LineReader`, to read a legacy encoding.
+    pub fn from_line_reader(line_reader: LineReader<B>) -> DocumentParser<B> {
+        let mut line_parser = PropertyParser::new(line_reader);
+        line_parser.keep_raw();
+
+        DocumentParser { line_parser }
+    }
+
+    /// Read the next property.
+    fn next_property(&mut self) -> Option<Result<DocumentProperty, ParserError>> {
+        self.line_parser.next_raw().map(|res| {
+            let (property, raw) = res?;
+            Ok(DocumentProperty::read(property, raw))
+        })
+    }
+
+    /// Parse the content of a component up to its end.
+    fn parse(&mut self, begin: DocumentProperty) -> Result<DocumentComponent, ParserError> {
+        let mut entries = Vec::new();
+
+        loop {
+            let line = match self.next_property() {
+                Some(val) => val?,
+                None => {
+                    return Err(ParserError::NotComplete {
+                        span: begin.property.span.unwrap_or_default(),
+                    })
+                }
+            };
+
+            match line.property.name.to_uppercase().as_str() {
+                "END" => {
+                    return Ok(DocumentComponent {
+                        begin,
+                        entries,
+                        end: line,
+                    })
+                }
+                "BEGIN" => match line.property.value {
+                    Some(_) => entries.push(DocumentEntry::Component(self.parse(line)?)),
+                    None => {
+                        return Err(ParserError::NotComplete {
+                            span: line.property.span.unwrap_or_default(),
+                        })
+                    }
+                },
+
+                _ => entries.push(DocumentEntry::Property(line)),
+            };
+        }
+    }
+}
+
+impl<B: BufRead> Iterator for DocumentParser<B> {
+    type Item = Result<DocumentComponent, ParserError>;
+
+    fn next(&mut self) -> Option<Result<DocumentComponent, ParserError>> {
+        let begin = match self.next_property()? {
+            Ok(begin) => begin,
+            Err(err) => return Some(Err(err)),
+        };
+
+        if begin.property.name.to_uppercase() != "BEGIN"
+            || begin.property.value.is_none()
+            || begin.property.params.is_some()
+        {
+            return Some(Err(ParserError::MissingHeader {
+                span: begin.property.span.unwrap_or_default(),
+            }));
+        }
+
+        Some(self.parse(begin))
+    }
+}
@@ -6,6 +6,7 @@ use std::io::BufRead;
 extern crate serde;
 
 // Internal mods
+use crate::line::Span;
 use crate::parser::Component;
 use crate::parser::ParserError;
 use crate::property::{Property, PropertyParser};
@@ -21,6 +22,14 @@ pub struct IcalCalendar {
     pub journals: Vec<IcalJournal>,
     pub free_busys: Vec<IcalFreeBusy>,
     pub timezones: Vec<IcalTimeZone>,
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub availabilities: Vec<IcalAvailability>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalCalendar {
@@ -33,6 +42,9 @@ impl IcalCalendar {
             journals: Vec::new(),
             free_busys: Vec::new(),
             timezones: Vec::new(),
+            availabilities: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -50,43 +62,56 @@ impl Component for IcalCalendar {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        value: &str,
+        begin: &Property,
         line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        match value {
+        match begin.value.as_deref().unwrap_or_default() {
             "VALARM" => {
                 let mut alarm = IcalAlarm::new();
-                alarm.parse(line_parser)?;
+                alarm.parse(begin, line_parser)?;
                 self.alarms.push(alarm);
             }
             "VEVENT" => {
                 let mut event = IcalEvent::new();
-                event.parse(line_parser)?;
+                event.parse(begin, line_parser)?;
                 self.events.push(event);
             }
             "VTODO" => {
                 let mut todo = IcalTodo::new();
-                todo.parse(line_parser)?;
+                todo.parse(begin, line_parser)?;
                 self.todos.push(todo);
             }
             "VJOURNAL" => {
                 let mut journal = IcalJournal::new();
-                journal.parse(line_parser)?;
+                journal.parse(begin, line_parser)?;
                 self.journals.push(journal);
             }
             "VFREEBUSY" => {
                 let mut free_busy = IcalFreeBusy::new();
-                free_busy.parse(line_parser)?;
+                free_busy.parse(begin, line_parser)?;
                 self.free_busys.push(free_busy);
             }
             "VTIMEZONE" => {
                 let mut timezone = IcalTimeZone::new();
-                timezone.parse(line_parser)?;
+                timezone.parse(begin, line_parser)?;
                 self.timezones.push(timezone);
             }
-            _ => return Err(ParserError::InvalidComponent),
+            "VAVAILABILITY" => {
+                let mut availability = IcalAvailability::new();
+                availability.parse(begin, line_parser)?;
+                self.availabilities.push(availability);
+            }
+            name => {
+                let mut component = IcalUnknownComponent::new(name);
+                component.parse(begin, line_parser)?;
+                self.unknown_components.push(component);
+            }
         };
 
         Ok(())
@@ -97,12 +122,20 @@ impl Component for IcalCalendar {
 #[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
 pub struct IcalAlarm {
     pub properties: Vec<Property>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalAlarm {
     pub fn new() -> IcalAlarm {
         IcalAlarm {
             properties: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -120,12 +153,20 @@ impl Component for IcalAlarm {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        _: &str,
-        _: &RefCell<PropertyParser<B>>,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        Err(ParserError::InvalidComponent)
+        let mut component = IcalUnknownComponent::new(begin.value.as_deref().unwrap_or_default());
+        component.parse(begin, line_parser)?;
+        self.unknown_components.push(component);
+
+        Ok(())
     }
 }
 
@@ -134,6 +175,12 @@ impl Component for IcalAlarm {
 pub struct IcalEvent {
     pub properties: Vec<Property>,
     pub alarms: Vec<IcalAlarm>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalEvent {
@@ -141,6 +188,8 @@ impl IcalEvent {
         IcalEvent {
             properties: Vec::new(),
             alarms: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -158,18 +207,26 @@ impl Component for IcalEvent {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        value: &str,
+        begin: &Property,
         line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        match value {
+        match begin.value.as_deref().unwrap_or_default() {
             "VALARM" => {
                 let mut alarm = IcalAlarm::new();
-                alarm.parse(line_parser)?;
+                alarm.parse(begin, line_parser)?;
                 self.alarms.push(alarm);
             }
-            _ => return Err(ParserError::InvalidComponent),
+            name => {
+                let mut component = IcalUnknownComponent::new(name);
+                component.parse(begin, line_parser)?;
+                self.unknown_components.push(component);
+            }
         };
 
         Ok(())
@@ -180,12 +237,20 @@ impl Component for IcalEvent {
 #[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
 pub struct IcalJournal {
     pub properties: Vec<Property>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalJournal {
     pub fn new() -> IcalJournal {
         IcalJournal {
             properties: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -203,12 +268,20 @@ impl Component for IcalJournal {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        _: &str,
-        _: &RefCell<PropertyParser<B>>,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        Err(ParserError::InvalidComponent)
+        let mut component = IcalUnknownComponent::new(begin.value.as_deref().unwrap_or_default());
+        component.parse(begin, line_parser)?;
+        self.unknown_components.push(component);
+
+        Ok(())
     }
 }
 
@@ -217,6 +290,12 @@ impl Component for IcalJournal {
 pub struct IcalTodo {
     pub properties: Vec<Property>,
     pub alarms: Vec<IcalAlarm>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalTodo {
@@ -224,6 +303,8 @@ impl IcalTodo {
         IcalTodo {
             properties: Vec::new(),
             alarms: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -241,18 +322,26 @@ impl Component for IcalTodo {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        value: &str,
+        begin: &Property,
         line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        match value {
+        match begin.value.as_deref().unwrap_or_default() {
             "VALARM" => {
                 let mut alarm = IcalAlarm::new();
-                alarm.parse(line_parser)?;
+                alarm.parse(begin, line_parser)?;
                 self.alarms.push(alarm);
             }
-            _ => return Err(ParserError::InvalidComponent),
+            name => {
+                let mut component = IcalUnknownComponent::new(name);
+                component.parse(begin, line_parser)?;
+                self.unknown_components.push(component);
+            }
         };
 
         Ok(())
@@ -264,6 +353,12 @@ impl Component for IcalTodo {
 pub struct IcalTimeZone {
     pub properties: Vec<Property>,
     pub transitions: Vec<IcalTimeZoneTransition>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalTimeZone {
@@ -271,6 +366,8 @@ impl IcalTimeZone {
         IcalTimeZone {
             properties: Vec::new(),
             transitions: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -288,25 +385,33 @@ impl Component for IcalTimeZone {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        value: &str,
+        begin: &Property,
         line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
         use self::IcalTimeZoneTransitionType::{DAYLIGHT, STANDARD};
 
-        match value {
+        match begin.value.as_deref().unwrap_or_default() {
             "STANDARD" => {
                 let mut transition = IcalTimeZoneTransition::new(STANDARD);
-                transition.parse(line_parser)?;
+                transition.parse(begin, line_parser)?;
                 self.transitions.push(transition);
             }
             "DAYLIGHT" => {
                 let mut transition = IcalTimeZoneTransition::new(DAYLIGHT);
-                transition.parse(line_parser)?;
+                transition.parse(begin, line_parser)?;
                 self.transitions.push(transition);
             }
-            _ => return Err(ParserError::InvalidComponent),
+            name => {
+                let mut component = IcalUnknownComponent::new(name);
+                component.parse(begin, line_parser)?;
+                self.unknown_components.push(component);
+            }
         };
 
         Ok(())
@@ -326,6 +431,12 @@ pub enum IcalTimeZoneTransitionType {
 pub struct IcalTimeZoneTransition {
     pub transition: IcalTimeZoneTransitionType,
     pub properties: Vec<Property>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalTimeZoneTransition {
@@ -333,6 +444,8 @@ impl IcalTimeZoneTransition {
         IcalTimeZoneTransition {
             transition,
             properties: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -350,12 +463,20 @@ impl Component for IcalTimeZoneTransition {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        _: &str,
-        _: &RefCell<PropertyParser<B>>,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        Err(ParserError::InvalidComponent)
+        let mut component = IcalUnknownComponent::new(begin.value.as_deref().unwrap_or_default());
+        component.parse(begin, line_parser)?;
+        self.unknown_components.push(component);
+
+        Ok(())
     }
 }
 
@@ -363,12 +484,20 @@ impl Component for IcalTimeZoneTransition {
 #[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
 pub struct IcalFreeBusy {
     pub properties: Vec<Property>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl IcalFreeBusy {
     pub fn new() -> IcalFreeBusy {
         IcalFreeBusy {
             properties: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
 }
@@ -386,11 +515,195 @@ impl Component for IcalFreeBusy {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
+    fn add_sub_component<B: BufRead>(
+        &mut self,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
+    ) -> Result<(), ParserError> {
+        let mut component = IcalUnknownComponent::new(begin.value.as_deref().unwrap_or_default());
+        component.parse(begin, line_parser)?;
+        self.unknown_components.push(component);
+
+        Ok(())
+    }
+}
+
+/// A `VAVAILABILITY` component, as defined in
+/// [RFC7953 section 3.1](https://tools.ietf.org/html/rfc7953#section-3.1): the time it covers
+/// is busy, except for the time of its `AVAILABLE` sub-components.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct IcalAvailability {
+    pub properties: Vec<Property>,
+    pub available: Vec<IcalAvailable>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
+}
+
+impl IcalAvailability {
+    pub fn new() -> IcalAvailability {
+        IcalAvailability {
+            properties: Vec::new(),
+            available: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
+        }
+    }
+}
+
+impl Component for IcalAvailability {
+    fn add_property(&mut self, property: Property) {
+        self.properties.push(property);
+    }
+
+    fn get_property<'c>(&'c self, name: &str) -> Option<&'c Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn get_property_mut<'c>(&'c mut self, name: &str) -> Option<&'c mut Property> {
+        self.properties.iter_mut().find(|p| p.name == name)
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
+    fn add_sub_component<B: BufRead>(
+        &mut self,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
+    ) -> Result<(), ParserError> {
+        match begin.value.as_deref().unwrap_or_default() {
+            "AVAILABLE" => {
+                let mut available = IcalAvailable::new();
+                available.parse(begin, line_parser)?;
+                self.available.push(available);
+            }
+            name => {
+                let mut component = IcalUnknownComponent::new(name);
+                component.parse(begin, line_parser)?;
+                self.unknown_components.push(component);
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// An `AVAILABLE` component, as defined in
+/// [RFC7953 section 3.1](https://tools.ietf.org/html/rfc7953#section-3.1): a free time slot,
+/// which can recur, of a `VAVAILABILITY`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct IcalAvailable {
+    pub properties: Vec<Property>,
+    /// The sub-components this component doesn't know, such as the `X-` ones.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
+}
+
+impl IcalAvailable {
+    pub fn new() -> IcalAvailable {
+        IcalAvailable {
+            properties: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
+        }
+    }
+}
+
+impl Component for IcalAvailable {
+    fn add_property(&mut self, property: Property) {
+        self.properties.push(property);
+    }
+
+    fn get_property<'c>(&'c self, name: &str) -> Option<&'c Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn get_property_mut<'c>(&'c mut self, name: &str) -> Option<&'c mut Property> {
+        self.properties.iter_mut().find(|p| p.name == name)
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        _: &str,
-        _: &RefCell<PropertyParser<B>>,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        Err(ParserError::InvalidComponent)
+        let mut component = IcalUnknownComponent::new(begin.value.as_deref().unwrap_or_default());
+        component.parse(begin, line_parser)?;
+        self.unknown_components.push(component);
+
+        Ok(())
+    }
+}
+
+/// A component this crate doesn't know, such as an `X-` one, kept as it was read with its
+/// sub-components.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct IcalUnknownComponent {
+    /// The name of the component as it was written, `VPOLL` for instance.
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub sub_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
+}
+
+impl IcalUnknownComponent {
+    pub fn new(name: &str) -> IcalUnknownComponent {
+        IcalUnknownComponent {
+            name: String::from(name),
+            properties: Vec::new(),
+            sub_components: Vec::new(),
+            span: None,
+        }
+    }
+}
+
+impl Component for IcalUnknownComponent {
+    fn add_property(&mut self, property: Property) {
+        self.properties.push(property);
+    }
+
+    fn get_property<'c>(&'c self, name: &str) -> Option<&'c Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn get_property_mut<'c>(&'c mut self, name: &str) -> Option<&'c mut Property> {
+        self.properties.iter_mut().find(|p| p.name == name)
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
+    fn add_sub_component<B: BufRead>(
+        &mut self,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
+    ) -> Result<(), ParserError> {
+        let mut component = IcalUnknownComponent::new(begin.value.as_deref().unwrap_or_default());
+        component.parse(begin, line_parser)?;
+        self.sub_components.push(component);
+
+        Ok(())
     }
 }
@@ -40,8 +40,8 @@ use std::io::BufRead;
 
 // Internal mods
 use crate::line::LineReader;
-use crate::parser::{Component, ParserError};
-use crate::property::PropertyParser;
+use crate::parser::{lenient, recover, Component, Diagnostic, ParserError, Severity};
+use crate::property::{Property, PropertyParser};
 
 /// Reader returning `IcalCalendar` object from a `BufRead`.
 pub struct IcalParser<B> {
@@ -59,22 +59,46 @@ impl<B: BufRead> IcalParser<B> {
         }
     }
 
-    /// Read the next line and check if it's a valid VCALENDAR start.
-    fn check_header(&mut self) -> Result<Option<()>, ParserError> {
-        let line = match self.line_parser.borrow_mut().next() {
-            Some(val) => val.map_err(ParserError::PropertyError)?,
-            None => return Ok(None),
-        };
-
-        if line.name != "BEGIN"
-            || line.value.is_none()
-            || line.value.unwrap() != "VCALENDAR"
-            || line.params.is_some()
-        {
-            return Err(ParserError::MissingHeader);
+    /// Create a new `IcalParser` from a `LineReader`, to read a legacy encoding.
+    pub fn from_line_reader(line_reader: LineReader<B>) -> IcalParser<B> {
+        IcalParser {
+            line_parser: RefCell::new(PropertyParser::new(line_reader)),
         }
+    }
 
-        Ok(Some(()))
+    /// Return an iterator which recovers from the malformed content instead of failing,
+    /// returning each calendar with the diagnostics of the problems found while reading it.
+    ///
+    /// The content found after the last calendar is reported with an empty one.
+    pub fn lenient(self) -> impl Iterator<Item = (component::IcalCalendar, Vec<Diagnostic>)> {
+        lenient(
+            self,
+            |parser| &parser.line_parser,
+            component::IcalCalendar::new,
+        )
+    }
+
+    /// Read the next line and check if it's a valid VCALENDAR start, skipping the other lines in
+    /// the lenient mode.
+    fn check_header(&mut self) -> Result<Option<Property>, ParserError> {
+        loop {
+            let line = match self.line_parser.borrow_mut().next() {
+                Some(val) => val?,
+                None => return Ok(None),
+            };
+
+            if line.name == "BEGIN"
+                && line.value.as_deref() == Some("VCALENDAR")
+                && line.params.is_none()
+            {
+                return Ok(Some(line));
+            }
+
+            let err = ParserError::MissingHeader {
+                span: line.span.unwrap_or_default(),
+            };
+            recover(&self.line_parser, Severity::Error, err)?;
+        }
     }
 }
 
@@ -82,13 +106,13 @@ impl<B: BufRead> Iterator for IcalParser<B> {
     type Item = Result<component::IcalCalendar, ParserError>;
 
     fn next(&mut self) -> Option<Result<component::IcalCalendar, ParserError>> {
-        match self.check_header() {
+        let begin = match self.check_header() {
             Ok(res) => res?,
             Err(err) => return Some(Err(err)),
         };
 
         let mut calendar = component::IcalCalendar::new();
-        let result = match calendar.parse(&self.line_parser) {
+        let result = match calendar.parse(&begin, &self.line_parser) {
             Ok(_) => Ok(calendar),
             Err(err) => Err(err),
         };
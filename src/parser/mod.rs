@@ -4,8 +4,20 @@
 //!   The parsers (`VcardParser` / `IcalParser`) only parse the content and set to uppercase
 //!   the case-insensitive fields.  No checks are made on the fields validity.
 //!
+//! #### Lenient mode
+//!   By default, the first malformed line makes the parsers return an error for the whole
+//!   object. The `lenient` method of the parsers returns instead each object with the
+//!   `Diagnostic` list of the problems it recovered from:
+//!
+//!   * a line which can't be read or parsed, or which isn't in any object, is skipped with an
+//!     error;
+//!   * a sub-component rejected by its parent component is skipped up to its `END` line with
+//!     an error;
+//!   * a component missing its `END` line is kept as it is with a warning.
+//!
 //!
 
+pub mod document;
 pub mod ical;
 pub mod vcard;
 
@@ -14,18 +26,130 @@ use std::cell::RefCell;
 use std::io::BufRead;
 
 // Internal mods
+use crate::line::{LineError, Span};
+pub use crate::property::{Diagnostic, Severity};
 use crate::property::{Property, PropertyError, PropertyParser};
 
 #[derive(Debug, Error)]
 pub enum ParserError {
-    #[error("invalid component")]
-    InvalidComponent,
-    #[error("incomplete object")]
-    NotComplete,
-    #[error("missing header")]
-    MissingHeader,
+    #[error("Line {}: Invalid component {:?}.", span.start_line, name)]
+    InvalidComponent { span: Span, name: String },
+    #[error("Line {}: Incomplete object.", span.start_line)]
+    NotComplete { span: Span },
+    #[error("Line {}: Missing header.", span.start_line)]
+    MissingHeader { span: Span },
     #[error("property error: {0}")]
-    PropertyError(#[from] PropertyError),
+    PropertyError(PropertyError),
+    #[error(transparent)]
+    Line(LineError),
+}
+
+impl ParserError {
+    /// Return the number of the line where the error was found.
+    pub fn line(&self) -> usize {
+        match self {
+            ParserError::InvalidComponent { span, .. }
+            | ParserError::NotComplete { span }
+            | ParserError::MissingHeader { span } => span.start_line,
+            ParserError::PropertyError(err) => err.line(),
+            ParserError::Line(err) => err.line(),
+        }
+    }
+
+    /// Return the location of the component or of the line the error was found in, the
+    /// property and line errors only knowing the line number.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ParserError::InvalidComponent { span, .. }
+            | ParserError::NotComplete { span }
+            | ParserError::MissingHeader { span } => Some(*span),
+            ParserError::PropertyError(_) | ParserError::Line(_) => None,
+        }
+    }
+}
+
+impl From<PropertyError> for ParserError {
+    fn from(err: PropertyError) -> ParserError {
+        match err {
+            PropertyError::Line(err) => ParserError::Line(err),
+            err => ParserError::PropertyError(err),
+        }
+    }
+}
+
+/// Record a recoverable error as a diagnostic of a lenient parser, returning it back in the
+/// strict mode.
+pub(crate) fn recover<B: BufRead>(
+    line_parser: &RefCell<PropertyParser<B>>,
+    severity: Severity,
+    err: ParserError,
+) -> Result<(), ParserError> {
+    let line = err.line();
+    let span = err.span();
+
+    if line_parser
+        .borrow_mut()
+        .diagnose(severity, line, span, err.to_string())
+    {
+        Ok(())
+    } else {
+        Err(err)
+    }
+}
+
+/// Return an iterator over the objects of `parser` in the lenient mode, each with the
+/// diagnostics of the problems found while reading it. An object failing anyway, or the
+/// content found after the last one, is reported with an `empty` object.
+pub(crate) fn lenient<B, P, C>(
+    mut parser: P,
+    line_parser: fn(&P) -> &RefCell<PropertyParser<B>>,
+    empty: fn() -> C,
+) -> impl Iterator<Item = (C, Vec<Diagnostic>)>
+where
+    B: BufRead,
+    P: Iterator<Item = Result<C, ParserError>>,
+{
+    line_parser(&parser).borrow_mut().set_lenient();
+
+    std::iter::from_fn(move || {
+        let res = parser.next();
+        let mut diagnostics = line_parser(&parser).borrow_mut().take_diagnostics();
+
+        match res {
+            Some(Ok(object)) => Some((object, diagnostics)),
+            Some(Err(err)) => {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    line: err.line(),
+                    span: err.span(),
+                    message: err.to_string(),
+                });
+                Some((empty(), diagnostics))
+            }
+            None if !diagnostics.is_empty() => Some((empty(), diagnostics)),
+            None => None,
+        }
+    })
+}
+
+/// Skip the lines up to the `END` of a component, its `BEGIN` line being already read.
+fn skip_component<B: BufRead>(line_parser: &RefCell<PropertyParser<B>>) -> Result<(), ParserError> {
+    let mut depth = 1;
+
+    while depth > 0 {
+        let line = match line_parser.borrow_mut().next() {
+            Some(val) => val?,
+            None => break,
+        };
+
+        match line.name.to_uppercase().as_str() {
+            "BEGIN" => depth += 1,
+            "END" => depth -= 1,
+            _ => {}
+        }
+    }
+
+    Ok(())
 }
 
 /// An interface for an Ical/Vcard component.
@@ -33,10 +157,10 @@ pub enum ParserError {
 /// It take a `PropertyParser` and fill the component with. It's also able to create
 /// sub-component used by event and alarms.
 pub trait Component {
-    /// Add the givent sub component.
+    /// Add the givent sub component, starting with the `begin` line.
     fn add_sub_component<B: BufRead>(
         &mut self,
-        value: &str,
+        begin: &Property,
         line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError>;
 
@@ -47,26 +171,50 @@ pub trait Component {
     fn get_property<'c>(&'c self, name: &str) -> Option<&'c Property>;
     fn get_property_mut<'c>(&'c mut self, name: &str) -> Option<&'c mut Property>;
 
-    /// Parse the content from `line_parser` and fill the component with.
+    /// Set the location of the component, from its `BEGIN` line to its `END` one.
+    fn set_span(&mut self, span: Span);
+
+    /// Parse the content from `line_parser` up to the `END` line and fill the component
+    /// with, `begin` being its `BEGIN` line.
     fn parse<B: BufRead>(
         &mut self,
+        begin: &Property,
         line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        loop {
-            let line: Property;
+        let begin_span = begin.span.unwrap_or_default();
 
-            {
-                line = match line_parser.borrow_mut().next() {
-                    Some(val) => val.map_err(ParserError::PropertyError)?,
-                    None => return Err(ParserError::NotComplete),
-                };
-            }
+        loop {
+            let next = line_parser.borrow_mut().next();
+            let line = match next {
+                Some(val) => val?,
+                None => {
+                    // Keep what was read of the component.
+                    let err = ParserError::NotComplete { span: begin_span };
+                    recover(line_parser, Severity::Warning, err)?;
+                    self.set_span(begin_span);
+                    break;
+                }
+            };
 
             match line.name.to_uppercase().as_str() {
-                "END" => break,
+                "END" => {
+                    self.set_span(begin_span.to(line.span.unwrap_or_default()));
+                    break;
+                }
                 "BEGIN" => match line.value {
-                    Some(v) => self.add_sub_component(v.as_str(), line_parser)?,
-                    None => return Err(ParserError::NotComplete),
+                    Some(_) => match self.add_sub_component(&line, line_parser) {
+                        Err(err @ ParserError::InvalidComponent { .. }) => {
+                            recover(line_parser, Severity::Error, err)?;
+                            skip_component(line_parser)?;
+                        }
+                        res => res?,
+                    },
+                    None => {
+                        let err = ParserError::NotComplete {
+                            span: line.span.unwrap_or_default(),
+                        };
+                        recover(line_parser, Severity::Error, err)?;
+                    }
                 },
 
                 _ => self.add_property(line),
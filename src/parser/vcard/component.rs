@@ -6,6 +6,8 @@ use std::io::BufRead;
 extern crate serde;
 
 // Internal mods
+use crate::line::Span;
+use crate::parser::ical::component::IcalUnknownComponent;
 use crate::parser::{Component, ParserError};
 use crate::property::{Property, PropertyParser};
 
@@ -14,14 +16,35 @@ use crate::property::{Property, PropertyParser};
 /// A VCARD contact.
 pub struct VcardContact {
     pub properties: Vec<Property>,
+    /// The sub-components of the contact, which vCard doesn't define.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub unknown_components: Vec<IcalUnknownComponent>,
+    /// Location of the component, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl VcardContact {
     pub fn new() -> VcardContact {
         VcardContact {
             properties: Vec::new(),
+            unknown_components: Vec::new(),
+            span: None,
         }
     }
+
+    /// Return the properties of a group, such as `item1` for `item1.EMAIL` and
+    /// `item1.X-ABLabel`. The group names are case-insensitive.
+    pub fn group<'c>(&'c self, group: &str) -> Vec<&'c Property> {
+        self.properties
+            .iter()
+            .filter(|p| {
+                p.group
+                    .as_ref()
+                    .is_some_and(|g| g.eq_ignore_ascii_case(group))
+            })
+            .collect()
+    }
 }
 
 impl Component for VcardContact {
@@ -37,11 +60,19 @@ impl Component for VcardContact {
         self.properties.iter_mut().find(|p| p.name == name)
     }
 
+    fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     fn add_sub_component<B: BufRead>(
         &mut self,
-        _: &str,
-        _: &RefCell<PropertyParser<B>>,
+        begin: &Property,
+        line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
-        Err(ParserError::InvalidComponent)
+        let mut component = IcalUnknownComponent::new(begin.value.as_deref().unwrap_or_default());
+        component.parse(begin, line_parser)?;
+        self.unknown_components.push(component);
+
+        Ok(())
     }
 }
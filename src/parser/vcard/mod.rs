@@ -40,8 +40,8 @@ use std::io::BufRead;
 
 // Internal mods
 use crate::line::LineReader;
-use crate::parser::Component;
-use crate::property::PropertyParser;
+use crate::parser::{lenient, recover, Component, Diagnostic, Severity};
+use crate::property::{Property, PropertyParser};
 
 /// Reader returning `VcardContact` object from a `BufRead`.
 pub struct VcardParser<B> {
@@ -59,22 +59,47 @@ impl<B: BufRead> VcardParser<B> {
         }
     }
 
-    /// Read the next line and check if it's a valid VCARD start.
-    fn check_header(&mut self) -> Result<Option<()>, ParserError> {
-        let line = match self.line_parser.borrow_mut().next() {
-            Some(val) => val.map_err(ParserError::PropertyError)?,
-            None => return Ok(None),
-        };
-
-        if line.name.to_uppercase() != "BEGIN"
-            || line.value.is_none()
-            || line.value.unwrap().to_uppercase() != "VCARD"
-            || line.params.is_some()
-        {
-            return Err(ParserError::MissingHeader);
+    /// Create a new `VcardParser` from a `LineReader`, to read a legacy encoding.
+    pub fn from_line_reader(line_reader: LineReader<B>) -> VcardParser<B> {
+        VcardParser {
+            line_parser: RefCell::new(PropertyParser::new(line_reader)),
         }
+    }
 
-        Ok(Some(()))
+    /// Return an iterator which recovers from the malformed content instead of failing,
+    /// returning each contact with the diagnostics of the problems found while reading it.
+    ///
+    /// The content found after the last contact is reported with an empty one.
+    pub fn lenient(self) -> impl Iterator<Item = (component::VcardContact, Vec<Diagnostic>)> {
+        lenient(
+            self,
+            |parser| &parser.line_parser,
+            component::VcardContact::new,
+        )
+    }
+
+    /// Read the next line and check if it's a valid VCARD start, skipping the other lines in
+    /// the lenient mode.
+    fn check_header(&mut self) -> Result<Option<Property>, ParserError> {
+        loop {
+            let line = match self.line_parser.borrow_mut().next() {
+                Some(val) => val?,
+                None => return Ok(None),
+            };
+
+            if line.name.to_uppercase() == "BEGIN"
+                && line.value.as_ref().map(|value| value.to_uppercase())
+                    == Some(String::from("VCARD"))
+                && line.params.is_none()
+            {
+                return Ok(Some(line));
+            }
+
+            let err = ParserError::MissingHeader {
+                span: line.span.unwrap_or_default(),
+            };
+            recover(&self.line_parser, Severity::Error, err)?;
+        }
     }
 }
 
@@ -82,13 +107,13 @@ impl<B: BufRead> Iterator for VcardParser<B> {
     type Item = Result<component::VcardContact, ParserError>;
 
     fn next(&mut self) -> Option<Result<component::VcardContact, ParserError>> {
-        match self.check_header() {
+        let begin = match self.check_header() {
             Ok(res) => res?,
             Err(err) => return Some(Err(err)),
         };
 
         let mut contact = component::VcardContact::new();
-        let result = match contact.parse(&self.line_parser) {
+        let result = match contact.parse(&begin, &self.line_parser) {
             Ok(_) => Ok(contact),
             Err(err) => Err(err),
         };
@@ -3,11 +3,14 @@
 //! Split the result of `LineReader` into property. A property contains:
 //! - A name formated in uppercase.
 //! - An optional list of parameters represented by a vector of `(key/value)` tuple . The key is
-//! formatted in uppercase and the value stay untouched.
+//!   formatted in uppercase and the value stay untouched.
 //! - A value stay untouched.
 //!
 //! It work for both the Vcard and Ical format.
 //!
+//! The values are kept in their escaped form. The `TEXT` values are decoded by the `text`,
+//! `texts` and `structured_text` methods of `Property` and encoded by `Property::from_text`.
+//!
 //! #### Warning
 //!   The parsers `PropertyParser` only parse the content and set to uppercase the case-insensitive
 //!   fields. No checks are made on the fields validity.
@@ -38,6 +41,7 @@
 //! ```
 
 // Sys mods
+use std::borrow::Cow;
 use std::fmt;
 use std::io::BufRead;
 use std::iter::Iterator;
@@ -46,7 +50,7 @@ use std::iter::Iterator;
 extern crate serde;
 
 // Internal mods
-use crate::line::{Line, LineReader};
+use crate::line::{Line, LineError, LineReader, Span};
 
 #[derive(Debug, Error)]
 pub enum PropertyError {
@@ -60,6 +64,22 @@ pub enum PropertyError {
     MissingContentAfter { line: usize, letter: char },
     #[error("Line {}: Missing a parameter key.", line)]
     MissingParamKey { line: usize },
+    #[error(transparent)]
+    Line(#[from] LineError),
+}
+
+impl PropertyError {
+    /// Return the number of the line which couldn't be parsed.
+    pub fn line(&self) -> usize {
+        match self {
+            PropertyError::MissingName { line }
+            | PropertyError::MissingClosingQuote { line }
+            | PropertyError::MissingDelimiter { line, .. }
+            | PropertyError::MissingContentAfter { line, .. }
+            | PropertyError::MissingParamKey { line } => *line,
+            PropertyError::Line(err) => err.line(),
+        }
+    }
 }
 
 /// A VCARD/ICAL property.
@@ -68,10 +88,16 @@ pub enum PropertyError {
 pub struct Property {
     /// Property name.
     pub name: String,
+    /// Group of the property, `item1` for `item1.EMAIL` (vCard only).
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub group: Option<String>,
     /// Property list of parameters.
     pub params: Option<Vec<(String, Vec<String>)>>,
     /// Property value.
     pub value: Option<String>,
+    /// Location of the property, if it was parsed.
+    #[cfg_attr(feature = "serde-derive", serde(default))]
+    pub span: Option<Span>,
 }
 
 impl Property {
@@ -79,10 +105,356 @@ impl Property {
     pub fn new() -> Property {
         Property {
             name: String::new(),
+            group: None,
             params: None,
             value: None,
+            span: None,
+        }
+    }
+
+    /// Return the number of the line the property was parsed from, if any.
+    pub fn line(&self) -> Option<usize> {
+        self.span.map(|span| span.start_line)
+    }
+}
+
+/// Format of the object holding a property, which changes the escaping of the `TEXT` values
+/// and the default type of a value.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// iCalendar ([RFC5545](https://tools.ietf.org/html/rfc5545)).
+    Ical,
+    /// vCard ([RFC6350](https://tools.ietf.org/html/rfc6350)).
+    Vcard,
+}
+
+/// The vCard parameters holding a list of values, separated by commas even when quoted.
+#[cfg(any(feature = "jcal", feature = "jcard", feature = "xcal"))]
+pub(crate) const VCARD_LIST_PARAMS: &[&str] = &["TYPE", "PID", "SORT-AS"];
+
+/// Escape a `TEXT` value.
+///
+/// The backslashes, commas and newlines are escaped in both formats. The semicolons are
+/// escaped in iCalendar ([RFC5545 section 3.3.11](https://tools.ietf.org/html/rfc5545#section-3.3.11))
+/// but not in vCard ([RFC6350 section 3.4](https://tools.ietf.org/html/rfc6350#section-3.4)),
+/// where they only need to be in the components of a structured value.
+pub fn escape_text(text: &str, format: Format) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ',' => escaped.push_str("\\,"),
+            ';' if format == Format::Ical => escaped.push_str("\\;"),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Escape a component of a structured value (`N`, `ADR`...), where the semicolons separate the
+/// components and the commas the values of a component.
+pub fn escape_component(text: &str) -> String {
+    escape_text(text, Format::Ical)
+}
+
+/// Decode an escaped `TEXT` value.
+///
+/// The unknown escape sequences are kept as found.
+pub fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(c @ '\\') | Some(c @ ',') | Some(c @ ';') => unescaped.push(c),
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
         }
     }
+
+    unescaped
+}
+
+/// Split an escaped value on the `delimiter`s which are not escaped.
+pub fn split_unescaped(text: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            parts.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+
+    parts
+}
+
+/// Decode a base64 ([RFC4648](https://tools.ietf.org/html/rfc4648)) string. The whitespaces
+/// and the padding are ignored.
+pub(crate) fn decode_base64(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in value.bytes() {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' | b' ' | b'\t' | b'\r' | b'\n' => continue,
+            _ => return None,
+        };
+
+        buffer = (buffer << 6) | sextet as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+/// Decode a quoted-printable ([RFC2045 section 6.7](https://tools.ietf.org/html/rfc2045#section-6.7))
+/// string. The soft line breaks are removed and the invalid escapes kept as found.
+pub fn decode_quoted_printable(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = |c: u8| (c as char).to_digit(16);
+
+        match &bytes[i..] {
+            [b'=', b'\r', b'\n', ..] => i += 3,
+            [b'=', b'\n', ..] => i += 2,
+            [b'=', high, low, ..] if hex(*high).is_some() && hex(*low).is_some() => {
+                decoded.push((hex(*high).unwrap() * 16 + hex(*low).unwrap()) as u8);
+                i += 3;
+            }
+            [c, ..] => {
+                decoded.push(*c);
+                i += 1;
+            }
+            [] => break,
+        }
+    }
+
+    decoded
+}
+
+/// Encode a parameter value following [RFC6868](https://tools.ietf.org/html/rfc6868): a
+/// `^` is written `^^`, a `"` is written `^'` and a newline `^n`.
+pub fn encode_param_value(value: &str) -> Cow<'_, str> {
+    if !value.contains(['^', '"', '\n', '\r']) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut encoded = String::with_capacity(value.len() + 2);
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '^' => encoded.push_str("^^"),
+            '"' => encoded.push_str("^'"),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => encoded.push_str("^n"),
+            c => encoded.push(c),
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+/// Decode a parameter value encoded following [RFC6868](https://tools.ietf.org/html/rfc6868).
+///
+/// A `^` followed by another character is kept as found.
+pub fn decode_param_value(value: &str) -> Cow<'_, str> {
+    if !value.contains('^') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '^' {
+            decoded.push(c);
+            continue;
+        }
+
+        match chars.peek() {
+            Some('^') => decoded.push('^'),
+            Some('\'') => decoded.push('"'),
+            Some('n') => decoded.push('\n'),
+            _ => {
+                decoded.push('^');
+                continue;
+            }
+        }
+        chars.next();
+    }
+
+    Cow::Owned(decoded)
+}
+
+/// Transfer encoding of a value, given by its `ENCODING` parameter.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ValueEncoding {
+    /// `ENCODING=QUOTED-PRINTABLE`, used by vCard 2.1.
+    QuotedPrintable,
+    /// `ENCODING=b` (vCard 3, iCalendar `BASE64`) or `ENCODING=BASE64` (vCard 2.1).
+    Base64,
+}
+
+impl Property {
+    /// Return the transfer encoding of the value, if any.
+    pub fn encoding(&self) -> Option<ValueEncoding> {
+        let (_, values) = self
+            .params
+            .as_ref()?
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("ENCODING"))?;
+
+        match values.first()?.to_uppercase().as_str() {
+            "QUOTED-PRINTABLE" => Some(ValueEncoding::QuotedPrintable),
+            "B" | "BASE64" => Some(ValueEncoding::Base64),
+            _ => None,
+        }
+    }
+
+    /// Return the value decoded from its transfer encoding, or as found if it has none.
+    ///
+    /// Return `None` if there is no value or if it isn't valid base64.
+    pub fn decoded_value(&self) -> Option<Vec<u8>> {
+        let value = self.value.as_deref()?;
+
+        match self.encoding() {
+            Some(ValueEncoding::QuotedPrintable) => Some(decode_quoted_printable(value)),
+            Some(ValueEncoding::Base64) => decode_base64(value),
+            None => Some(value.as_bytes().to_vec()),
+        }
+    }
+
+    /// Return the value, decoded from quoted-printable and its `CHARSET` if it is.
+    ///
+    /// The `CHARSET` is only known with the `charset` feature, UTF-8 is assumed otherwise.
+    pub(crate) fn raw_text(&self) -> Option<Cow<'_, str>> {
+        let value = self.value.as_deref()?;
+        if self.encoding() != Some(ValueEncoding::QuotedPrintable) {
+            return Some(Cow::Borrowed(value));
+        }
+
+        let bytes = decode_quoted_printable(value);
+
+        #[cfg(feature = "charset")]
+        {
+            let encoding = self
+                .params
+                .iter()
+                .flatten()
+                .find(|(key, _)| key.eq_ignore_ascii_case("CHARSET"))
+                .and_then(|(_, values)| values.first())
+                .and_then(|charset| encoding_rs::Encoding::for_label(charset.as_bytes()));
+
+            if let Some(encoding) = encoding {
+                let (text, _) = encoding.decode_without_bom_handling(&bytes);
+                return Some(Cow::Owned(text.into_owned()));
+            }
+        }
+
+        Some(Cow::Owned(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
+
+impl Property {
+    /// Return a new `Property` holding the `TEXT` value `text`, escaped for `format`.
+    pub fn from_text<S: Into<String>>(name: S, text: &str, format: Format) -> Property {
+        let mut property = Property::new();
+        property.name = name.into();
+        property.set_text(text, format);
+
+        property
+    }
+
+    /// Return the decoded `TEXT` value of the property.
+    ///
+    /// A quoted-printable value is decoded too.
+    pub fn text(&self) -> Option<String> {
+        self.raw_text().as_deref().map(unescape_text)
+    }
+
+    /// Return the decoded values of a `TEXT` list, such as `CATEGORIES` or `NICKNAME`, split
+    /// on the unescaped commas.
+    pub fn texts(&self) -> Vec<String> {
+        self.raw_text()
+            .as_deref()
+            .map(|value| {
+                split_unescaped(value, ',')
+                    .into_iter()
+                    .map(unescape_text)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return the decoded components of a structured value, such as `N` or `ADR`, split on the
+    /// unescaped semicolons, each of them split on the unescaped commas.
+    pub fn structured_text(&self) -> Vec<Vec<String>> {
+        self.raw_text()
+            .as_deref()
+            .map(|value| {
+                split_unescaped(value, ';')
+                    .into_iter()
+                    .map(|component| {
+                        split_unescaped(component, ',')
+                            .into_iter()
+                            .map(unescape_text)
+                            .collect()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Set the value of the property to `text`, escaped for `format`.
+    pub fn set_text(&mut self, text: &str, format: Format) {
+        self.value = Some(escape_text(text, format));
+    }
+
+    /// Set the value of the property to the `TEXT` list `texts`, escaped for `format`.
+    pub fn set_texts<S: AsRef<str>>(&mut self, texts: &[S], format: Format) {
+        let texts = texts
+            .iter()
+            .map(|text| escape_text(text.as_ref(), format))
+            .collect::<Vec<_>>();
+
+        self.value = Some(texts.join(","));
+    }
 }
 
 impl fmt::Display for Property {
@@ -95,49 +467,112 @@ impl fmt::Display for Property {
     }
 }
 
+/// The severity of a `Diagnostic`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Severity {
+    /// The content was skipped.
+    Error,
+    /// The content was repaired and kept.
+    Warning,
+}
+
+/// A problem a lenient parser recovered from.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Number of the line the problem was found at.
+    pub line: usize,
+    /// Location of the faulty line or component, if known.
+    pub span: Option<Span>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 /// Take a `LineReader` and return a list of `Property`.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct PropertyParser<B> {
     line_reader: LineReader<B>,
+    /// The diagnostics of the lenient mode, `None` in the strict one.
+    diagnostics: Option<Vec<Diagnostic>>,
 }
 
 impl<B: BufRead> PropertyParser<B> {
     /// Return a new `PropertyParser` from a `LineReader`.
     pub fn new(line_reader: LineReader<B>) -> PropertyParser<B> {
-        PropertyParser { line_reader }
+        PropertyParser {
+            line_reader,
+            diagnostics: None,
+        }
     }
 
     /// Return a new `PropertyParser` from a `Reader`.
     pub fn from_reader(reader: B) -> PropertyParser<B> {
-        let line_reader = LineReader::new(reader);
+        PropertyParser::new(LineReader::new(reader))
+    }
+
+    /// Return a new lenient `PropertyParser`, which skips the lines it can't read or parse
+    /// instead of returning an error, recording a diagnostic for each.
+    pub fn lenient(line_reader: LineReader<B>) -> PropertyParser<B> {
+        let mut parser = PropertyParser::new(line_reader);
+        parser.set_lenient();
+        parser
+    }
+
+    pub(crate) fn set_lenient(&mut self) {
+        self.diagnostics.get_or_insert_with(Vec::new);
+    }
+
+    /// Return the diagnostics recorded since the last call, always none in the strict mode.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.diagnostics
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
 
-        PropertyParser { line_reader }
+    /// Record a diagnostic, returning `false` in the strict mode where the error has to be
+    /// returned instead.
+    pub(crate) fn diagnose(
+        &mut self,
+        severity: Severity,
+        line: usize,
+        span: Option<Span>,
+        message: String,
+    ) -> bool {
+        match &mut self.diagnostics {
+            Some(diagnostics) => {
+                diagnostics.push(Diagnostic {
+                    severity,
+                    line,
+                    span,
+                    message,
+                });
+                true
+            }
+            None => false,
+        }
     }
 
     fn parse(&self, line: Line) -> Result<Property, PropertyError> {
-        let mut property = Property::new();
+        let mut property = Property {
+            span: Some(line.span()),
+            ..Property::new()
+        };
 
         let mut to_parse = line.as_str();
 
         // Parse name.
-        let end_name_index;
-
-        let mut param_index = to_parse
-            .find(::PARAM_DELIMITER)
-            .unwrap_or(usize::max_value());
-        let mut value_index = to_parse
-            .find(::VALUE_DELIMITER)
-            .unwrap_or(usize::max_value());
-
-        if param_index < value_index && param_index != 0 {
-            end_name_index = param_index;
-        } else if value_index != usize::max_value() && value_index != 0 {
-            end_name_index = value_index;
-        } else {
-            return Err(PropertyError::MissingName {
+        let end_name_index = to_parse
+            .find([::PARAM_DELIMITER, ::VALUE_DELIMITER])
+            .filter(|index| *index != 0)
+            .ok_or_else(|| PropertyError::MissingName {
                 line: line.number(),
-            });
-        }
+            })?;
 
         {
             let split = to_parse.split_at(end_name_index);
@@ -145,130 +580,103 @@ impl<B: BufRead> PropertyParser<B> {
             to_parse = split.1;
         }
 
-        // Parse parameters.
-        value_index = to_parse
-            .find(::VALUE_DELIMITER)
-            .unwrap_or(usize::max_value());
-        param_index = to_parse
-            .find(::PARAM_DELIMITER)
-            .unwrap_or(usize::max_value());
-
-        // If there is a PARAM_DELIMITER and it not after the VALUE_DELIMITER
-        // there is arguments.
-        if param_index != usize::max_value() && value_index > param_index {
-            let mut param_list = Vec::new();
-
-            while to_parse.starts_with(::PARAM_DELIMITER) {
-                to_parse = to_parse.trim_start_matches(::PARAM_DELIMITER);
-
-                // Split the param key and the rest of the line
-                let mut param_elements = to_parse.splitn(2, ::PARAM_NAME_DELIMITER);
-
-                let key = param_elements
-                    .next()
-                    .and_then(|key| {
-                        if key.is_empty() {
-                            return None;
-                        }
+        // Split the group. (item1.EMAIL:...)
+        if let Some((group, name)) = property.name.split_once('.') {
+            if !group.is_empty()
+                && !name.is_empty()
+                && group
+                    .bytes()
+                    .all(|c| c.is_ascii_alphanumeric() || c == b'-')
+            {
+                property.group = Some(group.to_string());
+                property.name = name.to_string();
+            }
+        }
 
-                        Some(key)
-                    })
-                    .ok_or_else(|| PropertyError::MissingParamKey {
-                        line: line.number(),
+        // Parse parameters, following RFC5545 section 3.2:
+        //
+        // param = param-name "=" param-value *("," param-value)
+        // param-value = paramtext / quoted-string
+        let mut param_list = Vec::new();
+
+        while to_parse.starts_with(::PARAM_DELIMITER) {
+            to_parse = to_parse.trim_start_matches(::PARAM_DELIMITER);
+
+            // Split the param key and the rest of the line
+            let end_key_index = to_parse
+                .find([::PARAM_NAME_DELIMITER, ::PARAM_DELIMITER, ::VALUE_DELIMITER])
+                .unwrap_or(to_parse.len());
+            let key = &to_parse[..end_key_index];
+
+            if key.is_empty() {
+                return Err(PropertyError::MissingParamKey {
+                    line: line.number(),
+                });
+            }
+
+            to_parse = to_parse[end_key_index..]
+                .strip_prefix(::PARAM_NAME_DELIMITER)
+                .ok_or_else(|| PropertyError::MissingDelimiter {
+                    delimiter: ::PARAM_NAME_DELIMITER,
+                    line: line.number(),
+                })?;
+
+            let mut values = Vec::new();
+
+            // Parse parameter values.
+            loop {
+                let value;
+
+                if let Some(quoted) = to_parse.strip_prefix(::PARAM_QUOTE) {
+                    // This is a dquoted value. (NAME;Foo="Bar:Baz":value)
+                    let end_value_index = quoted.find(::PARAM_QUOTE).ok_or_else(|| {
+                        PropertyError::MissingClosingQuote {
+                            line: line.number(),
+                        }
                     })?;
 
-                to_parse =
-                    param_elements
-                        .next()
-                        .ok_or_else(|| PropertyError::MissingDelimiter {
-                            delimiter: ::PARAM_NAME_DELIMITER,
+                    value = &quoted[..end_value_index];
+                    to_parse = &quoted[end_value_index + 1..];
+                } else {
+                    // This is a 'raw' value. (NAME;Foo=Bar:value)
+                    let end_value_index = to_parse
+                        .find([
+                            ::PARAM_VALUE_DELIMITER,
+                            ::PARAM_DELIMITER,
+                            ::VALUE_DELIMITER,
+                        ])
+                        .ok_or_else(|| PropertyError::MissingContentAfter {
+                            letter: ::PARAM_NAME_DELIMITER,
                             line: line.number(),
                         })?;
 
-                let mut values = Vec::new();
-
-                let mut i = 10;
-
-                // Parse parameter value.
-                while i > 0 {
-                    i -= 1;
-                    if to_parse.starts_with('"') {
-                        // This is a dquoted value. (NAME:Foo="Bar":value)
-                        let mut elements = to_parse.splitn(3, ::PARAM_QUOTE).skip(1);
-                        // unwrap is safe here as we have already check above if there is on '"'.
-                        values.push(
-                            elements
-                                .next()
-                                .ok_or_else(|| PropertyError::MissingClosingQuote {
-                                    line: line.number(),
-                                })?
-                                .to_string(),
-                        );
-
-                        to_parse =
-                            elements
-                                .next()
-                                .ok_or_else(|| PropertyError::MissingClosingQuote {
-                                    line: line.number(),
-                                })?
-                    } else {
-                        // This is a 'raw' value. (NAME;Foo=Bar:value)
-
-                        // Try to find the next param separator.
-                        let param_delimiter = to_parse
-                            .find(::PARAM_DELIMITER)
-                            .unwrap_or(usize::max_value());
-                        let value_delimiter = to_parse
-                            .find(::VALUE_DELIMITER)
-                            .unwrap_or(usize::max_value());
-                        let param_value_delimiter = to_parse
-                            .find(::PARAM_VALUE_DELIMITER)
-                            .unwrap_or(usize::max_value());
-
-                        let end_param_value = {
-                            if param_value_delimiter < value_delimiter
-                                && param_value_delimiter < param_delimiter
-                            {
-                                Ok(param_value_delimiter)
-                            } else if param_delimiter < value_delimiter
-                                && param_delimiter < param_value_delimiter
-                            {
-                                Ok(param_delimiter)
-                            } else if value_delimiter != usize::max_value() {
-                                Ok(value_delimiter)
-                            } else {
-                                Err(PropertyError::MissingContentAfter {
-                                    letter: ::PARAM_NAME_DELIMITER,
-                                    line: line.number(),
-                                })
-                            }
-                        }?;
-
-                        let elements = to_parse.split_at(end_param_value);
-                        values.push(elements.0.to_string());
-                        to_parse = elements.1;
-                    }
-
-                    if !to_parse.starts_with(::PARAM_VALUE_DELIMITER) {
-                        break;
-                    }
-
-                    to_parse = to_parse.trim_start_matches(::PARAM_VALUE_DELIMITER);
+                    value = &to_parse[..end_value_index];
+                    to_parse = &to_parse[end_value_index..];
                 }
 
-                param_list.push((key.to_uppercase(), values));
+                values.push(decode_param_value(value).into_owned());
+
+                match to_parse.strip_prefix(::PARAM_VALUE_DELIMITER) {
+                    Some(rest) => to_parse = rest,
+                    None => break,
+                }
             }
 
+            param_list.push((key.to_uppercase(), values));
+        }
+
+        if !param_list.is_empty() {
             property.params = Some(param_list);
-        } else {
-            property.params = None;
         }
 
         // Parse value
-        to_parse = to_parse.trim_start_matches(::VALUE_DELIMITER);
-        if to_parse.is_empty() {
-            property.value = None;
-        } else {
+        to_parse = to_parse.strip_prefix(::VALUE_DELIMITER).ok_or_else(|| {
+            PropertyError::MissingDelimiter {
+                delimiter: ::VALUE_DELIMITER,
+                line: line.number(),
+            }
+        })?;
+        if !to_parse.is_empty() {
             property.value = Some(to_parse.to_string());
         }
 
@@ -276,10 +684,169 @@ impl<B: BufRead> PropertyParser<B> {
     }
 }
 
+// The raw octets are only read by the `DocumentParser`.
+#[cfg(any(feature = "ical", feature = "vcard"))]
+impl<B: BufRead> PropertyParser<B> {
+    /// Keep the octets the properties are read from, see `next_raw`.
+    pub(crate) fn keep_raw(&mut self) {
+        self.line_reader.keep_raw();
+    }
+
+    /// Return the next property with the octets it was read from, which are empty unless
+    /// `keep_raw` was called.
+    pub(crate) fn next_raw(&mut self) -> Option<Result<(Property, Vec<u8>), PropertyError>> {
+        self.line_reader.next().map(|line| {
+            let line = line?;
+            let raw = self.line_reader.take_raw();
+
+            Ok((self.parse(line)?, raw))
+        })
+    }
+}
+
 impl<B: BufRead> Iterator for PropertyParser<B> {
     type Item = Result<Property, PropertyError>;
 
     fn next(&mut self) -> Option<Result<Property, PropertyError>> {
-        self.line_reader.next().map(|line| self.parse(line))
+        loop {
+            let (result, span) = match self.line_reader.next()? {
+                Ok(line) => {
+                    let span = line.span();
+                    (self.parse(line), Some(span))
+                }
+                Err(err) => (Err(PropertyError::Line(err)), None),
+            };
+
+            match result {
+                Err(err) if self.diagnose(Severity::Error, err.line(), span, err.to_string()) => {}
+                result => return Some(result),
+            }
+        }
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+
+    #[test]
+    fn escape_and_unescape_text() {
+        let text = "Meeting; room 3, \\\\server\\share\nbring snacks";
+
+        let ical = escape_text(text, Format::Ical);
+        assert_eq!(
+            ical,
+            "Meeting\\; room 3\\, \\\\\\\\server\\\\share\\nbring snacks"
+        );
+        assert_eq!(unescape_text(&ical), text);
+
+        let vcard = escape_text(text, Format::Vcard);
+        assert_eq!(
+            vcard,
+            "Meeting; room 3\\, \\\\\\\\server\\\\share\\nbring snacks"
+        );
+        assert_eq!(unescape_text(&vcard), text);
+
+        assert_eq!(escape_text("a\r\nb", Format::Ical), "a\\nb");
+        assert_eq!(unescape_text("a\\Nb\\:c\\"), "a\nb\\:c\\");
+    }
+
+    #[test]
+    fn encode_and_decode_param_values() {
+        let value = "George \"Babe\" Ruth\n^_^";
+
+        let encoded = encode_param_value(value);
+        assert_eq!(encoded, "George ^'Babe^' Ruth^n^^_^^");
+        assert_eq!(decode_param_value(&encoded), value);
+
+        assert_eq!(encode_param_value("a\r\nb"), "a^nb");
+        assert_eq!(decode_param_value("^a^"), "^a^");
+    }
+
+    #[test]
+    fn parse_parameter_lists() {
+        let members = (1..=12)
+            .map(|i| format!("\"mailto:member{}@example.com\"", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        let input = format!(
+            "ATTENDEE;;MEMBER={};X-NOTE=\"a:b;c,d\",plain;RSVP=TRUE:mailto:jdoe@example.com\n",
+            members
+        );
+
+        let property = PropertyParser::from_reader(input.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+        let params = property.params.unwrap();
+
+        assert_eq!(params.len(), 3);
+        assert_eq!(params[0].0, "MEMBER");
+        assert_eq!(params[0].1.len(), 12);
+        assert_eq!(params[0].1[11], "mailto:member12@example.com");
+        assert_eq!(
+            params[1],
+            (
+                String::from("X-NOTE"),
+                vec![String::from("a:b;c,d"), String::from("plain")]
+            )
+        );
+        assert_eq!(
+            params[2],
+            (String::from("RSVP"), vec![String::from("TRUE")])
+        );
+        assert_eq!(property.value.as_deref(), Some("mailto:jdoe@example.com"));
+    }
+
+    #[test]
+    fn decode_transfer_encodings() {
+        assert_eq!(
+            decode_quoted_printable("a=3Db=\r\nc=0D=0A=ZZ="),
+            b"a=bc\r\n=ZZ="
+        );
+
+        let mut property = Property::new();
+        property.name = String::from("NOTE");
+        property.params = Some(vec![(
+            String::from("ENCODING"),
+            vec![String::from("QUOTED-PRINTABLE")],
+        )]);
+        property.value = Some(String::from("Caf=C3=A9\\, cr=C3=A8me"));
+
+        assert_eq!(property.encoding(), Some(ValueEncoding::QuotedPrintable));
+        assert_eq!(property.text().unwrap(), "Café, crème");
+
+        property.params = Some(vec![(String::from("encoding"), vec![String::from("b")])]);
+        property.value = Some(String::from("Zm9vYmFy"));
+
+        assert_eq!(property.encoding(), Some(ValueEncoding::Base64));
+        assert_eq!(property.decoded_value().unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn split_text_lists() {
+        let mut property = Property::new();
+        property.name = String::from("CATEGORIES");
+        property.value = Some(String::from("APPOINTMENT,Rock\\, Paper\\\\,Scissors"));
+
+        assert_eq!(
+            property.texts(),
+            vec!["APPOINTMENT", "Rock, Paper\\", "Scissors"]
+        );
+
+        property.set_texts(&["a,b", "c"], Format::Vcard);
+        assert_eq!(property.value.as_deref(), Some("a\\,b,c"));
+
+        property.value = Some(String::from("Stevenson;John;Philip,Paul;Dr.;Jr.\\,M.D."));
+        assert_eq!(
+            property.structured_text(),
+            vec![
+                vec!["Stevenson"],
+                vec!["John"],
+                vec!["Philip", "Paul"],
+                vec!["Dr."],
+                vec!["Jr.,M.D."],
+            ]
+        );
     }
 }
@@ -0,0 +1,365 @@
+//! Compute the available time described by the `VAVAILABILITY` components of a calendar.
+//!
+//! As defined in [RFC7953 section 4](https://tools.ietf.org/html/rfc7953#section-4), the
+//! time covered by a `VAVAILABILITY` is busy, except for the occurrences of its `AVAILABLE`
+//! sub-components. Where they overlap, a `VAVAILABILITY` overrides the ones of a lower
+//! priority (a higher `PRIORITY`, 0 being the lowest of all) and is combined with the ones of
+//! the same priority. The time which isn't covered by any `VAVAILABILITY` is available.
+//!
+//! As for the recurrences, all the times are handled as wall-clock times.
+
+// Sys mods
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+// Internal mods
+use super::{property_instant, unexpected, Occurrences, RecurrenceError, Recurrent};
+use crate::parser::ical::component::{IcalAvailability, IcalAvailable, IcalCalendar};
+use crate::parser::Component;
+use crate::property::Property;
+use crate::value::{Format, Value};
+use chrono::NaiveDateTime;
+
+impl Recurrent for IcalAvailable {
+    const END_PROPERTY: &'static str = "DTEND";
+
+    fn properties(&self) -> &[Property] {
+        &self.properties
+    }
+
+    /// The `AVAILABLE` components overriding an instance are held by their `VAVAILABILITY`,
+    /// not by the calendar: use `IcalAvailability::available_intervals` to take them in
+    /// account.
+    fn siblings(_: &IcalCalendar) -> &[IcalAvailable] {
+        &[]
+    }
+}
+
+/// Return the intersection of `range` and `window`, if not empty.
+fn clip(
+    range: Range<NaiveDateTime>,
+    window: &Range<NaiveDateTime>,
+) -> Option<Range<NaiveDateTime>> {
+    let clipped = range.start.max(window.start)..range.end.min(window.end);
+
+    if clipped.start < clipped.end {
+        Some(clipped)
+    } else {
+        None
+    }
+}
+
+/// Sort the intervals, merging the ones which overlap or touch.
+fn merge(mut intervals: Vec<Range<NaiveDateTime>>) -> Vec<Range<NaiveDateTime>> {
+    intervals.sort_by_key(|interval| interval.start);
+
+    let mut merged: Vec<Range<NaiveDateTime>> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+
+    merged
+}
+
+/// Remove `cut` from the intervals.
+fn subtract(
+    intervals: Vec<Range<NaiveDateTime>>,
+    cut: &Range<NaiveDateTime>,
+) -> Vec<Range<NaiveDateTime>> {
+    let mut remaining = Vec::with_capacity(intervals.len() + 1);
+
+    for interval in intervals {
+        if interval.end <= cut.start || interval.start >= cut.end {
+            remaining.push(interval);
+            continue;
+        }
+        if interval.start < cut.start {
+            remaining.push(interval.start..cut.start);
+        }
+        if interval.end > cut.end {
+            remaining.push(cut.end..interval.end);
+        }
+    }
+
+    remaining
+}
+
+impl IcalAvailability {
+    /// Return the `PRIORITY` of the availability, from 1 (the highest) to 9 (the lowest), or
+    /// 0 if it's undefined, which is lower than 9.
+    pub fn priority(&self) -> Result<u8, RecurrenceError> {
+        let property = match self.get_property("PRIORITY") {
+            Some(property) => property,
+            None => return Ok(0),
+        };
+
+        match property.typed_value(Format::Ical)? {
+            Value::Integer(priority @ 0..=9) => Ok(priority as u8),
+            value => Err(unexpected(property, &value)),
+        }
+    }
+
+    /// Return the start and the end of the time covered by the availability, `None` for an
+    /// unbounded side.
+    pub fn period(
+        &self,
+    ) -> Result<(Option<NaiveDateTime>, Option<NaiveDateTime>), RecurrenceError> {
+        let start = match self.get_property("DTSTART") {
+            Some(start) => Some(property_instant(start)?.0),
+            None => None,
+        };
+
+        let end = if let Some(end) = self.get_property("DTEND") {
+            Some(property_instant(end)?.0)
+        } else if let (Some(start), Some(duration)) = (start, self.get_property("DURATION")) {
+            match duration.typed_value(Format::Ical)? {
+                Value::Duration(value) => Some(start + value.to_chrono()),
+                value => return Err(unexpected(duration, &value)),
+            }
+        } else {
+            None
+        };
+
+        Ok((start, end))
+    }
+
+    /// Return the part of the `[start, end[` window covered by the availability.
+    fn covered(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Option<Range<NaiveDateTime>>, RecurrenceError> {
+        let (period_start, period_end) = self.period()?;
+        let period = period_start.unwrap_or(start)..period_end.unwrap_or(end);
+
+        Ok(clip(period, &(start..end)))
+    }
+
+    /// Return the sorted intervals of the `[start, end[` window where the `AVAILABLE`
+    /// sub-components give some free time, within the time covered by the availability.
+    pub fn available_intervals(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Range<NaiveDateTime>>, RecurrenceError> {
+        self.intervals(None, start, end)
+    }
+
+    /// Return the available intervals, the rules being expanded with the time zones of
+    /// `calendar`.
+    fn intervals(
+        &self,
+        calendar: Option<&IcalCalendar>,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Range<NaiveDateTime>>, RecurrenceError> {
+        let window = match self.covered(start, end)? {
+            Some(window) => window,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut intervals = Vec::new();
+        for available in &self.available {
+            // The overrides are returned with the instances of their master.
+            if available.get_property("RECURRENCE-ID").is_some() {
+                continue;
+            }
+
+            let occurrences = Occurrences::build(
+                available,
+                &self.available,
+                calendar,
+                window.start,
+                window.end,
+            )?;
+            intervals.extend(occurrences.filter_map(|o| clip(o.start..o.end, &window)));
+        }
+
+        Ok(merge(intervals))
+    }
+}
+
+impl IcalCalendar {
+    /// Return the sorted intervals of the `[start, end[` window where the calendar user is
+    /// available, following the `VAVAILABILITY` components of the calendar and their
+    /// priorities.
+    pub fn available_intervals(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<Range<NaiveDateTime>>, RecurrenceError> {
+        // The availabilities by rank, from the highest priority to the lowest.
+        let mut ranks: BTreeMap<u8, Vec<&IcalAvailability>> = BTreeMap::new();
+        for availability in &self.availabilities {
+            let rank = match availability.priority()? {
+                0 => 10,
+                priority => priority,
+            };
+            ranks.entry(rank).or_default().push(availability);
+        }
+
+        let mut free = vec![start..end];
+        for availabilities in ranks.values().rev() {
+            let mut available = Vec::new();
+
+            for availability in availabilities {
+                if let Some(covered) = availability.covered(start, end)? {
+                    free = subtract(free, &covered);
+                }
+                available.extend(availability.intervals(Some(self), start, end)?);
+            }
+
+            free.extend(available);
+            free = merge(free);
+        }
+
+        Ok(free)
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use crate::parser::ical::component::IcalCalendar;
+    use crate::IcalParser;
+    use chrono::NaiveDateTime;
+    use std::io::Cursor;
+    use std::ops::Range;
+
+    fn calendar(availabilities: &str) -> IcalCalendar {
+        let content = format!("BEGIN:VCALENDAR\n{}\nEND:VCALENDAR\n", availabilities);
+
+        IcalParser::new(Cursor::new(content))
+            .next()
+            .unwrap()
+            .unwrap()
+    }
+
+    fn date_time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").unwrap()
+    }
+
+    fn intervals(cal: &IcalCalendar, from: &str, to: &str) -> Vec<String> {
+        let format = |range: Range<NaiveDateTime>| {
+            format!(
+                "{}/{}",
+                range.start.format("%Y%m%dT%H%M%S"),
+                range.end.format("%Y%m%dT%H%M%S")
+            )
+        };
+
+        cal.available_intervals(date_time(from), date_time(to))
+            .unwrap()
+            .into_iter()
+            .map(format)
+            .collect()
+    }
+
+    #[test]
+    fn keep_the_working_hours() {
+        // RFC 7953: Monday to Friday, 9:00 to 17:00, from 2011-10-02. The DTSTART is still
+        // the first instance, a Sunday.
+        let cal = calendar(
+            "BEGIN:VAVAILABILITY\n\
+             UID:1\n\
+             DTSTART:20111002T000000\n\
+             BEGIN:AVAILABLE\n\
+             UID:1-1\n\
+             DTSTART:20111002T090000\n\
+             DTEND:20111002T170000\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\n\
+             END:AVAILABLE\n\
+             END:VAVAILABILITY",
+        );
+
+        assert_eq!(
+            intervals(&cal, "20111001T000000", "20111005T120000"),
+            vec![
+                "20111001T000000/20111002T000000",
+                "20111002T090000/20111002T170000",
+                "20111003T090000/20111003T170000",
+                "20111004T090000/20111004T170000",
+                "20111005T090000/20111005T120000",
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_the_start_with_rdates_only() {
+        let cal = calendar(
+            "BEGIN:VAVAILABILITY\n\
+             UID:1\n\
+             DTSTART:20240101T000000\n\
+             BEGIN:AVAILABLE\n\
+             UID:1-1\n\
+             DTSTART:20240101T090000\n\
+             DTEND:20240101T120000\n\
+             RDATE:20240103T090000\n\
+             END:AVAILABLE\n\
+             END:VAVAILABILITY",
+        );
+
+        assert_eq!(
+            intervals(&cal, "20240101T000000", "20240104T000000"),
+            vec![
+                "20240101T090000/20240101T120000",
+                "20240103T090000/20240103T120000",
+            ]
+        );
+    }
+
+    #[test]
+    fn override_the_lower_priorities() {
+        let cal = calendar(
+            "BEGIN:VAVAILABILITY\n\
+             UID:1\n\
+             BEGIN:AVAILABLE\n\
+             UID:1-1\n\
+             DTSTART:20240101T090000\n\
+             DTEND:20240101T170000\n\
+             RRULE:FREQ=DAILY\n\
+             END:AVAILABLE\n\
+             END:VAVAILABILITY\n\
+             BEGIN:VAVAILABILITY\n\
+             UID:2\n\
+             PRIORITY:1\n\
+             DTSTART:20240102T000000\n\
+             DURATION:P1D\n\
+             END:VAVAILABILITY\n\
+             BEGIN:VAVAILABILITY\n\
+             UID:3\n\
+             PRIORITY:5\n\
+             DTSTART:20240103T000000\n\
+             DTEND:20240104T000000\n\
+             BEGIN:AVAILABLE\n\
+             UID:3-1\n\
+             DTSTART:20240103T140000\n\
+             DURATION:PT6H\n\
+             END:AVAILABLE\n\
+             END:VAVAILABILITY\n\
+             BEGIN:VAVAILABILITY\n\
+             UID:4\n\
+             PRIORITY:5\n\
+             DTSTART:20240103T120000\n\
+             DTEND:20240103T180000\n\
+             BEGIN:AVAILABLE\n\
+             UID:4-1\n\
+             DTSTART:20240103T120000\n\
+             DTEND:20240103T130000\n\
+             END:AVAILABLE\n\
+             END:VAVAILABILITY",
+        );
+
+        // The day off of the 2nd, then the availabilities of the same priority combined.
+        assert_eq!(
+            intervals(&cal, "20240101T000000", "20240104T000000"),
+            vec![
+                "20240101T090000/20240101T170000",
+                "20240103T120000/20240103T130000",
+                "20240103T140000/20240103T200000",
+            ]
+        );
+    }
+}
@@ -0,0 +1,883 @@
+//! Expand the recurrence set of an `IcalEvent` or an `IcalTodo`.
+//!
+//! The recurrence set is built from the `DTSTART`, `RRULE`, `RDATE` and `EXDATE` properties
+//! of a "master" component as described in
+//! [RFC5545 section 3.8.5](https://tools.ietf.org/html/rfc5545#section-3.8.5). The instances
+//! overridden by another component of the same calendar sharing its `UID` and holding a
+//! `RECURRENCE-ID` are replaced by this component.
+//!
+//! The expansion is always bounded by a time window, so rules without `COUNT` or `UNTIL` are
+//! safe to expand. An occurrence is returned if it overlaps the window, following the CalDAV
+//! time-range semantic ([RFC4791 section 9.9](https://tools.ietf.org/html/rfc4791#section-9.9)).
+//!
+//! The free time described by the `VAVAILABILITY` components of a calendar is computed by
+//! `IcalCalendar::available_intervals`.
+//!
+//! #### Warning
+//!   All the times are handled as the wall-clock time of the master `DTSTART`. The `TZID`
+//!   parameter and the UTC markers are not interpreted, except for the UTC `UNTIL` of a rule
+//!   whose `DTSTART` has a `TZID`: with the `timezone` feature, it is converted into the time
+//!   zone of `DTSTART`, otherwise the expansion fails. The `timezone` module converts the
+//!   resulting times into UTC.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["recurrence"]
+//! ```
+//!
+//! ```rust
+//! extern crate chrono;
+//! extern crate ical;
+//!
+//! use chrono::NaiveDate;
+//! use ical::recurrence::Recurrent;
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/ical_recurrence.ics")
+//!     .unwrap());
+//!
+//! let calendar = ical::IcalParser::new(buf).next().unwrap().unwrap();
+//!
+//! let start = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+//! let end = NaiveDate::from_ymd_opt(2023, 2, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+//!
+//! for occurrence in calendar.events[0].occurrences(&calendar, start, end).unwrap() {
+//!     println!("{} -> {}", occurrence.start, occurrence.end);
+//! }
+//! ```
+
+mod availability;
+pub(crate) mod rule;
+
+// Sys mods
+use std::collections::{HashSet, VecDeque};
+use std::iter::Peekable;
+
+// Internal mods
+use self::rule::{Rule, RuleIter};
+use crate::parser::ical::component::{IcalCalendar, IcalEvent, IcalTodo};
+use crate::parser::Component;
+use crate::property::Property;
+use crate::value::recur::{RecurEnd, Until};
+use crate::value::{DateTime, Format, PeriodEnd, Value, ValueError};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+#[derive(Debug, Error)]
+pub enum RecurrenceError {
+    #[error("Missing a DTSTART property.")]
+    MissingStart,
+    #[error("The UTC UNTIL of a RRULE can't be converted into the time zone {0:?} of DTSTART.")]
+    UnresolvedUntil(String),
+    #[error("{0}")]
+    Value(#[from] ValueError),
+}
+
+/// Return the date-time of a `DATE` or `DATE-TIME` value. The boolean is `true` for a `DATE`.
+pub(crate) fn instant(value: &Value) -> Option<(NaiveDateTime, bool)> {
+    match value {
+        Value::Date(date) => Some((date.and_time(NaiveTime::MIN), true)),
+        Value::DateTime(date_time) => Some((date_time.naive(), false)),
+        _ => None,
+    }
+}
+
+fn unexpected(property: &Property, value: &Value) -> RecurrenceError {
+    RecurrenceError::Value(ValueError::InvalidValue {
+        line: property.line(),
+        property: property.name.clone(),
+        value_type: property.value_type(Format::Ical),
+        value: value.to_string(),
+    })
+}
+
+/// Return the first property of `component` named `name`, whatever its case.
+fn find_property<'c, C: Recurrent>(component: &'c C, name: &str) -> Option<&'c Property> {
+    component
+        .properties()
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+/// Convert the UTC `until` into the local time of the zone `tzid`, with the time zones of
+/// `calendar`.
+#[cfg(feature = "timezone")]
+fn local_until(
+    calendar: Option<&IcalCalendar>,
+    until: NaiveDateTime,
+    tzid: &str,
+) -> Option<NaiveDateTime> {
+    crate::timezone::TimeZones::new(calendar?)
+        .from_utc(until, tzid)
+        .ok()
+}
+
+/// Without the `timezone` feature, a UTC `until` can't be converted.
+#[cfg(not(feature = "timezone"))]
+fn local_until(_: Option<&IcalCalendar>, _: NaiveDateTime, _: &str) -> Option<NaiveDateTime> {
+    None
+}
+
+/// Decode the single `DATE` or `DATE-TIME` value of `property`.
+fn property_instant(property: &Property) -> Result<(NaiveDateTime, bool), RecurrenceError> {
+    let value = property.typed_value(Format::Ical)?;
+
+    instant(&value).ok_or_else(|| unexpected(property, &value))
+}
+
+fn has_param(property: &Property, key: &str, value: &str) -> bool {
+    property.params.as_ref().is_some_and(|params| {
+        params.iter().any(|(k, values)| {
+            k.eq_ignore_ascii_case(key) && values.iter().any(|v| v.eq_ignore_ascii_case(value))
+        })
+    })
+}
+
+/// A component which can hold a recurrence set: `IcalEvent` or `IcalTodo`.
+pub trait Recurrent: Component + Sized {
+    /// Name of the property holding the end of an instance (`DTEND` or `DUE`).
+    const END_PROPERTY: &'static str;
+
+    /// Return all the properties of the component.
+    fn properties(&self) -> &[Property];
+
+    /// Return the components of the same kind in `calendar`.
+    fn siblings(calendar: &IcalCalendar) -> &[Self];
+
+    /// Return the occurrences overlapping the `[start, end[` window, taking in account the
+    /// overridden instances found in `calendar`.
+    fn occurrences<'a>(
+        &'a self,
+        calendar: &'a IcalCalendar,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Occurrences<'a, Self>, RecurrenceError> {
+        Occurrences::in_calendar(self, calendar, start, end)
+    }
+}
+
+impl Recurrent for IcalEvent {
+    const END_PROPERTY: &'static str = "DTEND";
+
+    fn properties(&self) -> &[Property] {
+        &self.properties
+    }
+
+    fn siblings(calendar: &IcalCalendar) -> &[IcalEvent] {
+        &calendar.events
+    }
+}
+
+impl Recurrent for IcalTodo {
+    const END_PROPERTY: &'static str = "DUE";
+
+    fn properties(&self) -> &[Property] {
+        &self.properties
+    }
+
+    fn siblings(calendar: &IcalCalendar) -> &[IcalTodo] {
+        &calendar.todos
+    }
+}
+
+/// A concrete instance of a recurring component.
+#[derive(Debug, Clone)]
+pub struct Occurrence<'a, C> {
+    /// Start of the instance.
+    pub start: NaiveDateTime,
+    /// End of the instance (not inclusive).
+    pub end: NaiveDateTime,
+    /// Original start of the instance, as referenced by a `RECURRENCE-ID`.
+    pub recurrence_id: NaiveDateTime,
+    /// `true` if the instance is a whole-day one (`DATE` values).
+    pub all_day: bool,
+    /// The component describing the instance: the master or an override.
+    pub component: &'a C,
+}
+
+/// The start and duration of a component.
+struct Timing {
+    start: NaiveDateTime,
+    /// The `TZID` of the start, if any.
+    tzid: Option<String>,
+    all_day: bool,
+    duration: Duration,
+}
+
+impl Timing {
+    fn from_component<C: Recurrent>(component: &C) -> Result<Timing, RecurrenceError> {
+        let dtstart = find_property(component, "DTSTART").ok_or(RecurrenceError::MissingStart)?;
+        let value = dtstart.typed_value(Format::Ical)?;
+        let (start, all_day) = instant(&value).ok_or_else(|| unexpected(dtstart, &value))?;
+        let tzid = match &value {
+            Value::DateTime(date_time) => date_time.tzid().map(String::from),
+            _ => None,
+        };
+
+        let duration = if let Some(end) = find_property(component, C::END_PROPERTY) {
+            property_instant(end)?.0 - start
+        } else if let Some(duration) = find_property(component, "DURATION") {
+            match duration.typed_value(Format::Ical)? {
+                Value::Duration(duration) => duration.to_chrono(),
+                value => return Err(unexpected(duration, &value)),
+            }
+        } else if all_day {
+            Duration::days(1)
+        } else {
+            Duration::zero()
+        };
+
+        Ok(Timing {
+            start,
+            tzid,
+            all_day,
+            duration,
+        })
+    }
+}
+
+/// An override applying to its instance and all the following ones
+/// (`RECURRENCE-ID;RANGE=THISANDFUTURE`).
+struct RangeOverride<'a, C> {
+    recurrence_id: NaiveDateTime,
+    timing: Timing,
+    component: &'a C,
+}
+
+/// Iterator over the occurrences of a recurring component, ordered by start.
+pub struct Occurrences<'a, C> {
+    master: &'a C,
+    timing: Timing,
+    window: (NaiveDateTime, NaiveDateTime),
+    limit: NaiveDateTime,
+    rules: Vec<Peekable<RuleIter>>,
+    rdates: VecDeque<(NaiveDateTime, Option<NaiveDateTime>)>,
+    exdates: HashSet<NaiveDateTime>,
+    exdays: HashSet<NaiveDate>,
+    overridden: HashSet<NaiveDateTime>,
+    ranges: Vec<RangeOverride<'a, C>>,
+    overrides: VecDeque<Occurrence<'a, C>>,
+    pending: Option<Occurrence<'a, C>>,
+    last: Option<NaiveDateTime>,
+}
+
+fn overlaps(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    window: (NaiveDateTime, NaiveDateTime),
+) -> bool {
+    if end > start {
+        start < window.1 && end > window.0
+    } else {
+        start >= window.0 && start < window.1
+    }
+}
+
+impl<'a, C: Recurrent> Occurrences<'a, C> {
+    /// Return the occurrences of `master` overlapping the `[start, end[` window.
+    ///
+    /// `siblings` are searched for the components overriding some instances of `master`.
+    /// Without the time zones of a calendar, a rule with a UTC `UNTIL` and a `DTSTART` with a
+    /// `TZID` fails with `RecurrenceError::UnresolvedUntil`: see `in_calendar`.
+    pub fn new(
+        master: &'a C,
+        siblings: &'a [C],
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Occurrences<'a, C>, RecurrenceError> {
+        Occurrences::build(master, siblings, None, start, end)
+    }
+
+    /// Return the occurrences of `master` overlapping the `[start, end[` window, with the
+    /// overrides and the time zones of `calendar`.
+    pub fn in_calendar(
+        master: &'a C,
+        calendar: &'a IcalCalendar,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Occurrences<'a, C>, RecurrenceError> {
+        Occurrences::build(master, C::siblings(calendar), Some(calendar), start, end)
+    }
+
+    fn build(
+        master: &'a C,
+        siblings: &'a [C],
+        calendar: Option<&IcalCalendar>,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Occurrences<'a, C>, RecurrenceError> {
+        let timing = Timing::from_component(master)?;
+        let window = (start, end);
+
+        let mut overridden = HashSet::new();
+        let mut ranges = Vec::new();
+        let mut overrides = Vec::new();
+
+        let uid = find_property(master, "UID").and_then(|p| p.value.as_ref());
+        for sibling in siblings {
+            if std::ptr::eq(sibling, master)
+                || uid.is_none()
+                || find_property(sibling, "UID").and_then(|p| p.value.as_ref()) != uid
+            {
+                continue;
+            }
+
+            let recurrence_id = match find_property(sibling, "RECURRENCE-ID") {
+                Some(recurrence_id) => recurrence_id,
+                None => continue,
+            };
+            let id = property_instant(recurrence_id)?.0;
+            let sibling_timing = Timing::from_component(sibling)?;
+
+            if has_param(recurrence_id, "RANGE", "THISANDFUTURE") {
+                ranges.push(RangeOverride {
+                    recurrence_id: id,
+                    timing: sibling_timing,
+                    component: sibling,
+                });
+            } else {
+                overridden.insert(id);
+                let occurrence_end = sibling_timing.start + sibling_timing.duration;
+                if overlaps(sibling_timing.start, occurrence_end, window) {
+                    overrides.push(Occurrence {
+                        start: sibling_timing.start,
+                        end: occurrence_end,
+                        recurrence_id: id,
+                        all_day: sibling_timing.all_day,
+                        component: sibling,
+                    });
+                }
+            }
+        }
+        ranges.sort_by_key(|range| range.recurrence_id);
+        overrides.sort_by_key(|occurrence| occurrence.start);
+
+        // The instances moved forward by a range override must still be generated.
+        let shift = ranges
+            .iter()
+            .map(|range| range.timing.start - range.recurrence_id)
+            .max()
+            .unwrap_or_else(Duration::zero)
+            .max(Duration::zero());
+        let limit = end + shift;
+        let from = start - timing.duration.max(Duration::zero()) - shift.max(Duration::zero());
+
+        let rules = master
+            .properties()
+            .iter()
+            .filter(|p| p.name.eq_ignore_ascii_case("RRULE"))
+            .map(|p| match p.typed_value(Format::Ical)? {
+                Value::Recur(recur) => {
+                    let mut rule = Rule::from(recur.as_ref());
+                    // A UTC UNTIL is compared with the local time of a zoned DTSTART.
+                    if let (
+                        Some(tzid),
+                        Some(RecurEnd::Until(Until::DateTime(DateTime::Utc(until)))),
+                    ) = (&timing.tzid, &recur.end)
+                    {
+                        let until = local_until(calendar, *until, tzid)
+                            .ok_or_else(|| RecurrenceError::UnresolvedUntil(tzid.clone()))?;
+                        rule.until = Some((until, false));
+                    }
+
+                    Ok(RuleIter::new(rule, timing.start, from, limit).peekable())
+                }
+                value => Err(unexpected(p, &value)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut rdates = Vec::new();
+        for property in master
+            .properties()
+            .iter()
+            .filter(|p| p.name.eq_ignore_ascii_case("RDATE"))
+        {
+            for value in property.typed_values(Format::Ical)? {
+                if let Value::Period(period) = &value {
+                    let period_start = period.start.naive();
+                    let period_end = match &period.end {
+                        PeriodEnd::DateTime(end) => end.naive(),
+                        PeriodEnd::Duration(duration) => period_start + duration.to_chrono(),
+                    };
+                    rdates.push((period_start, Some(period_end)));
+                } else {
+                    let date_time = instant(&value).ok_or_else(|| unexpected(property, &value))?;
+                    rdates.push((date_time.0, None));
+                }
+            }
+        }
+        // Without RRULE, the DTSTART is still the first instance, unless a RDATE period
+        // starting at the same time gives its end.
+        if rules.is_empty() && !rdates.iter().any(|rdate| rdate.0 == timing.start) {
+            rdates.push((timing.start, None));
+        }
+        rdates.sort();
+
+        let mut exdates = HashSet::new();
+        let mut exdays = HashSet::new();
+        for property in master
+            .properties()
+            .iter()
+            .filter(|p| p.name.eq_ignore_ascii_case("EXDATE"))
+        {
+            for value in property.typed_values(Format::Ical)? {
+                match instant(&value).ok_or_else(|| unexpected(property, &value))? {
+                    (date, true) => exdays.insert(date.date()),
+                    (date_time, false) => exdates.insert(date_time),
+                };
+            }
+        }
+
+        Ok(Occurrences {
+            master,
+            timing,
+            window,
+            limit,
+            rules,
+            rdates: rdates.into(),
+            exdates,
+            exdays,
+            overridden,
+            ranges,
+            overrides: overrides.into(),
+            pending: None,
+            last: None,
+        })
+    }
+
+    /// Return the next instance of the recurrence set, with its explicit end if any.
+    fn next_instance(&mut self) -> Option<(NaiveDateTime, Option<NaiveDateTime>)> {
+        let from_rules = self
+            .rules
+            .iter_mut()
+            .filter_map(|rule| rule.peek().cloned())
+            .min();
+        let from_rdates = self.rdates.front().map(|rdate| rdate.0);
+
+        let next = match (from_rules, from_rdates) {
+            (None, None) => None,
+            (Some(rule), Some(rdate)) if rdate <= rule => self.rdates.pop_front(),
+            (None, Some(_)) => self.rdates.pop_front(),
+            (Some(rule), _) => Some((rule, None)),
+        }?;
+
+        for rule in self.rules.iter_mut() {
+            while rule.peek().is_some_and(|instance| *instance <= next.0) {
+                rule.next();
+            }
+        }
+
+        Some(next)
+    }
+
+    /// Return the next occurrence generated by the master component, overridden instances
+    /// excluded.
+    fn next_master(&mut self) -> Option<Occurrence<'a, C>> {
+        loop {
+            let (recurrence_id, explicit_end) = self.next_instance()?;
+
+            if self.last.is_some_and(|last| last >= recurrence_id) {
+                continue;
+            }
+            self.last = Some(recurrence_id);
+
+            if recurrence_id > self.limit {
+                return None;
+            }
+
+            if self.exdates.contains(&recurrence_id)
+                || self.exdays.contains(&recurrence_id.date())
+                || self.overridden.contains(&recurrence_id)
+            {
+                continue;
+            }
+
+            let range = self
+                .ranges
+                .iter()
+                .rev()
+                .find(|range| range.recurrence_id <= recurrence_id);
+
+            let occurrence = match range {
+                Some(range) => {
+                    let start = if range.recurrence_id == recurrence_id {
+                        range.timing.start
+                    } else {
+                        recurrence_id + (range.timing.start - range.recurrence_id)
+                    };
+
+                    Occurrence {
+                        start,
+                        end: start + range.timing.duration,
+                        recurrence_id,
+                        all_day: range.timing.all_day,
+                        component: range.component,
+                    }
+                }
+                None => Occurrence {
+                    start: recurrence_id,
+                    end: explicit_end.unwrap_or(recurrence_id + self.timing.duration),
+                    recurrence_id,
+                    all_day: self.timing.all_day,
+                    component: self.master,
+                },
+            };
+
+            if overlaps(occurrence.start, occurrence.end, self.window) {
+                return Some(occurrence);
+            }
+        }
+    }
+}
+
+impl<'a, C: Recurrent> Iterator for Occurrences<'a, C> {
+    type Item = Occurrence<'a, C>;
+
+    fn next(&mut self) -> Option<Occurrence<'a, C>> {
+        if self.pending.is_none() {
+            self.pending = self.next_master();
+        }
+
+        let take_override = match (&self.pending, self.overrides.front()) {
+            (Some(master), Some(first)) => first.start < master.start,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+
+        if take_override {
+            self.overrides.pop_front()
+        } else {
+            self.pending.take()
+        }
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::{Occurrences, RecurrenceError, Recurrent};
+    use crate::parser::ical::component::IcalCalendar;
+    use crate::IcalParser;
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use std::io::Cursor;
+
+    fn calendar(events: &str) -> IcalCalendar {
+        let content = format!("BEGIN:VCALENDAR\n{}\nEND:VCALENDAR\n", events);
+
+        IcalParser::new(Cursor::new(content))
+            .next()
+            .unwrap()
+            .unwrap()
+    }
+
+    fn date_time(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").unwrap()
+    }
+
+    fn starts(rule: &str, dtstart: &str, from: &str, to: &str) -> Vec<String> {
+        let cal = calendar(&format!(
+            "BEGIN:VEVENT\nUID:1\nDTSTART:{}\n{}\nEND:VEVENT",
+            dtstart, rule
+        ));
+
+        cal.events[0]
+            .occurrences(&cal, date_time(from), date_time(to))
+            .unwrap()
+            .map(|o| o.start.format("%Y%m%dT%H%M%S").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn expand_daily_count() {
+        // RFC 5545: Daily for 10 occurrences.
+        let res = starts(
+            "RRULE:FREQ=DAILY;COUNT=10",
+            "19970902T090000",
+            "19970101T000000",
+            "19980101T000000",
+        );
+        assert_eq!(res.len(), 10);
+        assert_eq!(res[9], "19970911T090000");
+    }
+
+    #[test]
+    fn expand_monthly_last_weekday_with_setpos() {
+        // RFC 5545: The last work day of the month. The DTSTART isn't synchronized with the
+        // rule but is still the first instance.
+        let res = starts(
+            "RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            "19970929T090000",
+            "19970101T000000",
+            "19980401T000000",
+        );
+        assert_eq!(
+            res,
+            vec![
+                "19970929T090000",
+                "19970930T090000",
+                "19971031T090000",
+                "19971128T090000",
+                "19971231T090000",
+                "19980130T090000",
+                "19980227T090000",
+                "19980331T090000",
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_yearly_byweekno() {
+        // RFC 5545: Monday of week number 20 (where the default start of the week is Monday).
+        let res = starts(
+            "RRULE:FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO",
+            "19970512T090000",
+            "19970101T000000",
+            "20000101T000000",
+        );
+        assert_eq!(
+            res,
+            vec!["19970512T090000", "19980511T090000", "19990517T090000"]
+        );
+    }
+
+    #[test]
+    fn expand_weekly_with_wkst() {
+        // RFC 5545: the WKST changes the generated instances.
+        let monday = starts(
+            "RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=MO",
+            "19970805T090000",
+            "19970101T000000",
+            "19980101T000000",
+        );
+        assert_eq!(
+            monday,
+            vec![
+                "19970805T090000",
+                "19970810T090000",
+                "19970819T090000",
+                "19970824T090000"
+            ]
+        );
+
+        let sunday = starts(
+            "RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=SU",
+            "19970805T090000",
+            "19970101T000000",
+            "19980101T000000",
+        );
+        assert_eq!(
+            sunday,
+            vec![
+                "19970805T090000",
+                "19970817T090000",
+                "19970819T090000",
+                "19970831T090000"
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_until_and_friday_13th() {
+        // RFC 5545: Every Friday the 13th, until 2000.
+        let res = starts(
+            "RRULE:FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13;UNTIL=20000101T000000Z\n\
+             EXDATE:19970902T090000",
+            "19970902T090000",
+            "19970101T000000",
+            "20100101T000000",
+        );
+        assert_eq!(
+            res,
+            vec![
+                "19980213T090000",
+                "19980313T090000",
+                "19981113T090000",
+                "19990813T090000"
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_infinite_rule_in_window() {
+        let res = starts(
+            "RRULE:FREQ=MINUTELY;INTERVAL=20;BYHOUR=9,10,11,12,13,14,15,16",
+            "19970902T090000",
+            "20200101T160000",
+            "20200101T170000",
+        );
+        assert_eq!(
+            res,
+            vec!["20200101T160000", "20200101T162000", "20200101T164000"]
+        );
+    }
+
+    #[test]
+    fn stop_the_rules_without_any_instance() {
+        let res = starts(
+            "RRULE:FREQ=MINUTELY;COUNT=3;BYMONTH=2;BYMONTHDAY=30",
+            "19700101T000000",
+            "20240101T000000",
+            "20250101T000000",
+        );
+        assert!(res.is_empty());
+
+        let res = starts(
+            "RRULE:FREQ=HOURLY;BYMONTH=2;BYMONTHDAY=30",
+            "19700101T000000",
+            "20240101T000000",
+            "20250101T000000",
+        );
+        assert!(res.is_empty());
+    }
+
+    #[test]
+    fn expand_sparse_rules_with_count() {
+        let res = starts(
+            "RRULE:FREQ=HOURLY;COUNT=3;BYMONTH=2;BYMONTHDAY=29",
+            "19700101T000000",
+            "19700101T000000",
+            "19800101T000000",
+        );
+        assert_eq!(
+            res,
+            vec!["19700101T000000", "19720229T000000", "19720229T010000"]
+        );
+    }
+
+    #[test]
+    fn match_the_names_in_any_case() {
+        let res = starts(
+            "rrule:FREQ=DAILY;COUNT=3\nexdate:19970903T090000",
+            "19970902T090000",
+            "19970101T000000",
+            "19980101T000000",
+        );
+        assert_eq!(res, vec!["19970902T090000", "19970904T090000"]);
+    }
+
+    #[test]
+    #[cfg(feature = "timezone")]
+    fn convert_the_utc_until_into_the_start_zone() {
+        let cal = calendar(
+            "BEGIN:VTIMEZONE\n\
+             TZID:Europe/Paris\n\
+             BEGIN:STANDARD\n\
+             DTSTART:19701025T030000\n\
+             TZOFFSETFROM:+0100\n\
+             TZOFFSETTO:+0100\n\
+             END:STANDARD\n\
+             END:VTIMEZONE\n\
+             BEGIN:VEVENT\n\
+             UID:1\n\
+             DTSTART;TZID=Europe/Paris:20240101T090000\n\
+             RRULE:FREQ=DAILY;UNTIL=20240103T080000Z\n\
+             END:VEVENT",
+        );
+        let (start, end) = (date_time("20240101T000000"), date_time("20240201T000000"));
+
+        let res: Vec<_> = cal.events[0]
+            .occurrences(&cal, start, end)
+            .unwrap()
+            .map(|o| o.start)
+            .collect();
+        assert_eq!(
+            res,
+            vec![
+                date_time("20240101T090000"),
+                date_time("20240102T090000"),
+                date_time("20240103T090000")
+            ]
+        );
+
+        // Without the calendar, the time zone is unknown.
+        assert!(matches!(
+            Occurrences::new(&cal.events[0], &cal.events, start, end),
+            Err(RecurrenceError::UnresolvedUntil(_))
+        ));
+    }
+
+    #[test]
+    fn keep_the_start_with_rdates_only() {
+        let cal = calendar(
+            "BEGIN:VEVENT\n\
+             UID:1\n\
+             DTSTART:20230102T100000\n\
+             DTEND:20230102T110000\n\
+             RDATE:20230105T100000\n\
+             END:VEVENT",
+        );
+
+        let res: Vec<_> = cal.events[0]
+            .occurrences(
+                &cal,
+                date_time("20230101T000000"),
+                date_time("20230201T000000"),
+            )
+            .unwrap()
+            .map(|o| o.start)
+            .collect();
+
+        assert_eq!(
+            res,
+            vec![date_time("20230102T100000"), date_time("20230105T100000")]
+        );
+    }
+
+    #[test]
+    fn merge_rdates_and_overrides() {
+        let cal = calendar(
+            "BEGIN:VEVENT\n\
+             UID:1\n\
+             DTSTART:20230102T100000\n\
+             DTEND:20230102T110000\n\
+             RRULE:FREQ=DAILY;COUNT=3\n\
+             RDATE;VALUE=PERIOD:20230110T080000/PT30M\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             UID:1\n\
+             RECURRENCE-ID:20230103T100000\n\
+             DTSTART:20230105T150000\n\
+             DTEND:20230105T160000\n\
+             END:VEVENT",
+        );
+
+        let res: Vec<_> = cal.events[0]
+            .occurrences(
+                &cal,
+                date_time("20230101T000000"),
+                date_time("20230201T000000"),
+            )
+            .unwrap()
+            .map(|o| (o.start, o.end, o.component.properties.len()))
+            .collect();
+
+        assert_eq!(
+            res,
+            vec![
+                (
+                    date_time("20230102T100000"),
+                    date_time("20230102T110000"),
+                    5
+                ),
+                (
+                    date_time("20230104T100000"),
+                    date_time("20230104T110000"),
+                    5
+                ),
+                (
+                    date_time("20230105T150000"),
+                    date_time("20230105T160000"),
+                    4
+                ),
+                (
+                    date_time("20230110T080000"),
+                    date_time("20230110T083000"),
+                    5
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,637 @@
+//! Parse and expand a `RRULE` value.
+//!
+//! The expansion follows the algorithm described in
+//! [RFC5545 section 3.3.10](https://tools.ietf.org/html/rfc5545#section-3.3.10): each
+//! "period" of the frequency (a year, a month, a week...) is turned into a set of candidate
+//! dates filtered by the `BYxxx` rule parts, then `BYSETPOS` is applied on the sorted set.
+
+// Sys mods
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+// Internal mods
+use crate::value::recur::{Frequency, Recur, RecurEnd, Until};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+
+/// A `Recur` with its default values filled, ready to be expanded.
+#[derive(Debug, Clone)]
+pub(crate) struct Rule {
+    pub freq: Frequency,
+    pub interval: u32,
+    /// The `UNTIL` bound and whether it was given as a `DATE`.
+    pub until: Option<(NaiveDateTime, bool)>,
+    pub count: Option<u32>,
+    pub by_second: Vec<u32>,
+    pub by_minute: Vec<u32>,
+    pub by_hour: Vec<u32>,
+    /// `(ordinal, weekday)` tuples, an ordinal of 0 means "every".
+    pub by_day: Vec<(i32, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub by_year_day: Vec<i32>,
+    pub by_week_no: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+    pub wkst: Weekday,
+}
+
+impl<'a> From<&'a Recur> for Rule {
+    fn from(recur: &'a Recur) -> Rule {
+        let (until, count) = match &recur.end {
+            Some(RecurEnd::Until(Until::Date(date))) => {
+                (Some((date.and_time(NaiveTime::MIN), true)), None)
+            }
+            Some(RecurEnd::Until(Until::DateTime(date_time))) => {
+                (Some((date_time.naive(), false)), None)
+            }
+            Some(RecurEnd::Count(count)) => (None, Some(*count)),
+            None => (None, None),
+        };
+
+        Rule {
+            freq: recur.freq,
+            interval: recur.interval.unwrap_or(1).max(1),
+            until,
+            count,
+            by_second: recur.by_second.clone(),
+            by_minute: recur.by_minute.clone(),
+            by_hour: recur.by_hour.clone(),
+            by_day: recur
+                .by_day
+                .iter()
+                .map(|day| (day.ordinal.unwrap_or(0), day.weekday))
+                .collect(),
+            by_month_day: recur.by_month_day.clone(),
+            by_year_day: recur.by_year_day.clone(),
+            by_week_no: recur.by_week_no.clone(),
+            by_month: recur.by_month.clone(),
+            by_set_pos: recur.by_set_pos.clone(),
+            wkst: recur.wkst.unwrap_or(Weekday::Mon),
+        }
+    }
+}
+
+impl Rule {
+    /// Check if the `BYMONTHDAY` days exist in none of the `BYMONTH` months, such as in
+    /// `BYMONTH=2;BYMONTHDAY=30`, so that the rule never gives any instance.
+    pub fn never_matches(&self) -> bool {
+        // The longest length of each month, in a leap year.
+        const MONTH_DAYS: [i32; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+        if self.by_month_day.is_empty() {
+            return false;
+        }
+
+        !(1..=12u32)
+            .filter(|month| self.by_month.is_empty() || self.by_month.contains(month))
+            .any(|month| {
+                let len = MONTH_DAYS[month as usize - 1];
+                self.by_month_day
+                    .iter()
+                    .any(|day| (1..=len).contains(&day.abs()))
+            })
+    }
+
+    /// Fill the implicit rule parts from `DTSTART` as described in RFC 5545:
+    /// "Information, not contained in the rule, necessary to determine the various recurrence
+    /// instance start time and dates are derived from the Start Time ("DTSTART") component
+    /// attribute".
+    fn with_defaults(mut self, dtstart: NaiveDateTime) -> Rule {
+        if self.by_week_no.is_empty()
+            && self.by_year_day.is_empty()
+            && self.by_month_day.is_empty()
+            && self.by_day.is_empty()
+        {
+            match self.freq {
+                Frequency::Yearly => {
+                    if self.by_month.is_empty() {
+                        self.by_month = vec![dtstart.month()];
+                    }
+                    self.by_month_day = vec![dtstart.day() as i32];
+                }
+                Frequency::Monthly => self.by_month_day = vec![dtstart.day() as i32],
+                Frequency::Weekly => self.by_day = vec![(0, dtstart.weekday())],
+                _ => {}
+            }
+        }
+
+        if self.by_hour.is_empty() && self.freq > Frequency::Hourly {
+            self.by_hour = vec![dtstart.hour()];
+        }
+        if self.by_minute.is_empty() && self.freq > Frequency::Minutely {
+            self.by_minute = vec![dtstart.minute()];
+        }
+        if self.by_second.is_empty() && self.freq > Frequency::Secondly {
+            self.by_second = vec![dtstart.second()];
+        }
+
+        self
+    }
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(31)
+}
+
+fn days_in_year(year: i32) -> u32 {
+    if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+        366
+    } else {
+        365
+    }
+}
+
+/// Number of days between `wkst` and the previous (or same) `weekday`.
+fn days_since(weekday: Weekday, wkst: Weekday) -> i64 {
+    (7 + weekday.num_days_from_monday() as i64 - wkst.num_days_from_monday() as i64) % 7
+}
+
+/// First day of the week number 1 of `year`: the week, starting on `wkst`, containing at
+/// least four days of the year.
+fn week_one_start(year: i32, wkst: Weekday) -> Option<NaiveDate> {
+    let jan_4 = NaiveDate::from_ymd_opt(year, 1, 4)?;
+
+    Some(jan_4 - Duration::days(days_since(jan_4.weekday(), wkst)))
+}
+
+/// Return the week number of `date` and the number of weeks of its week-numbering year.
+fn week_number(date: NaiveDate, wkst: Weekday) -> Option<(i32, i32)> {
+    let mut year = date.year();
+    let mut start = week_one_start(year, wkst)?;
+
+    if date < start {
+        year -= 1;
+        start = week_one_start(year, wkst)?;
+    } else {
+        let next = week_one_start(year + 1, wkst)?;
+        if date >= next {
+            year += 1;
+            start = next;
+        }
+    }
+
+    let number = (date - start).num_days() / 7 + 1;
+    let total = (week_one_start(year + 1, wkst)? - start).num_days() / 7;
+
+    Some((number as i32, total as i32))
+}
+
+/// Check if a `value` counted from 1 (or from -1 backward) matches one of `list`.
+fn matches_ordinal(list: &[i32], value: u32, len: u32) -> bool {
+    let forward = value as i32;
+    let backward = forward - len as i32 - 1;
+
+    list.iter().any(|v| *v == forward || *v == backward)
+}
+
+/// The days of a 400 years Gregorian cycle, after which the calendar repeats itself.
+const CYCLE_DAYS: i64 = 146_097;
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// A lazy iterator over the start of every instance of a `Rule`.
+///
+/// `DTSTART` is always the first instance and is counted by `COUNT`. The iteration stops at
+/// `UNTIL`, after `COUNT` instances or when a period starts after `limit`. It also stops
+/// right after `DTSTART` for a rule which never matches, such as `BYMONTH=2;BYMONTHDAY=30`,
+/// and when the calendar and the periods repeat themselves without any instance.
+pub(crate) struct RuleIter {
+    rule: Rule,
+    dtstart: NaiveDateTime,
+    limit: NaiveDateTime,
+    period: i64,
+    /// The last period holding an instance, or the first one expanded.
+    last_found: i64,
+    buffer: VecDeque<NaiveDateTime>,
+    emitted: u32,
+    started: bool,
+    finished: bool,
+}
+
+impl RuleIter {
+    /// Create an iterator over the instances of `rule` until `limit`.
+    ///
+    /// When the rule has no `COUNT`, the periods entirely before `from` are skipped.
+    pub fn new(
+        rule: Rule,
+        dtstart: NaiveDateTime,
+        from: NaiveDateTime,
+        limit: NaiveDateTime,
+    ) -> RuleIter {
+        let rule = rule.with_defaults(dtstart);
+
+        let mut iter = RuleIter {
+            rule,
+            dtstart,
+            limit,
+            period: 0,
+            last_found: 0,
+            buffer: VecDeque::new(),
+            emitted: 0,
+            started: false,
+            finished: false,
+        };
+
+        if iter.rule.count.is_none() && from > dtstart {
+            iter.period = iter.skip_periods(from);
+            iter.last_found = iter.period;
+        }
+
+        iter
+    }
+
+    /// Return the number of whole periods which can be skipped before reaching `from`.
+    fn skip_periods(&self, from: NaiveDateTime) -> i64 {
+        let interval = self.rule.interval as i64;
+        let elapsed = match self.rule.freq {
+            Frequency::Yearly => (from.year() - self.dtstart.year()) as i64,
+            Frequency::Monthly => {
+                (from.year() - self.dtstart.year()) as i64 * 12 + from.month() as i64
+                    - self.dtstart.month() as i64
+            }
+            Frequency::Weekly => (from - self.dtstart).num_weeks(),
+            Frequency::Daily => (from - self.dtstart).num_days(),
+            Frequency::Hourly => (from - self.dtstart).num_hours(),
+            Frequency::Minutely => (from - self.dtstart).num_minutes(),
+            Frequency::Secondly => (from - self.dtstart).num_seconds(),
+        };
+
+        // Keep one period of margin as the periods are not aligned on `from`.
+        (elapsed / interval - 1).max(0)
+    }
+
+    /// Return the first instant of the `index`th period and the days it covers.
+    fn period_days(&self, index: i64) -> Option<(NaiveDateTime, Vec<NaiveDate>)> {
+        let step = index.checked_mul(self.rule.interval as i64)?;
+        let start_date = self.dtstart.date();
+
+        let (start, days) = match self.rule.freq {
+            Frequency::Yearly => {
+                let year = i32::try_from(self.dtstart.year() as i64 + step).ok()?;
+                let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+                let days = if self.rule.by_month.is_empty() {
+                    (0..days_in_year(year))
+                        .filter_map(|d| first.checked_add_signed(Duration::days(d as i64)))
+                        .collect()
+                } else {
+                    // Only the days of the `BYMONTH` months can match.
+                    let mut months = self.rule.by_month.clone();
+                    months.sort_unstable();
+                    months.dedup();
+                    months
+                        .into_iter()
+                        .flat_map(|m| {
+                            (1..=days_in_month(year, m))
+                                .filter_map(move |d| NaiveDate::from_ymd_opt(year, m, d))
+                        })
+                        .collect()
+                };
+                (first.and_time(NaiveTime::MIN), days)
+            }
+            Frequency::Monthly => {
+                let month = self.dtstart.year() as i64 * 12 + self.dtstart.month0() as i64 + step;
+                let year = i32::try_from(month.div_euclid(12)).ok()?;
+                let month = month.rem_euclid(12) as u32 + 1;
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let days = (0..days_in_month(year, month))
+                    .filter_map(|d| first.checked_add_signed(Duration::days(d as i64)))
+                    .collect();
+                (first.and_time(NaiveTime::MIN), days)
+            }
+            Frequency::Weekly => {
+                let week_start =
+                    start_date - Duration::days(days_since(start_date.weekday(), self.rule.wkst));
+                let first = week_start.checked_add_signed(Duration::try_weeks(step)?)?;
+                let days = (0..7)
+                    .filter_map(|d| first.checked_add_signed(Duration::days(d)))
+                    .collect();
+                (first.and_time(NaiveTime::MIN), days)
+            }
+            Frequency::Daily => {
+                let day = start_date.checked_add_signed(Duration::try_days(step)?)?;
+                (day.and_time(NaiveTime::MIN), vec![day])
+            }
+            Frequency::Hourly => {
+                let hour = self.dtstart.with_minute(0)?.with_second(0)?;
+                let start = hour.checked_add_signed(Duration::try_hours(step)?)?;
+                (start, vec![start.date()])
+            }
+            Frequency::Minutely => {
+                let minute = self.dtstart.with_second(0)?;
+                let start = minute.checked_add_signed(Duration::try_minutes(step)?)?;
+                (start, vec![start.date()])
+            }
+            Frequency::Secondly => {
+                let start = self
+                    .dtstart
+                    .checked_add_signed(Duration::try_seconds(step)?)?;
+                (start, vec![start.date()])
+            }
+        };
+
+        Some((start, days))
+    }
+
+    /// Return the number of periods after which the Gregorian calendar and the periods of the
+    /// rule are aligned again, so that a rule without any instance for as long never has one.
+    fn cycle_periods(&self) -> i64 {
+        let cycle = match self.rule.freq {
+            Frequency::Yearly => 400,
+            Frequency::Monthly => 400 * 12,
+            Frequency::Weekly => CYCLE_DAYS / 7,
+            Frequency::Daily => CYCLE_DAYS,
+            Frequency::Hourly => CYCLE_DAYS * 24,
+            Frequency::Minutely => CYCLE_DAYS * 24 * 60,
+            Frequency::Secondly => CYCLE_DAYS * 24 * 60 * 60,
+        };
+
+        cycle / gcd(cycle, self.rule.interval as i64)
+    }
+
+    /// Return the index of the first period starting at or after `instant`, for the
+    /// frequencies finer than a day.
+    fn period_at(&self, instant: NaiveDateTime) -> Option<i64> {
+        let unit = match self.rule.freq {
+            Frequency::Hourly => 3600,
+            Frequency::Minutely => 60,
+            _ => 1,
+        } * self.rule.interval as i64;
+        let elapsed = (instant - self.period_days(0)?.0).num_seconds();
+
+        Some((elapsed + unit - 1).div_euclid(unit))
+    }
+
+    /// Return the start of the next day, hour or minute when the period starting at `start`
+    /// can't hold any instance, for the frequencies finer than a day.
+    fn next_candidate(&self, start: NaiveDateTime) -> Option<NaiveDateTime> {
+        let rule = &self.rule;
+        if rule.freq >= Frequency::Daily {
+            return None;
+        }
+
+        if !self.day_matches(start.date()) {
+            Some(start.date().succ_opt()?.and_time(NaiveTime::MIN))
+        } else if rule.freq < Frequency::Hourly
+            && !rule.by_hour.is_empty()
+            && !rule.by_hour.contains(&start.hour())
+        {
+            start
+                .with_minute(0)?
+                .with_second(0)?
+                .checked_add_signed(Duration::hours(1))
+        } else if rule.freq < Frequency::Minutely
+            && !rule.by_minute.is_empty()
+            && !rule.by_minute.contains(&start.minute())
+        {
+            start
+                .with_second(0)?
+                .checked_add_signed(Duration::minutes(1))
+        } else {
+            None
+        }
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let rule = &self.rule;
+
+        if !rule.by_month.is_empty() && !rule.by_month.contains(&date.month()) {
+            return false;
+        }
+
+        if !rule.by_week_no.is_empty() {
+            match week_number(date, rule.wkst) {
+                Some((number, total)) => {
+                    if !matches_ordinal(&rule.by_week_no, number as u32, total as u32) {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        if !rule.by_year_day.is_empty()
+            && !matches_ordinal(&rule.by_year_day, date.ordinal(), days_in_year(date.year()))
+        {
+            return false;
+        }
+
+        let month_len = days_in_month(date.year(), date.month());
+        if !rule.by_month_day.is_empty()
+            && !matches_ordinal(&rule.by_month_day, date.day(), month_len)
+        {
+            return false;
+        }
+
+        if !rule.by_day.is_empty() {
+            // The ordinal is relative to the month for a MONTHLY rule or a YEARLY rule
+            // restricted by BYMONTH, and relative to the year for other YEARLY rules.
+            let scope = match rule.freq {
+                Frequency::Monthly => Some((date.day(), month_len)),
+                Frequency::Yearly if !rule.by_month.is_empty() => Some((date.day(), month_len)),
+                Frequency::Yearly => Some((date.ordinal(), days_in_year(date.year()))),
+                _ => None,
+            };
+
+            let found = rule.by_day.iter().any(|(ordinal, weekday)| {
+                if *weekday != date.weekday() {
+                    return false;
+                }
+
+                match scope {
+                    Some((day, len)) if *ordinal != 0 => {
+                        let forward = (day as i32 - 1) / 7 + 1;
+                        let backward = -((len as i32 - day as i32) / 7 + 1);
+                        *ordinal == forward || *ordinal == backward
+                    }
+                    _ => true,
+                }
+            });
+
+            if !found {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Return the list of `(hour, minute, second)` of the instances in a day of the period
+    /// starting at `start`.
+    fn times(&self, start: NaiveDateTime) -> Vec<NaiveTime> {
+        let rule = &self.rule;
+        let filter = |list: &Vec<u32>, value: u32| -> Vec<u32> {
+            if list.is_empty() || list.contains(&value) {
+                vec![value]
+            } else {
+                Vec::new()
+            }
+        };
+
+        let hours = match rule.freq {
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly => {
+                filter(&rule.by_hour, start.hour())
+            }
+            _ => rule.by_hour.clone(),
+        };
+        let minutes = match rule.freq {
+            Frequency::Minutely | Frequency::Secondly => filter(&rule.by_minute, start.minute()),
+            _ => rule.by_minute.clone(),
+        };
+        let seconds = match rule.freq {
+            Frequency::Secondly => filter(&rule.by_second, start.second()),
+            _ => rule.by_second.clone(),
+        };
+
+        let mut times = Vec::new();
+        for hour in &hours {
+            for minute in &minutes {
+                for second in &seconds {
+                    // Leap seconds (60) can't be represented and are skipped.
+                    if let Some(time) = NaiveTime::from_hms_opt(*hour, *minute, *second) {
+                        times.push(time);
+                    }
+                }
+            }
+        }
+
+        times.sort();
+        times.dedup();
+        times
+    }
+
+    /// Compute the sorted instances of the next period. Return `false` once there is no more
+    /// period to expand.
+    fn fill_buffer(&mut self) -> bool {
+        if self.rule.never_matches() {
+            return false;
+        }
+
+        let (start, days) = match self.period_days(self.period) {
+            Some(period) => period,
+            None => return false,
+        };
+        self.period += 1;
+
+        if start > self.limit {
+            return false;
+        }
+        if let Some((until, _)) = self.rule.until {
+            if start > until {
+                return false;
+            }
+        }
+        if self.period - self.last_found > self.cycle_periods() {
+            return false;
+        }
+
+        if let Some(next) = self.next_candidate(start) {
+            // Jump over the periods of the day, hour or minute which can't match.
+            return match self.period_at(next) {
+                Some(period) => {
+                    self.period = period.max(self.period);
+                    true
+                }
+                None => false,
+            };
+        }
+
+        let times = self.times(start);
+        let mut set: Vec<NaiveDateTime> = days
+            .into_iter()
+            .filter(|day| self.day_matches(*day))
+            .flat_map(|day| times.iter().map(move |time| day.and_time(*time)))
+            .collect();
+
+        if !self.rule.by_set_pos.is_empty() {
+            let len = set.len() as i32;
+            let mut selected: Vec<NaiveDateTime> = self
+                .rule
+                .by_set_pos
+                .iter()
+                .filter_map(|pos| {
+                    let index = if *pos > 0 { pos - 1 } else { len + pos };
+                    if index >= 0 && index < len {
+                        Some(set[index as usize])
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            selected.sort();
+            selected.dedup();
+            set = selected;
+        }
+
+        if !set.is_empty() {
+            self.last_found = self.period;
+        }
+
+        let dtstart = self.dtstart;
+        self.buffer
+            .extend(set.into_iter().filter(|dt| *dt > dtstart));
+
+        true
+    }
+
+    fn is_after_until(&self, instance: NaiveDateTime) -> bool {
+        match self.rule.until {
+            Some((until, true)) => instance.date() > until.date(),
+            Some((until, false)) => instance > until,
+            None => false,
+        }
+    }
+}
+
+impl Iterator for RuleIter {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<NaiveDateTime> {
+        if self.finished {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            self.emitted = 1;
+            return Some(self.dtstart);
+        }
+
+        loop {
+            if let Some(count) = self.rule.count {
+                if self.emitted >= count {
+                    self.finished = true;
+                    return None;
+                }
+            }
+
+            if let Some(instance) = self.buffer.pop_front() {
+                if self.is_after_until(instance) {
+                    self.finished = true;
+                    return None;
+                }
+
+                self.emitted += 1;
+                return Some(instance);
+            }
+
+            if !self.fill_buffer() {
+                self.finished = true;
+                return None;
+            }
+        }
+    }
+}
@@ -0,0 +1,700 @@
+//! Resolve the date-times qualified by a `TZID` parameter.
+//!
+//! The offsets of a time zone are described by the `VTIMEZONE` component of the calendar
+//! referencing it, as defined in
+//! [RFC5545 section 3.6.5](https://tools.ietf.org/html/rfc5545#section-3.6.5). Each `STANDARD`
+//! and `DAYLIGHT` sub-component gives the local time of its first onset (`DTSTART`), the
+//! following onsets (`RRULE` and `RDATE`) and the offsets in use before and after them
+//! (`TZOFFSETFROM` and `TZOFFSETTO`).
+//!
+//! Around a transition a local time can occur twice (overlap) or not at all (gap). Both cases
+//! are reported by `TimeZoneRules::resolve` and solved as described in
+//! [RFC5545 section 3.3.5](https://tools.ietf.org/html/rfc5545#section-3.3.5): an ambiguous
+//! time refers to its first occurrence and a skipped time is interpreted with the offset in
+//! use before the gap.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["timezone"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use ical::parser::Component;
+//! use ical::value::{Format, Value};
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/ical_example_1.ics")
+//!     .unwrap());
+//!
+//! let calendar = ical::IcalParser::new(buf).next().unwrap().unwrap();
+//! let dtstart = calendar.events[0].get_property("DTSTART").unwrap();
+//!
+//! if let Value::DateTime(date_time) = dtstart.typed_value(Format::Ical).unwrap() {
+//!     println!("{}", calendar.to_utc(&date_time).unwrap());
+//! }
+//! ```
+//!
+//! With the `tzdb` feature, the `TZID`s without a `VTIMEZONE` are looked up in the bundled IANA
+//! database, recognizing the Windows zone names too.
+//!
+//! `IcalCalendar::to_utc` decodes the `VTIMEZONE` at each call. To convert many date-times,
+//! `TimeZones` keeps the decoded time zones of the calendar instead.
+
+#[cfg(feature = "tzdb")]
+pub mod tzdb;
+pub mod windows;
+
+// Sys mods
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Internal mods
+use crate::parser::ical::component::{
+    IcalCalendar, IcalTimeZone, IcalTimeZoneTransition, IcalTimeZoneTransitionType,
+};
+use crate::parser::Component;
+use crate::property::Property;
+use crate::recurrence::instant;
+use crate::recurrence::rule::{Rule, RuleIter};
+use crate::value::recur::{RecurEnd, Until};
+use crate::value::{DateTime, Format, Value, ValueError};
+use chrono::{Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+#[derive(Debug, Error)]
+pub enum TimeZoneError {
+    #[error("Unknown time zone {0:?}.")]
+    UnknownTimeZone(String),
+    #[error("A floating date-time has no time zone.")]
+    Floating,
+    #[error("Missing a {0} property.")]
+    MissingProperty(&'static str),
+    #[error("The time zone {0:?} has no STANDARD or DAYLIGHT sub-component.")]
+    NoObservance(String),
+    #[error("{0}")]
+    Value(#[from] ValueError),
+}
+
+fn unexpected(property: &Property, value: &Value) -> TimeZoneError {
+    TimeZoneError::Value(ValueError::InvalidValue {
+        line: property.line(),
+        property: property.name.clone(),
+        value_type: property.value_type(Format::Ical),
+        value: value.to_string(),
+    })
+}
+
+/// The last onset computed for the rules ending with a `COUNT`, the years of the iCalendar
+/// dates having four digits.
+fn last_onset() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(9999, 12, 31)
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .unwrap_or(NaiveDateTime::MAX)
+}
+
+fn offset(seconds: i64) -> Duration {
+    Duration::seconds(seconds)
+}
+
+/// The UTC instant(s) matching a local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// The local time occurs exactly once.
+    Single(NaiveDateTime),
+    /// The local time occurs twice, when the clocks are set back.
+    Ambiguous {
+        earliest: NaiveDateTime,
+        latest: NaiveDateTime,
+    },
+    /// The local time is skipped, when the clocks are set forward. `utc` is computed with the
+    /// offset in use before the gap.
+    Gap { utc: NaiveDateTime },
+}
+
+impl Resolution {
+    /// Return the UTC instant chosen by RFC 5545: the first occurrence of an ambiguous time
+    /// and the offset before the gap for a skipped one.
+    pub fn utc(&self) -> NaiveDateTime {
+        match *self {
+            Resolution::Single(utc) => utc,
+            Resolution::Ambiguous { earliest, .. } => earliest,
+            Resolution::Gap { utc } => utc,
+        }
+    }
+}
+
+/// A `STANDARD` or `DAYLIGHT` sub-component, decoded.
+#[derive(Debug, Clone)]
+struct Observance {
+    kind: IcalTimeZoneTransitionType,
+    name: Option<String>,
+    /// Local time of the first onset.
+    dtstart: NaiveDateTime,
+    offset_from: FixedOffset,
+    offset_to: FixedOffset,
+    rules: Vec<Rule>,
+    rdates: Vec<NaiveDateTime>,
+}
+
+impl Observance {
+    fn from_transition(transition: &IcalTimeZoneTransition) -> Result<Observance, TimeZoneError> {
+        let utc_offset = |name: &'static str| -> Result<FixedOffset, TimeZoneError> {
+            let property = transition
+                .get_property(name)
+                .ok_or(TimeZoneError::MissingProperty(name))?;
+
+            match property.typed_value(Format::Ical)? {
+                Value::UtcOffset(offset) => Ok(offset),
+                value => Err(unexpected(property, &value)),
+            }
+        };
+
+        let offset_from = utc_offset("TZOFFSETFROM")?;
+        let offset_to = utc_offset("TZOFFSETTO")?;
+
+        let property = transition
+            .get_property("DTSTART")
+            .ok_or(TimeZoneError::MissingProperty("DTSTART"))?;
+        let value = property.typed_value(Format::Ical)?;
+        let dtstart = instant(&value)
+            .ok_or_else(|| unexpected(property, &value))?
+            .0;
+
+        let mut rules = Vec::new();
+        let mut rdates = Vec::new();
+
+        for property in &transition.properties {
+            match property.name.as_str() {
+                "RRULE" => match property.typed_value(Format::Ical)? {
+                    Value::Recur(recur) => {
+                        let mut rule = Rule::from(recur.as_ref());
+                        if rule.never_matches() {
+                            return Err(unexpected(property, &Value::Recur(recur)));
+                        }
+
+                        // An `UNTIL` date-time is given in UTC while the onsets are local.
+                        if let Some(RecurEnd::Until(Until::DateTime(DateTime::Utc(until)))) =
+                            recur.end
+                        {
+                            let until = until + offset(offset_from.local_minus_utc() as i64);
+                            rule.until = Some((until, false));
+                        }
+
+                        rules.push(rule);
+                    }
+                    value => return Err(unexpected(property, &value)),
+                },
+                "RDATE" => {
+                    for value in property.typed_values(Format::Ical)? {
+                        rdates.push(
+                            instant(&value)
+                                .ok_or_else(|| unexpected(property, &value))?
+                                .0,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Observance {
+            kind: transition.transition.clone(),
+            name: transition
+                .get_property("TZNAME")
+                .and_then(|p| p.value.clone()),
+            dtstart,
+            offset_from,
+            offset_to,
+            rules,
+            rdates,
+        })
+    }
+
+    /// Return the shift from UTC to the local time of the onsets.
+    fn shift(&self) -> Duration {
+        offset(self.offset_from.local_minus_utc() as i64)
+    }
+
+    /// Return the UTC instants of the `DTSTART`, the `RDATE`s and the onsets of the rules ending
+    /// with an `UNTIL` or a `COUNT`.
+    fn bounded_onsets(&self) -> Vec<NaiveDateTime> {
+        let mut onsets = vec![self.dtstart];
+        onsets.extend(&self.rdates);
+
+        for rule in self
+            .rules
+            .iter()
+            .filter(|r| r.until.is_some() || r.count.is_some())
+        {
+            let limit = rule.until.map_or_else(last_onset, |(until, _)| until);
+            onsets.extend(RuleIter::new(
+                rule.clone(),
+                self.dtstart,
+                self.dtstart,
+                limit,
+            ));
+        }
+
+        onsets
+            .into_iter()
+            .map(|onset| onset - self.shift())
+            .collect()
+    }
+
+    /// Return the UTC instant of the last onset of the endless rules at or before `utc`.
+    fn last_endless_onset(&self, utc: NaiveDateTime) -> Option<NaiveDateTime> {
+        let limit = utc + self.shift();
+
+        self.rules
+            .iter()
+            .filter(|r| r.until.is_none() && r.count.is_none() && self.dtstart <= limit)
+            .filter_map(|rule| {
+                // Start a year earlier so the previous onset of a yearly rule is not skipped.
+                let from = limit - Duration::days(366);
+
+                RuleIter::new(rule.clone(), self.dtstart, from, limit)
+                    .take_while(|onset| *onset <= limit)
+                    .last()
+            })
+            .max()
+            .map(|onset| onset - self.shift())
+    }
+}
+
+/// The decoded offsets of a `VTIMEZONE`.
+#[derive(Debug, Clone)]
+pub struct TimeZoneRules {
+    tzid: String,
+    observances: Vec<Observance>,
+    /// The sorted UTC onsets known in advance, with the index of their observance.
+    onsets: Vec<(NaiveDateTime, usize)>,
+}
+
+impl TimeZoneRules {
+    fn new(tzid: String, observances: Vec<Observance>) -> TimeZoneRules {
+        let mut onsets = observances
+            .iter()
+            .enumerate()
+            .flat_map(|(i, o)| o.bounded_onsets().into_iter().map(move |onset| (onset, i)))
+            .collect::<Vec<_>>();
+        onsets.sort();
+
+        TimeZoneRules {
+            tzid,
+            observances,
+            onsets,
+        }
+    }
+
+    /// Return the `TZID` of the time zone.
+    pub fn tzid(&self) -> &str {
+        &self.tzid
+    }
+
+    /// Return the observance in effect at `utc` and whether its first onset is passed.
+    fn observance(&self, utc: NaiveDateTime) -> (&Observance, bool) {
+        let known = self.onsets.partition_point(|(onset, _)| *onset <= utc);
+        let mut last = known.checked_sub(1).map(|i| self.onsets[i]);
+
+        for (i, observance) in self.observances.iter().enumerate() {
+            if let Some(onset) = observance.last_endless_onset(utc) {
+                if last.is_none_or(|(last, _)| onset > last) {
+                    last = Some((onset, i));
+                }
+            }
+        }
+
+        match last {
+            Some((_, i)) => (&self.observances[i], true),
+            // Before the first onset, so before the first observance.
+            None => (&self.observances[self.onsets[0].1], false),
+        }
+    }
+
+    /// Return the UTC offset in effect at `utc`.
+    pub fn offset_at(&self, utc: NaiveDateTime) -> FixedOffset {
+        match self.observance(utc) {
+            (observance, true) => observance.offset_to,
+            (observance, false) => observance.offset_from,
+        }
+    }
+
+    /// Return the `TZNAME` and the kind of the observance in effect at `utc`.
+    pub fn name_at(&self, utc: NaiveDateTime) -> (Option<&str>, IcalTimeZoneTransitionType) {
+        let observance = self.observance(utc).0;
+
+        (observance.name.as_deref(), observance.kind.clone())
+    }
+
+    /// Convert an UTC instant into the local time of the zone.
+    pub fn from_utc(&self, utc: NaiveDateTime) -> NaiveDateTime {
+        utc + offset(self.offset_at(utc).local_minus_utc() as i64)
+    }
+
+    /// Return the UTC instant(s) matching the `local` time.
+    pub fn resolve(&self, local: NaiveDateTime) -> Resolution {
+        // The offsets are less than a day, so a transition near `local` sits in this range.
+        let before = self.offset_at(local - Duration::days(2));
+        let after = self.offset_at(local + Duration::days(2));
+
+        let mut instants = [before, after]
+            .iter()
+            .map(|o| local - offset(o.local_minus_utc() as i64))
+            .filter(|utc| local == self.from_utc(*utc))
+            .collect::<Vec<_>>();
+
+        instants.sort();
+        instants.dedup();
+
+        match instants.as_slice() {
+            [utc] => Resolution::Single(*utc),
+            [earliest, latest] => Resolution::Ambiguous {
+                earliest: *earliest,
+                latest: *latest,
+            },
+            _ => Resolution::Gap {
+                utc: local - offset(before.local_minus_utc() as i64),
+            },
+        }
+    }
+
+    /// Convert the `local` time into UTC, following RFC 5545 for the gaps and overlaps.
+    pub fn to_utc(&self, local: NaiveDateTime) -> chrono::DateTime<Utc> {
+        chrono::DateTime::from_naive_utc_and_offset(self.resolve(local).utc(), Utc)
+    }
+}
+
+impl IcalTimeZone {
+    /// Return the `TZID` of the time zone.
+    pub fn tzid(&self) -> Option<&str> {
+        self.get_property("TZID").and_then(|p| p.value.as_deref())
+    }
+
+    /// Decode the `STANDARD` and `DAYLIGHT` sub-components of the time zone.
+    pub fn rules(&self) -> Result<TimeZoneRules, TimeZoneError> {
+        let tzid = self
+            .tzid()
+            .ok_or(TimeZoneError::MissingProperty("TZID"))?
+            .to_string();
+
+        let observances = self
+            .transitions
+            .iter()
+            .map(Observance::from_transition)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if observances.is_empty() {
+            return Err(TimeZoneError::NoObservance(tzid));
+        }
+
+        Ok(TimeZoneRules::new(tzid, observances))
+    }
+}
+
+impl IcalCalendar {
+    /// Return the `VTIMEZONE` with the given `TZID`.
+    pub fn timezone(&self, tzid: &str) -> Option<&IcalTimeZone> {
+        self.timezones.iter().find(|tz| tz.tzid() == Some(tzid))
+    }
+
+    /// Return the UTC instant(s) matching the `local` time of the zone `tzid`.
+    ///
+    /// The `VTIMEZONE` of the calendar is used first. With the `tzdb` feature, the IANA
+    /// database is used as a fallback. The `VTIMEZONE` is decoded at each call: use
+    /// `TimeZones` to convert many date-times.
+    pub fn resolve(&self, local: NaiveDateTime, tzid: &str) -> Result<Resolution, TimeZoneError> {
+        TimeZones::new(self).resolve(local, tzid)
+    }
+
+    /// Convert a date-time into UTC, using the time zone matching its `TZID`.
+    ///
+    /// The `VTIMEZONE` is decoded at each call: use `TimeZones` to convert many date-times.
+    pub fn to_utc(&self, date_time: &DateTime) -> Result<chrono::DateTime<Utc>, TimeZoneError> {
+        TimeZones::new(self).to_utc(date_time)
+    }
+}
+
+/// The time zones of a calendar, whose `VTIMEZONE` components are decoded once and kept to
+/// convert many date-times.
+#[derive(Debug)]
+pub struct TimeZones<'c> {
+    calendar: &'c IcalCalendar,
+    rules: RefCell<HashMap<String, Rc<TimeZoneRules>>>,
+}
+
+impl<'c> TimeZones<'c> {
+    pub fn new(calendar: &'c IcalCalendar) -> TimeZones<'c> {
+        TimeZones {
+            calendar,
+            rules: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Return the calendar holding the time zones.
+    pub fn calendar(&self) -> &'c IcalCalendar {
+        self.calendar
+    }
+
+    /// Return the rules of the `VTIMEZONE` with the given `TZID`, decoded on first use, or
+    /// `None` if the calendar has no such component.
+    pub fn rules(&self, tzid: &str) -> Result<Option<Rc<TimeZoneRules>>, TimeZoneError> {
+        if let Some(rules) = self.rules.borrow().get(tzid) {
+            return Ok(Some(Rc::clone(rules)));
+        }
+
+        let timezone = match self.calendar.timezone(tzid) {
+            Some(timezone) => timezone,
+            None => return Ok(None),
+        };
+        let rules = Rc::new(timezone.rules()?);
+        self.rules
+            .borrow_mut()
+            .insert(tzid.to_string(), Rc::clone(&rules));
+
+        Ok(Some(rules))
+    }
+
+    /// Return the UTC instant(s) matching the `local` time of the zone `tzid`.
+    ///
+    /// The `VTIMEZONE` of the calendar is used first. With the `tzdb` feature, the IANA
+    /// database is used as a fallback.
+    pub fn resolve(&self, local: NaiveDateTime, tzid: &str) -> Result<Resolution, TimeZoneError> {
+        if let Some(rules) = self.rules(tzid)? {
+            return Ok(rules.resolve(local));
+        }
+
+        #[cfg(feature = "tzdb")]
+        {
+            if let Some(tz) = tzdb::find(tzid) {
+                return Ok(tzdb::resolve(tz, local));
+            }
+        }
+
+        Err(TimeZoneError::UnknownTimeZone(tzid.to_string()))
+    }
+
+    /// Convert an UTC instant into the local time of the zone `tzid`.
+    ///
+    /// The `VTIMEZONE` of the calendar is used first. With the `tzdb` feature, the IANA
+    /// database is used as a fallback.
+    pub fn from_utc(&self, utc: NaiveDateTime, tzid: &str) -> Result<NaiveDateTime, TimeZoneError> {
+        if let Some(rules) = self.rules(tzid)? {
+            return Ok(rules.from_utc(utc));
+        }
+
+        #[cfg(feature = "tzdb")]
+        {
+            if let Some(tz) = tzdb::find(tzid) {
+                return Ok(tzdb::from_utc(tz, utc));
+            }
+        }
+
+        Err(TimeZoneError::UnknownTimeZone(tzid.to_string()))
+    }
+
+    /// Convert a date-time into UTC, using the time zone matching its `TZID`.
+    pub fn to_utc(&self, date_time: &DateTime) -> Result<chrono::DateTime<Utc>, TimeZoneError> {
+        let utc = match date_time {
+            DateTime::Floating(_) => return Err(TimeZoneError::Floating),
+            DateTime::Utc(utc) => *utc,
+            DateTime::Zoned { date_time, tzid } => self.resolve(*date_time, tzid)?.utc(),
+        };
+
+        Ok(chrono::DateTime::from_naive_utc_and_offset(utc, Utc))
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+    use crate::parser::ical::IcalParser;
+    use std::io::BufReader;
+
+    const NEW_YORK: &str = "BEGIN:VCALENDAR
+BEGIN:VTIMEZONE
+TZID:America/New_York
+BEGIN:STANDARD
+DTSTART:19671029T020000
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10;UNTIL=20061029T060000Z
+TZOFFSETFROM:-0400
+TZOFFSETTO:-0500
+TZNAME:EST
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:19870405T020000
+RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=4;UNTIL=20060402T070000Z
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:DAYLIGHT
+DTSTART:20070311T020000
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0400
+TZNAME:EDT
+END:DAYLIGHT
+BEGIN:STANDARD
+DTSTART:20071104T020000
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU
+TZOFFSETFROM:-0400
+TZOFFSETTO:-0500
+TZNAME:EST
+END:STANDARD
+END:VTIMEZONE
+END:VCALENDAR
+";
+
+    fn calendar() -> IcalCalendar {
+        IcalParser::new(BufReader::new(NEW_YORK.as_bytes()))
+            .next()
+            .unwrap()
+            .unwrap()
+    }
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn pick_the_observance_in_effect() {
+        let rules = calendar().timezones[0].rules().unwrap();
+
+        assert_eq!(rules.tzid(), "America/New_York");
+        assert_eq!(
+            rules.offset_at(at(2006, 4, 15, 12, 0)).local_minus_utc(),
+            -4 * 3600
+        );
+        // The 2007 rules are not yet in effect.
+        assert_eq!(
+            rules.offset_at(at(2006, 11, 1, 12, 0)).local_minus_utc(),
+            -5 * 3600
+        );
+        assert_eq!(
+            rules.offset_at(at(2007, 3, 20, 12, 0)).local_minus_utc(),
+            -4 * 3600
+        );
+        assert_eq!(
+            rules.offset_at(at(2024, 1, 1, 0, 0)).local_minus_utc(),
+            -5 * 3600
+        );
+        // Before the first onset.
+        assert_eq!(
+            rules.offset_at(at(1900, 1, 1, 0, 0)).local_minus_utc(),
+            -4 * 3600
+        );
+
+        assert_eq!(rules.name_at(at(2024, 7, 1, 0, 0)).0, Some("EDT"));
+        assert_eq!(rules.from_utc(at(2024, 7, 1, 16, 0)), at(2024, 7, 1, 12, 0));
+    }
+
+    #[test]
+    fn resolve_gaps_and_overlaps() {
+        let rules = calendar().timezones[0].rules().unwrap();
+
+        assert_eq!(
+            rules.resolve(at(2007, 6, 1, 9, 0)),
+            Resolution::Single(at(2007, 6, 1, 13, 0))
+        );
+
+        // RFC 5545: "TZID=America/New_York:20071104T013000 indicates November 4, 2007 at
+        // 1:30 A.M. EDT (UTC-04:00)".
+        assert_eq!(
+            rules.resolve(at(2007, 11, 4, 1, 30)),
+            Resolution::Ambiguous {
+                earliest: at(2007, 11, 4, 5, 30),
+                latest: at(2007, 11, 4, 6, 30),
+            }
+        );
+
+        // RFC 5545: "TZID=America/New_York:20070311T023000 indicates March 11, 2007 at 3:30
+        // A.M. EDT (UTC-04:00)".
+        assert_eq!(
+            rules.resolve(at(2007, 3, 11, 2, 30)),
+            Resolution::Gap {
+                utc: at(2007, 3, 11, 7, 30)
+            }
+        );
+        assert_eq!(
+            rules.from_utc(at(2007, 3, 11, 7, 30)),
+            at(2007, 3, 11, 3, 30)
+        );
+    }
+
+    #[test]
+    fn convert_date_times_to_utc() {
+        let calendar = calendar();
+
+        let zoned = DateTime::Zoned {
+            date_time: at(2023, 1, 16, 9, 30),
+            tzid: "America/New_York".to_string(),
+        };
+        assert_eq!(
+            calendar.to_utc(&zoned).unwrap().naive_utc(),
+            at(2023, 1, 16, 14, 30)
+        );
+
+        let unknown = DateTime::Zoned {
+            date_time: at(2023, 1, 16, 9, 30),
+            tzid: "Middle Earth/Shire".to_string(),
+        };
+        assert!(matches!(
+            calendar.to_utc(&unknown),
+            Err(TimeZoneError::UnknownTimeZone(_))
+        ));
+        assert!(matches!(
+            calendar.to_utc(&DateTime::Floating(at(2023, 1, 16, 9, 30))),
+            Err(TimeZoneError::Floating)
+        ));
+    }
+
+    #[test]
+    fn decode_the_time_zones_once() {
+        let calendar = calendar();
+        let zones = TimeZones::new(&calendar);
+
+        let zoned = DateTime::Zoned {
+            date_time: at(2023, 7, 16, 9, 30),
+            tzid: "America/New_York".to_string(),
+        };
+        assert_eq!(
+            zones.to_utc(&zoned).unwrap().naive_utc(),
+            at(2023, 7, 16, 13, 30)
+        );
+
+        let rules = zones.rules("America/New_York").unwrap().unwrap();
+        assert!(Rc::ptr_eq(
+            &rules,
+            &zones.rules("America/New_York").unwrap().unwrap()
+        ));
+        assert!(zones.rules("Middle Earth/Shire").unwrap().is_none());
+    }
+    #[test]
+    fn reject_the_rules_without_any_onset() {
+        let content = NEW_YORK.replace(
+            "RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10;UNTIL=20061029T060000Z",
+            "RRULE:FREQ=HOURLY;COUNT=2;BYMONTH=2;BYMONTHDAY=30",
+        );
+        let calendar = IcalParser::new(BufReader::new(content.as_bytes()))
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert!(calendar.timezones[0].rules().is_err());
+        let zoned = DateTime::Zoned {
+            date_time: at(2023, 1, 16, 9, 30),
+            tzid: "America/New_York".to_string(),
+        };
+        assert!(calendar.to_utc(&zoned).is_err());
+    }
+}
@@ -0,0 +1,529 @@
+//! Resolve the `TZID`s without a `VTIMEZONE` with the bundled IANA time zone database.
+//!
+//! A lot of producers reference an IANA (`Europe/Berlin`) or a Windows
+//! (`W. Europe Standard Time`) zone without shipping its definition. The offsets are then taken
+//! from the [chrono-tz](https://docs.rs/chrono-tz) copy of the database.
+//!
+//! The database can also be turned into a `VTIMEZONE` with `vtimezone`, to be shipped along a
+//! generated calendar.
+
+// Internal mods
+use super::windows::windows_to_iana;
+use super::{Resolution, TimeZoneError};
+use crate::parser::ical::component::{
+    IcalCalendar, IcalTimeZone, IcalTimeZoneTransition, IcalTimeZoneTransitionType,
+};
+use crate::property::Property;
+use crate::recurrence::rule::days_in_month;
+use crate::value::recur::{Frequency, Recur, RecurEnd, Until, WeekdayNum};
+use crate::value::{DateTime, Format, PeriodEnd, Value};
+use chrono::{Datelike, Duration, FixedOffset, LocalResult, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use std::collections::BTreeMap;
+
+/// Return the IANA zone matching `tzid`.
+///
+/// Besides the IANA names, the Windows names and the "globally unique" `TZID`s prefixed by a
+/// solidus (`/mozilla.org/20050126_1/Europe/Berlin`) are recognized.
+pub fn find(tzid: &str) -> Option<Tz> {
+    let tzid = tzid.trim();
+
+    if let Ok(tz) = tzid.parse::<Tz>() {
+        return Some(tz);
+    }
+
+    if let Some(iana) = windows_to_iana(tzid) {
+        return iana.parse().ok();
+    }
+
+    if tzid.starts_with('/') {
+        return tzid
+            .match_indices('/')
+            .filter_map(|(i, _)| tzid[i + 1..].parse::<Tz>().ok())
+            .next();
+    }
+
+    None
+}
+
+/// Return the UTC instant(s) matching the `local` time of `tz`.
+pub fn resolve(tz: Tz, local: NaiveDateTime) -> Resolution {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(date_time) => Resolution::Single(date_time.naive_utc()),
+        LocalResult::Ambiguous(first, second) => Resolution::Ambiguous {
+            earliest: first.naive_utc().min(second.naive_utc()),
+            latest: first.naive_utc().max(second.naive_utc()),
+        },
+        LocalResult::None => {
+            let before = tz
+                .offset_from_utc_datetime(&(local - Duration::days(1)))
+                .fix()
+                .local_minus_utc();
+
+            Resolution::Gap {
+                utc: local - Duration::seconds(before as i64),
+            }
+        }
+    }
+}
+
+/// Convert an UTC instant into the local time of `tz`.
+pub fn from_utc(tz: Tz, utc: NaiveDateTime) -> NaiveDateTime {
+    tz.from_utc_datetime(&utc).naive_local()
+}
+
+/// The offset, daylight saving flag and abbreviation in use at an instant.
+#[derive(Debug, Clone, PartialEq)]
+struct State {
+    offset: FixedOffset,
+    daylight: bool,
+    name: Option<String>,
+}
+
+impl State {
+    fn at(tz: Tz, utc: NaiveDateTime) -> State {
+        let offset = tz.offset_from_utc_datetime(&utc);
+
+        State {
+            offset: offset.fix(),
+            daylight: !offset.dst_offset().is_zero(),
+            name: offset.abbreviation().map(String::from),
+        }
+    }
+}
+
+/// A change of `State`.
+#[derive(Debug, Clone)]
+struct Transition {
+    utc: NaiveDateTime,
+    from: FixedOffset,
+    to: State,
+}
+
+impl Transition {
+    /// Return the onset in the local time in use before it, as a `DTSTART`.
+    fn local(&self) -> NaiveDateTime {
+        self.utc + Duration::seconds(self.from.local_minus_utc() as i64)
+    }
+
+    /// Return whether `other` is the same kind of transition, ignoring the date.
+    fn same_kind(&self, other: &Transition) -> bool {
+        let (local, other_local) = (self.local(), other.local());
+
+        self.from == other.from
+            && self.to == other.to
+            && local.month() == other_local.month()
+            && local.time() == other_local.time()
+    }
+
+    /// Return the `BYDAY` rule parts matching the day of the onset, the last one first.
+    fn by_day(&self) -> Vec<WeekdayNum> {
+        let local = self.local();
+        let weekday = local.weekday();
+        let mut by_day = Vec::new();
+
+        if local.day() + 7 > days_in_month(local.year(), local.month()) {
+            by_day.push(WeekdayNum::nth(-1, weekday));
+        }
+        by_day.push(WeekdayNum::nth((local.day() as i32 - 1) / 7 + 1, weekday));
+
+        by_day
+    }
+}
+
+/// Return the transitions of `tz` in `[start, end[`.
+fn transitions(tz: Tz, start: NaiveDateTime, end: NaiveDateTime) -> Vec<Transition> {
+    let mut transitions = Vec::new();
+    let mut at = start;
+    let mut state = State::at(tz, start);
+
+    while at < end {
+        let next = (at + Duration::days(1)).min(end);
+        let next_state = State::at(tz, next);
+
+        if next_state != state {
+            // Narrow down the instant of the change to the second.
+            let (mut low, mut high) = (at, next);
+            while (high - low).num_seconds() > 1 {
+                let middle = low + Duration::seconds((high - low).num_seconds() / 2);
+                if State::at(tz, middle) == state {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+
+            transitions.push(Transition {
+                utc: high,
+                from: state.offset,
+                to: next_state.clone(),
+            });
+            state = next_state;
+        }
+
+        at = next;
+    }
+
+    transitions
+}
+
+/// Consecutive yearly transitions described by a single `RRULE`.
+struct Run {
+    first: Transition,
+    last: Transition,
+    by_day: Vec<WeekdayNum>,
+    open: bool,
+}
+
+impl Run {
+    /// Return whether `transition` follows the run a year later.
+    fn extends_with(&self, transition: &Transition) -> bool {
+        self.last.same_kind(transition)
+            && transition.local().year() == self.last.local().year() + 1
+            && transition.by_day().iter().any(|d| self.by_day.contains(d))
+    }
+
+    fn push(&mut self, transition: Transition) {
+        let by_day = transition.by_day();
+        self.by_day.retain(|d| by_day.contains(d));
+        self.last = transition;
+    }
+
+    fn into_transition(self) -> IcalTimeZoneTransition {
+        let kind = if self.first.to.daylight {
+            IcalTimeZoneTransitionType::DAYLIGHT
+        } else {
+            IcalTimeZoneTransitionType::STANDARD
+        };
+        let mut transition = IcalTimeZoneTransition::new(kind);
+
+        let mut add = |name: &str, value: String| {
+            let mut property = Property::new();
+            property.name = name.to_string();
+            property.value = Some(value);
+            transition.properties.push(property);
+        };
+
+        add(
+            "DTSTART",
+            self.first.local().format("%Y%m%dT%H%M%S").to_string(),
+        );
+        if self.open || self.first.utc != self.last.utc {
+            let mut recur = Recur::new(Frequency::Yearly);
+            recur.by_month = vec![self.first.local().month()];
+            recur.by_day = vec![self.by_day[0]];
+            if !self.open {
+                recur.end = Some(RecurEnd::Until(Until::DateTime(DateTime::Utc(
+                    self.last.utc,
+                ))));
+            }
+            add("RRULE", recur.to_string());
+        }
+        add(
+            "TZOFFSETFROM",
+            Value::UtcOffset(self.first.from).to_string(),
+        );
+        add(
+            "TZOFFSETTO",
+            Value::UtcOffset(self.first.to.offset).to_string(),
+        );
+        if let Some(name) = &self.first.to.name {
+            add("TZNAME", name.clone());
+        }
+
+        transition
+    }
+}
+
+/// Build the `VTIMEZONE` of the IANA or Windows zone `tzid`, valid over the `[start, end]` UTC
+/// range.
+///
+/// The transitions repeating every year are grouped into `RRULE`s. When the rules in use at
+/// `end` still apply the following year, they are left open, so the component stays correct
+/// after `end` until the zone changes its rules.
+pub fn vtimezone(
+    tzid: &str,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Result<IcalTimeZone, TimeZoneError> {
+    let tz = find(tzid).ok_or_else(|| TimeZoneError::UnknownTimeZone(tzid.to_string()))?;
+
+    // Start with the last transition before `start`, or the state in use at `start`.
+    let mut onsets = transitions(tz, start - Duration::days(366), end);
+    let first = onsets.iter().rposition(|t| t.utc <= start);
+    match first {
+        Some(first) => {
+            onsets.drain(..first);
+        }
+        None => {
+            let state = State::at(tz, start);
+            onsets.insert(
+                0,
+                Transition {
+                    utc: start,
+                    from: state.offset,
+                    to: state,
+                },
+            );
+        }
+    }
+
+    let mut runs: Vec<Run> = Vec::new();
+    for onset in onsets {
+        match runs.iter_mut().rev().find(|run| run.last.same_kind(&onset)) {
+            Some(run) if run.extends_with(&onset) => run.push(onset),
+            _ => runs.push(Run {
+                by_day: onset.by_day(),
+                first: onset.clone(),
+                last: onset,
+                open: false,
+            }),
+        }
+    }
+
+    // Leave open the runs still followed the year after `end`.
+    let following = transitions(tz, end, end + Duration::days(366));
+    for run in &mut runs {
+        let next = following.iter().find(|t| run.last.same_kind(t));
+
+        run.open = next.is_some_and(|next| run.extends_with(next));
+    }
+    // Only the latest run of each kind can stay open.
+    for i in 0..runs.len() {
+        if runs[i + 1..]
+            .iter()
+            .any(|later| later.first.same_kind(&runs[i].first))
+        {
+            runs[i].open = false;
+        }
+    }
+
+    let mut timezone = IcalTimeZone::new();
+
+    let mut property = Property::new();
+    property.name = "TZID".to_string();
+    property.value = Some(tzid.to_string());
+    timezone.properties.push(property);
+
+    if tz.name() != tzid {
+        let mut property = Property::new();
+        property.name = "X-LIC-LOCATION".to_string();
+        property.value = Some(tz.name().to_string());
+        timezone.properties.push(property);
+    }
+
+    timezone
+        .transitions
+        .extend(runs.into_iter().map(Run::into_transition));
+
+    Ok(timezone)
+}
+
+impl IcalCalendar {
+    /// Add a `VTIMEZONE` generated from the IANA database for each `TZID` referenced by the
+    /// calendar without a matching `VTIMEZONE`.
+    ///
+    /// Each generated component covers the date-times referencing it. The `TZID`s unknown to
+    /// the database are left unresolved.
+    pub fn add_missing_timezones(&mut self) {
+        let mut ranges: BTreeMap<String, (NaiveDateTime, NaiveDateTime)> = BTreeMap::new();
+
+        let properties = self
+            .events
+            .iter()
+            .flat_map(|e| {
+                e.properties
+                    .iter()
+                    .chain(e.alarms.iter().flat_map(|a| &a.properties))
+            })
+            .chain(self.todos.iter().flat_map(|t| {
+                t.properties
+                    .iter()
+                    .chain(t.alarms.iter().flat_map(|a| &a.properties))
+            }))
+            .chain(self.journals.iter().flat_map(|j| &j.properties))
+            .chain(self.free_busys.iter().flat_map(|f| &f.properties))
+            .chain(self.availabilities.iter().flat_map(|a| {
+                a.properties
+                    .iter()
+                    .chain(a.available.iter().flat_map(|a| &a.properties))
+            }));
+
+        for property in properties {
+            let values = match property.typed_values(Format::Ical) {
+                Ok(values) => values,
+                Err(_) => continue,
+            };
+
+            for value in values {
+                let (date_time, end) = match value {
+                    Value::DateTime(date_time) => (date_time, None),
+                    Value::Period(period) => match period.end {
+                        PeriodEnd::DateTime(end) => (period.start, Some(end.naive())),
+                        PeriodEnd::Duration(duration) => {
+                            let end = period.start.naive() + duration.to_chrono();
+                            (period.start, Some(end))
+                        }
+                    },
+                    _ => continue,
+                };
+
+                if let DateTime::Zoned { date_time, tzid } = date_time {
+                    let end = end.unwrap_or(date_time);
+                    let range = ranges.entry(tzid).or_insert((date_time, end));
+                    *range = (range.0.min(date_time), range.1.max(end));
+                }
+            }
+        }
+
+        for (tzid, (start, end)) in ranges {
+            if self.timezone(&tzid).is_some() {
+                continue;
+            }
+
+            // The range is local, widen it to cover any UTC offset.
+            if let Ok(timezone) =
+                vtimezone(&tzid, start - Duration::days(1), end + Duration::days(1))
+            {
+                self.timezones.push(timezone);
+            }
+        }
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn find_zones() {
+        assert_eq!(find("Europe/Berlin"), Some(Tz::Europe__Berlin));
+        assert_eq!(find("W. Europe Standard Time"), Some(Tz::Europe__Berlin));
+        assert_eq!(
+            find("/mozilla.org/20050126_1/America/New_York"),
+            Some(Tz::America__New_York)
+        );
+        assert_eq!(find("Middle Earth/Shire"), None);
+    }
+
+    #[test]
+    fn resolve_like_rfc5545() {
+        let tz = Tz::America__New_York;
+
+        assert_eq!(
+            resolve(tz, at(2007, 6, 1, 9, 0)),
+            Resolution::Single(at(2007, 6, 1, 13, 0))
+        );
+        assert_eq!(
+            resolve(tz, at(2007, 11, 4, 1, 30)),
+            Resolution::Ambiguous {
+                earliest: at(2007, 11, 4, 5, 30),
+                latest: at(2007, 11, 4, 6, 30),
+            }
+        );
+        assert_eq!(
+            resolve(tz, at(2007, 3, 11, 2, 30)),
+            Resolution::Gap {
+                utc: at(2007, 3, 11, 7, 30)
+            }
+        );
+    }
+
+    /// Check the offsets of the generated `VTIMEZONE` against the database, every 13 hours, drifting along the day.
+    fn assert_same_offsets(tzid: &str, start: NaiveDateTime, end: NaiveDateTime) {
+        let tz = find(tzid).unwrap();
+        let rules = vtimezone(tzid, start, end).unwrap().rules().unwrap();
+
+        let mut at = start;
+        while at < end {
+            assert_eq!(
+                rules.offset_at(at),
+                tz.offset_from_utc_datetime(&at).fix(),
+                "{} at {}",
+                tzid,
+                at
+            );
+            at += Duration::hours(13);
+        }
+    }
+
+    #[test]
+    fn generate_vtimezone() {
+        let timezone = vtimezone(
+            "W. Europe Standard Time",
+            at(2020, 1, 1, 0, 0),
+            at(2025, 1, 1, 0, 0),
+        )
+        .unwrap();
+
+        let generated = timezone
+            .transitions
+            .iter()
+            .map(|t| {
+                t.properties
+                    .iter()
+                    .map(|p| format!("{}:{}", p.name, p.value.as_ref().unwrap()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(timezone.tzid(), Some("W. Europe Standard Time"));
+        assert_eq!(
+            timezone.properties[1].value.as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            generated,
+            vec![
+                "DTSTART:20191027T030000\nRRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\n\
+                 TZOFFSETFROM:+0200\nTZOFFSETTO:+0100\nTZNAME:CET",
+                "DTSTART:20200329T020000\nRRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3\n\
+                 TZOFFSETFROM:+0100\nTZOFFSETTO:+0200\nTZNAME:CEST",
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_matching_offsets() {
+        let (start, end) = (at(1995, 1, 1, 0, 0), at(2030, 1, 1, 0, 0));
+
+        for tzid in &[
+            "Europe/Berlin",
+            "America/New_York",
+            "America/Sao_Paulo",
+            "Australia/Lord_Howe",
+            "Africa/Casablanca",
+            "Asia/Kolkata",
+            "Etc/UTC",
+        ] {
+            assert_same_offsets(tzid, start, end);
+        }
+
+        // The open rules still apply after the range.
+        let tz = find("Europe/Berlin").unwrap();
+        let rules = vtimezone("Europe/Berlin", start, at(2000, 1, 1, 0, 0))
+            .unwrap()
+            .rules()
+            .unwrap();
+        assert_eq!(
+            rules.offset_at(at(2024, 7, 1, 0, 0)),
+            tz.offset_from_utc_datetime(&at(2024, 7, 1, 0, 0)).fix()
+        );
+    }
+
+    #[test]
+    fn know_every_windows_zone() {
+        for (windows, _) in super::super::windows::WINDOWS_ZONES {
+            assert!(find(windows).is_some(), "{}", windows);
+        }
+    }
+}
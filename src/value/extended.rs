@@ -0,0 +1,186 @@
+//! Convert the dates, times and rules between their basic format, used by iCalendar and vCard
+//! (`20240102T120000`), and the extended one used by their JSON and XML representations
+//! (`2024-01-02T12:00:00`).
+//!
+//! As the type of a value is given by these representations, the `VALUE` parameter is only
+//! written back when needed.
+
+// Internal mods
+use super::partial::{vcard_date_time, vcard_zone};
+use super::{
+    decode, parse_date, parse_date_time, parse_duration, parse_time, parse_utc_offset, DateTime,
+    PeriodEnd, Value, ValueType,
+};
+use crate::property::{Format, Property};
+
+/// The iCalendar properties whose `VALUE` parameter is required, even for their default type
+/// ([RFC7986 section 5](https://tools.ietf.org/html/rfc7986#section-5)).
+const EXPLICIT_VALUE_TYPES: &[&str] = &["REFRESH-INTERVAL", "IMAGE", "CONFERENCE"];
+
+/// Add the `VALUE` parameter to `property` when `value_type` isn't its default type or when
+/// it's required. As its position among the parameters isn't known, it comes last.
+pub(crate) fn add_value_param(property: &mut Property, value_type: &ValueType, format: Format) {
+    let explicit = format == Format::Ical && EXPLICIT_VALUE_TYPES.contains(&property.name.as_str());
+    if !explicit && property.value_type(format) == *value_type {
+        return;
+    }
+
+    // The vCard types are usually written in lowercase, the iCalendar ones in uppercase.
+    let type_name = match format {
+        Format::Ical => value_type.to_string(),
+        Format::Vcard => value_type.to_string().to_lowercase(),
+    };
+    property
+        .params
+        .get_or_insert_with(Vec::new)
+        .push((String::from("VALUE"), vec![type_name]));
+}
+
+fn date_time_to_extended(raw: &str) -> Option<String> {
+    let date_time = parse_date_time(raw, None)?;
+    let utc = if let DateTime::Utc(_) = date_time {
+        "Z"
+    } else {
+        ""
+    };
+
+    Some(format!(
+        "{}{}",
+        date_time.naive().format("%Y-%m-%dT%H:%M:%S"),
+        utc
+    ))
+}
+
+fn date_time_from_extended(text: &str) -> Option<String> {
+    parse_date_time(text, None).map(|date_time| date_time.to_string())
+}
+
+/// Convert a `DATE`, `DATE-TIME`, `TIME`, `DATE-AND-OR-TIME`, `TIMESTAMP`, `UTC-OFFSET` or
+/// `PERIOD` value to its extended format. The duration ending a period is kept as is.
+pub(crate) fn to_extended(raw: &str, value_type: &ValueType, format: Format) -> Option<String> {
+    match value_type {
+        ValueType::Date
+        | ValueType::DateAndOrTime
+        | ValueType::DateTime
+        | ValueType::Time
+        | ValueType::Timestamp
+            if format == Format::Vcard =>
+        {
+            vcard_date_time(raw, value_type, true)
+        }
+        ValueType::UtcOffset => {
+            parse_utc_offset(raw)?;
+            vcard_zone(raw, true)
+        }
+        ValueType::Period => {
+            let (start, end) = raw.split_once('/')?;
+            let end = match decode(raw, value_type, None)? {
+                Value::Period(period) => match period.end {
+                    PeriodEnd::DateTime(_) => date_time_to_extended(end)?,
+                    PeriodEnd::Duration(_) => end.to_string(),
+                },
+                _ => return None,
+            };
+
+            Some(format!("{}/{}", date_time_to_extended(start)?, end))
+        }
+        ValueType::Date | ValueType::DateTime | ValueType::Time => {
+            match decode(raw, value_type, None)? {
+                Value::Date(date) => Some(date.format("%Y-%m-%d").to_string()),
+                Value::DateTime(_) => date_time_to_extended(raw),
+                Value::Time { time, utc } => Some(format!(
+                    "{}{}",
+                    time.format("%H:%M:%S"),
+                    if utc { "Z" } else { "" }
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Convert a value of the types handled by `to_extended` back to its basic format.
+pub(crate) fn from_extended(text: &str, value_type: &ValueType, format: Format) -> Option<String> {
+    match value_type {
+        ValueType::Date
+        | ValueType::DateAndOrTime
+        | ValueType::DateTime
+        | ValueType::Time
+        | ValueType::Timestamp
+            if format == Format::Vcard =>
+        {
+            vcard_date_time(text, value_type, false)
+        }
+        ValueType::Date => Some(Value::Date(parse_date(text)?).to_string()),
+        ValueType::DateTime => date_time_from_extended(text),
+        ValueType::Time => {
+            let (time, utc) = parse_time(text)?;
+            Some(Value::Time { time, utc }.to_string())
+        }
+        ValueType::UtcOffset => {
+            parse_utc_offset(text)?;
+            vcard_zone(text, false)
+        }
+        ValueType::Period => {
+            let (start, end) = text.split_once('/')?;
+            let end = if end.starts_with(['P', '+', '-']) {
+                parse_duration(end)?;
+                end.to_string()
+            } else {
+                date_time_from_extended(end)?
+            };
+
+            Some(format!("{}/{}", date_time_from_extended(start)?, end))
+        }
+        _ => None,
+    }
+}
+
+/// Split a `RECUR` value into its rule parts, in uppercase, and their values, the `UNTIL` date
+/// or date-time being in its extended format.
+pub(crate) fn recur_to_parts(raw: &str) -> Option<Vec<(String, Vec<String>)>> {
+    raw.split(';')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (name, list) = part.split_once('=')?;
+            let name = name.to_uppercase();
+
+            let values = list
+                .split(',')
+                .map(|item| match name.as_str() {
+                    "UNTIL" if item.contains('T') => date_time_to_extended(item),
+                    "UNTIL" => to_extended(item, &ValueType::Date, Format::Ical),
+                    _ => Some(item.to_string()),
+                })
+                .collect::<Option<Vec<String>>>()?;
+
+            Some((name, values))
+        })
+        .collect()
+}
+
+/// Join the rule parts of a `RECUR` value, checking that the rule is valid.
+pub(crate) fn recur_from_parts(parts: &[(String, Vec<String>)]) -> Option<String> {
+    let parts = parts
+        .iter()
+        .map(|(name, values)| {
+            let name = name.to_uppercase();
+            let values = values
+                .iter()
+                .map(|item| match name.as_str() {
+                    "UNTIL" if item.contains('T') => date_time_from_extended(item),
+                    "UNTIL" => from_extended(item, &ValueType::Date, Format::Ical),
+                    _ => Some(item.clone()),
+                })
+                .collect::<Option<Vec<String>>>()?;
+
+            Some(format!("{}={}", name, values.join(",")))
+        })
+        .collect::<Option<Vec<String>>>()?;
+
+    let rule = parts.join(";");
+    rule.parse::<super::recur::Recur>().ok()?;
+
+    Some(rule)
+}
@@ -0,0 +1,1021 @@
+//! Decode the value of a `Property` into a typed `Value`.
+//!
+//! The type of a value is given by the `VALUE` parameter of the property or, if absent, by the
+//! default type of the property defined in [RFC5545](https://tools.ietf.org/html/rfc5545) for
+//! the iCalendar format or in [RFC6350](https://tools.ietf.org/html/rfc6350) for the vCard
+//! format. The unknown and `X-` properties default to `TEXT`.
+//!
+//! The `TEXT` values are unescaped. The lists of `CATEGORIES`, `RESOURCES` and `NICKNAME` are
+//! split on the unescaped commas, the other `TEXT` values are kept whole: use
+//! `Property::structured_text` for the structured ones such as `N` or `ADR`.
+//!
+//! The vCard dates and times which are reduced (`1985`) or truncated (`--0412`) are decoded
+//! as a `PartialDateTime`.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["value"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use std::io::BufReader;
+//! use std::fs::File;
+//! use ical::value::Format;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/ical_input.ics")
+//!     .unwrap());
+//!
+//! let reader = ical::PropertyParser::from_reader(buf);
+//!
+//! for property in reader {
+//!     println!("{:?}", property.unwrap().typed_values(Format::Ical));
+//! }
+//! ```
+
+#[cfg(any(feature = "jcal", feature = "jcard", feature = "xcal"))]
+pub(crate) mod extended;
+mod partial;
+pub mod recur;
+
+// Sys mods
+use std::fmt;
+
+// Internal mods
+use self::recur::{Recur, RecurError};
+pub use crate::property::Format;
+use crate::property::{
+    decode_base64, escape_text, split_unescaped, unescape_text, Property, ValueEncoding,
+};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc};
+
+/// Type of a property value, as found in the `VALUE` parameter.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ValueType {
+    Binary,
+    Boolean,
+    CalAddress,
+    Date,
+    DateAndOrTime,
+    DateTime,
+    Duration,
+    Float,
+    Integer,
+    LanguageTag,
+    Period,
+    Recur,
+    Text,
+    Time,
+    Timestamp,
+    Uri,
+    UtcOffset,
+    /// An experimental or IANA type unknown to this crate.
+    Unknown(String),
+}
+
+impl ValueType {
+    /// Return the type matching the value of a `VALUE` parameter.
+    pub fn from_name(name: &str) -> ValueType {
+        match name.to_uppercase().as_str() {
+            "BINARY" => ValueType::Binary,
+            "BOOLEAN" => ValueType::Boolean,
+            "CAL-ADDRESS" => ValueType::CalAddress,
+            "DATE" => ValueType::Date,
+            "DATE-AND-OR-TIME" => ValueType::DateAndOrTime,
+            "DATE-TIME" => ValueType::DateTime,
+            "DURATION" => ValueType::Duration,
+            "FLOAT" => ValueType::Float,
+            "INTEGER" => ValueType::Integer,
+            "LANGUAGE-TAG" => ValueType::LanguageTag,
+            "PERIOD" => ValueType::Period,
+            "RECUR" => ValueType::Recur,
+            "TEXT" => ValueType::Text,
+            "TIME" => ValueType::Time,
+            "TIMESTAMP" => ValueType::Timestamp,
+            "URI" => ValueType::Uri,
+            "UTC-OFFSET" => ValueType::UtcOffset,
+            other => ValueType::Unknown(other.to_string()),
+        }
+    }
+
+    /// Return the default type of the property `name` in the given `format`.
+    pub fn default_for(name: &str, format: Format) -> ValueType {
+        let name = name.to_uppercase();
+
+        match format {
+            Format::Ical => match name.as_str() {
+                "ATTACH" | "TZURL" | "URL" | "IMAGE" | "CONFERENCE" | "SOURCE" => ValueType::Uri,
+                "GEO" => ValueType::Float,
+                "PERCENT-COMPLETE" | "PRIORITY" | "REPEAT" | "SEQUENCE" => ValueType::Integer,
+                "COMPLETED" | "DTEND" | "DUE" | "DTSTART" | "RECURRENCE-ID" | "EXDATE"
+                | "RDATE" | "CREATED" | "DTSTAMP" | "LAST-MODIFIED" => ValueType::DateTime,
+                "DURATION" | "TRIGGER" | "REFRESH-INTERVAL" => ValueType::Duration,
+                "FREEBUSY" => ValueType::Period,
+                "TZOFFSETFROM" | "TZOFFSETTO" => ValueType::UtcOffset,
+                "ATTENDEE" | "ORGANIZER" => ValueType::CalAddress,
+                "RRULE" | "EXRULE" => ValueType::Recur,
+                _ => ValueType::Text,
+            },
+            Format::Vcard => match name.as_str() {
+                "SOURCE" | "PHOTO" | "IMPP" | "GEO" | "LOGO" | "MEMBER" | "RELATED" | "SOUND"
+                | "UID" | "URL" | "KEY" | "FBURL" | "CALADRURI" | "CALURI" => ValueType::Uri,
+                "BDAY" | "ANNIVERSARY" => ValueType::DateAndOrTime,
+                "LANG" => ValueType::LanguageTag,
+                "REV" => ValueType::Timestamp,
+                _ => ValueType::Text,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ValueType::Binary => "BINARY",
+            ValueType::Boolean => "BOOLEAN",
+            ValueType::CalAddress => "CAL-ADDRESS",
+            ValueType::Date => "DATE",
+            ValueType::DateAndOrTime => "DATE-AND-OR-TIME",
+            ValueType::DateTime => "DATE-TIME",
+            ValueType::Duration => "DURATION",
+            ValueType::Float => "FLOAT",
+            ValueType::Integer => "INTEGER",
+            ValueType::LanguageTag => "LANGUAGE-TAG",
+            ValueType::Period => "PERIOD",
+            ValueType::Recur => "RECUR",
+            ValueType::Text => "TEXT",
+            ValueType::Time => "TIME",
+            ValueType::Timestamp => "TIMESTAMP",
+            ValueType::Uri => "URI",
+            ValueType::UtcOffset => "UTC-OFFSET",
+            ValueType::Unknown(name) => name,
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+fn location(line: &Option<usize>) -> String {
+    match line {
+        Some(line) => format!("Line {}: ", line),
+        None => String::new(),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ValueError {
+    #[error("{}Missing a value for {}.", location(.line), property)]
+    MissingValue {
+        line: Option<usize>,
+        property: String,
+    },
+    #[error("{}Invalid {} value {:?} for {}.", location(.line), value_type, value, property)]
+    InvalidValue {
+        line: Option<usize>,
+        property: String,
+        value_type: ValueType,
+        value: String,
+    },
+    #[error("{}Invalid RECUR value {:?} for {}: {}.", location(.line), value, property, reason)]
+    InvalidRecur {
+        line: Option<usize>,
+        property: String,
+        value: String,
+        reason: RecurError,
+    },
+}
+
+impl ValueError {
+    /// Set the line number where the value have been found.
+    pub fn at_line(mut self, number: usize) -> ValueError {
+        match &mut self {
+            ValueError::MissingValue { line, .. }
+            | ValueError::InvalidValue { line, .. }
+            | ValueError::InvalidRecur { line, .. } => *line = Some(number),
+        }
+
+        self
+    }
+}
+
+/// A `DATE-TIME` value.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum DateTime {
+    /// A local time not bound to any time zone (`19980118T230000`).
+    Floating(NaiveDateTime),
+    /// A time in UTC (`19980119T070000Z`).
+    Utc(NaiveDateTime),
+    /// A local time in the time zone given by a `TZID` parameter.
+    Zoned {
+        date_time: NaiveDateTime,
+        tzid: String,
+    },
+}
+
+impl DateTime {
+    /// Return the local (or UTC) date and time, without its time zone.
+    pub fn naive(&self) -> NaiveDateTime {
+        match self {
+            DateTime::Floating(date_time)
+            | DateTime::Utc(date_time)
+            | DateTime::Zoned { date_time, .. } => *date_time,
+        }
+    }
+
+    /// Return the `TZID` of a zoned date-time.
+    pub fn tzid(&self) -> Option<&str> {
+        match self {
+            DateTime::Zoned { tzid, .. } => Some(tzid),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.naive().format("%Y%m%dT%H%M%S"))?;
+
+        if let DateTime::Utc(_) = self {
+            write!(f, "Z")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A vCard date, time or date-time which is reduced (`1985`, `19850412T1022`) or truncated
+/// (`--0412`, `-2200`), the missing parts being `None`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct PartialDateTime {
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    pub hour: Option<u32>,
+    pub minute: Option<u32>,
+    pub second: Option<u32>,
+    /// The UTC offset of the time, `Z` being a zero offset.
+    pub offset: Option<FixedOffset>,
+}
+
+impl fmt::Display for PartialDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.year, self.month, self.day) {
+            (Some(year), None, None) => write!(f, "{:04}", year)?,
+            (Some(year), Some(month), None) => write!(f, "{:04}-{:02}", year, month)?,
+            (Some(year), Some(month), Some(day)) => write!(f, "{:04}{:02}{:02}", year, month, day)?,
+            (None, Some(month), Some(day)) => write!(f, "--{:02}{:02}", month, day)?,
+            (None, Some(month), None) => write!(f, "--{:02}", month)?,
+            (None, None, Some(day)) => write!(f, "---{:02}", day)?,
+            _ => {}
+        }
+
+        if self.hour.is_none() && self.minute.is_none() && self.second.is_none() {
+            return Ok(());
+        }
+        if self.year.is_some() || self.month.is_some() || self.day.is_some() {
+            write!(f, "T")?;
+        }
+
+        match (self.hour, self.minute, self.second) {
+            (Some(hour), None, None) => write!(f, "{:02}", hour)?,
+            (Some(hour), Some(minute), None) => write!(f, "{:02}{:02}", hour, minute)?,
+            (Some(hour), Some(minute), Some(second)) => {
+                write!(f, "{:02}{:02}{:02}", hour, minute, second)?
+            }
+            (None, Some(minute), Some(second)) => write!(f, "-{:02}{:02}", minute, second)?,
+            (None, Some(minute), None) => write!(f, "-{:02}", minute)?,
+            (None, None, Some(second)) => write!(f, "--{:02}", second)?,
+            _ => {}
+        }
+
+        match self.offset {
+            Some(offset) if offset.local_minus_utc() == 0 => write!(f, "Z"),
+            Some(offset) => write!(f, "{}", Value::UtcOffset(offset)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A `DURATION` value.
+///
+/// The weeks and days are nominal durations which can't be converted into an exact number of
+/// seconds without a time zone, so the fields are kept as found.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Duration {
+    pub negative: bool,
+    pub weeks: u32,
+    pub days: u32,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+}
+
+impl Duration {
+    /// Convert the duration into an exact duration, counting a day as 24 hours.
+    pub fn to_chrono(&self) -> chrono::Duration {
+        let seconds = ((self.weeks as i64 * 7 + self.days as i64) * 24 + self.hours as i64) * 3600
+            + self.minutes as i64 * 60
+            + self.seconds as i64;
+        let duration = chrono::Duration::seconds(seconds);
+
+        if self.negative {
+            -duration
+        } else {
+            duration
+        }
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "P")?;
+
+        if self.weeks > 0 {
+            write!(f, "{}W", self.weeks)?;
+        }
+        if self.days > 0 {
+            write!(f, "{}D", self.days)?;
+        }
+        if self.hours > 0 || self.minutes > 0 || self.seconds > 0 {
+            write!(f, "T")?;
+            if self.hours > 0 {
+                write!(f, "{}H", self.hours)?;
+            }
+            if self.minutes > 0 {
+                write!(f, "{}M", self.minutes)?;
+            }
+            if self.seconds > 0 {
+                write!(f, "{}S", self.seconds)?;
+            }
+        } else if self.weeks == 0 && self.days == 0 {
+            write!(f, "T0S")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The end of a `PERIOD` value.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum PeriodEnd {
+    DateTime(DateTime),
+    Duration(Duration),
+}
+
+/// A `PERIOD` value: a start with either an end or a duration.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Period {
+    pub start: DateTime,
+    pub end: PeriodEnd,
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.end {
+            PeriodEnd::DateTime(end) => write!(f, "{}/{}", self.start, end),
+            PeriodEnd::Duration(duration) => write!(f, "{}/{}", self.start, duration),
+        }
+    }
+}
+
+/// A decoded property value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Binary(Vec<u8>),
+    Boolean(bool),
+    CalAddress(String),
+    Date(NaiveDate),
+    DateTime(DateTime),
+    Duration(Duration),
+    Float(f64),
+    Integer(i64),
+    Period(Period),
+    Recur(Box<Recur>),
+    Text(String),
+    Time {
+        time: NaiveTime,
+        utc: bool,
+    },
+    Uri(String),
+    UtcOffset(FixedOffset),
+    /// A vCard `DATE`, `TIME` or `DATE-AND-OR-TIME` value which is reduced or truncated.
+    PartialDateTime(PartialDateTime),
+    /// A value of an unknown type, kept as found.
+    Unknown(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Binary(bytes) => write!(f, "{}", encode_base64(bytes)),
+            Value::Boolean(true) => write!(f, "TRUE"),
+            Value::Boolean(false) => write!(f, "FALSE"),
+            Value::Date(date) => write!(f, "{}", date.format("%Y%m%d")),
+            Value::DateTime(date_time) => write!(f, "{}", date_time),
+            Value::Duration(duration) => write!(f, "{}", duration),
+            Value::Float(float) => write!(f, "{}", float),
+            Value::Integer(integer) => write!(f, "{}", integer),
+            Value::Period(period) => write!(f, "{}", period),
+            Value::PartialDateTime(partial) => write!(f, "{}", partial),
+            Value::Recur(recur) => write!(f, "{}", recur),
+            Value::Time { time, utc } => {
+                write!(
+                    f,
+                    "{}{}",
+                    time.format("%H%M%S"),
+                    if *utc { "Z" } else { "" }
+                )
+            }
+            Value::UtcOffset(offset) => {
+                let seconds = offset.local_minus_utc();
+                let sign = if seconds < 0 { '-' } else { '+' };
+                let seconds = seconds.abs();
+
+                write!(f, "{}{:02}{:02}", sign, seconds / 3600, seconds / 60 % 60)?;
+                if seconds % 60 != 0 {
+                    write!(f, "{:02}", seconds % 60)?;
+                }
+
+                Ok(())
+            }
+            Value::Text(text) => write!(f, "{}", escape_text(text, Format::Ical)),
+            Value::CalAddress(text) | Value::Uri(text) | Value::Unknown(text) => {
+                write!(f, "{}", text)
+            }
+        }
+    }
+}
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode `bytes` in base64 with padding.
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |buffer, (i, b)| buffer | (*b as u32) << (16 - 8 * i));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[(buffer >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Parse a `DATE` value (`19970714`). The vCard extended format (`1997-07-14`) is accepted.
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    if value.len() == 8 && value.bytes().all(|b| b.is_ascii_digit()) {
+        NaiveDate::parse_from_str(value, "%Y%m%d").ok()
+    } else if value.len() == 10 {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+    } else {
+        None
+    }
+}
+
+/// Parse a `TIME` value (`230000` or `070000Z`). The boolean is `true` for UTC.
+///
+/// The vCard extended format (`23:00:00`) is accepted.
+pub fn parse_time(value: &str) -> Option<(NaiveTime, bool)> {
+    let (time, utc) = match value.strip_suffix('Z') {
+        Some(time) => (time, true),
+        None => (value, false),
+    };
+
+    let time = match time.len() {
+        6 if time.bytes().all(|b| b.is_ascii_digit()) => {
+            NaiveTime::parse_from_str(time, "%H%M%S").ok()?
+        }
+        8 => NaiveTime::parse_from_str(time, "%H:%M:%S").ok()?,
+        _ => return None,
+    };
+
+    Some((time, utc))
+}
+
+/// Parse a `DATE-TIME` value. The `tzid` is only used for a non-UTC value.
+pub fn parse_date_time(value: &str, tzid: Option<&str>) -> Option<DateTime> {
+    let mut elements = value.splitn(2, 'T');
+    let date = parse_date(elements.next()?)?;
+    let (time, utc) = parse_time(elements.next()?)?;
+    let date_time = date.and_time(time);
+
+    Some(match (utc, tzid) {
+        (true, _) => DateTime::Utc(date_time),
+        (false, Some(tzid)) => DateTime::Zoned {
+            date_time,
+            tzid: tzid.to_string(),
+        },
+        (false, None) => DateTime::Floating(date_time),
+    })
+}
+
+/// Parse a `DURATION` value such as `-P1DT2H` or `P2W`.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let mut duration = Duration::default();
+
+    let rest = match value.chars().next()? {
+        '-' => {
+            duration.negative = true;
+            &value[1..]
+        }
+        '+' => &value[1..],
+        _ => value,
+    };
+    let rest = rest.strip_prefix('P')?;
+
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut has_part = false;
+
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            'W' | 'D' | 'H' | 'M' | 'S' => {
+                let n = number.parse().ok()?;
+                match (c, in_time) {
+                    ('W', false) => duration.weeks = n,
+                    ('D', false) => duration.days = n,
+                    ('H', true) => duration.hours = n,
+                    ('M', true) => duration.minutes = n,
+                    ('S', true) => duration.seconds = n,
+                    _ => return None,
+                };
+                number.clear();
+                has_part = true;
+            }
+            _ => return None,
+        }
+    }
+
+    if has_part && number.is_empty() {
+        Some(duration)
+    } else {
+        None
+    }
+}
+
+/// Parse a `PERIOD` value: `start/end` or `start/duration`.
+pub fn parse_period(value: &str, tzid: Option<&str>) -> Option<Period> {
+    let mut elements = value.splitn(2, '/');
+    let start = parse_date_time(elements.next()?, tzid)?;
+    let end = elements.next()?;
+
+    let end = if end.starts_with(['P', '+', '-']) {
+        PeriodEnd::Duration(parse_duration(end)?)
+    } else {
+        PeriodEnd::DateTime(parse_date_time(end, tzid)?)
+    };
+
+    Some(Period { start, end })
+}
+
+/// Parse a `UTC-OFFSET` value (`-0500` or `+013045`). The vCard format (`-05:00`, `-05`) is
+/// accepted.
+pub fn parse_utc_offset(value: &str) -> Option<FixedOffset> {
+    let sign = match value.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits = value[1..].replace(':', "");
+
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes, seconds): (i32, i32, i32) = match digits.len() {
+        2 => (digits.parse().ok()?, 0, 0),
+        4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?, 0),
+        6 => (
+            digits[..2].parse().ok()?,
+            digits[2..4].parse().ok()?,
+            digits[4..].parse().ok()?,
+        ),
+        _ => return None,
+    };
+
+    // RFC 5545: The "-0000" value is not allowed.
+    if sign < 0 && hours == 0 && minutes == 0 && seconds == 0 {
+        return None;
+    }
+    if minutes > 59 || seconds > 59 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+/// Parse a vCard `TIMESTAMP` value (`19961022T140000Z` or `19961022T140000-05`).
+fn parse_timestamp(value: &str) -> Option<DateTime> {
+    if let Some(date_time) = parse_date_time(value, None) {
+        return Some(date_time);
+    }
+
+    let split = value.rfind(['+', '-'])?;
+    let offset = parse_utc_offset(&value[split..])?;
+    let local = parse_date_time(&value[..split], None)?.naive();
+
+    let utc = offset
+        .from_local_datetime(&local)
+        .single()?
+        .with_timezone(&Utc.fix());
+
+    Some(DateTime::Utc(utc.naive_local()))
+}
+
+fn parse_boolean(value: &str) -> Option<bool> {
+    match value.to_uppercase().as_str() {
+        "TRUE" => Some(true),
+        "FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+fn param_value<'p>(property: &'p Property, key: &str) -> Option<&'p str> {
+    property
+        .params
+        .as_ref()?
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .and_then(|(_, values)| values.first())
+        .map(String::as_str)
+}
+
+/// Decode `value` as a `value_type`. The `tzid` is used for the non-UTC date-times.
+pub fn decode(value: &str, value_type: &ValueType, tzid: Option<&str>) -> Option<Value> {
+    Some(match value_type {
+        ValueType::Binary => Value::Binary(decode_base64(value)?),
+        ValueType::Boolean => Value::Boolean(parse_boolean(value)?),
+        ValueType::CalAddress => Value::CalAddress(value.to_string()),
+        ValueType::Date => Value::Date(parse_date(value)?),
+        ValueType::DateAndOrTime => {
+            if let Some(date) = parse_date(value) {
+                Value::Date(date)
+            } else if let Some(time) = value.strip_prefix('T') {
+                let (time, utc) = parse_time(time)?;
+                Value::Time { time, utc }
+            } else {
+                Value::DateTime(parse_timestamp(value)?)
+            }
+        }
+        ValueType::DateTime => Value::DateTime(parse_date_time(value, tzid)?),
+        ValueType::Duration => Value::Duration(parse_duration(value)?),
+        ValueType::Float => Value::Float(value.parse().ok()?),
+        ValueType::Integer => Value::Integer(value.parse().ok()?),
+        ValueType::Period => Value::Period(parse_period(value, tzid)?),
+        ValueType::Recur => Value::Recur(Box::new(value.parse().ok()?)),
+        ValueType::Text => Value::Text(unescape_text(value)),
+        ValueType::LanguageTag => Value::Text(value.to_string()),
+        ValueType::Time => {
+            let (time, utc) = parse_time(value)?;
+            Value::Time { time, utc }
+        }
+        ValueType::Timestamp => Value::DateTime(parse_timestamp(value)?),
+        ValueType::Uri => Value::Uri(value.to_string()),
+        ValueType::UtcOffset => Value::UtcOffset(parse_utc_offset(value)?),
+        ValueType::Unknown(_) => Value::Unknown(value.to_string()),
+    })
+}
+
+/// The properties holding a list of `TEXT` values.
+pub(crate) const TEXT_LISTS: &[&str] = &["CATEGORIES", "RESOURCES", "NICKNAME"];
+
+/// The vCard properties holding a structured `TEXT` value.
+#[cfg(any(feature = "generator", feature = "jcal", feature = "jcard"))]
+pub(crate) const VCARD_STRUCTURED: &[&str] = &["N", "ADR", "ORG", "GENDER", "CLIENTPIDMAP"];
+
+impl Property {
+    /// Return the type of the value, from the `VALUE` parameter or the property default.
+    ///
+    /// A value encoded with `ENCODING=b` or `ENCODING=BASE64` is a `BINARY` one.
+    pub fn value_type(&self, format: Format) -> ValueType {
+        if let Some(value_type) = param_value(self, "VALUE") {
+            return ValueType::from_name(value_type);
+        }
+
+        match self.encoding() {
+            Some(ValueEncoding::Base64) => ValueType::Binary,
+            _ => ValueType::default_for(&self.name, format),
+        }
+    }
+
+    /// Decode the value of the property.
+    ///
+    /// Return an error for the multi-valued properties holding more than one value, use
+    /// `typed_values` for them.
+    pub fn typed_value(&self, format: Format) -> Result<Value, ValueError> {
+        let mut values = self.typed_values(format)?;
+
+        if values.len() == 1 {
+            Ok(values.remove(0))
+        } else {
+            Err(ValueError::InvalidValue {
+                line: self.line(),
+                property: self.name.clone(),
+                value_type: self.value_type(format),
+                value: self.value.clone().unwrap_or_default(),
+            })
+        }
+    }
+
+    /// Decode all the values of the property.
+    ///
+    /// The values are split on `,`, except for the `TEXT` values which are kept whole unless
+    /// they are a list, and for the iCalendar `GEO` which is split on `;`.
+    pub fn typed_values(&self, format: Format) -> Result<Vec<Value>, ValueError> {
+        // A quoted-printable value is decoded first, a base64 one is the `BINARY` value.
+        let value = self.raw_text().ok_or_else(|| ValueError::MissingValue {
+            line: self.line(),
+            property: self.name.clone(),
+        })?;
+        let value = value.as_ref();
+        let value_type = self.value_type(format);
+        let tzid = param_value(self, "TZID");
+        let text_list = TEXT_LISTS
+            .iter()
+            .any(|n| n.eq_ignore_ascii_case(&self.name));
+
+        let values: Vec<&str> = match value_type {
+            ValueType::Text if text_list => split_unescaped(value, ','),
+            ValueType::Text
+            | ValueType::Binary
+            | ValueType::Recur
+            | ValueType::Unknown(_)
+            | ValueType::LanguageTag => vec![value],
+            ValueType::Float if format == Format::Ical && self.name.eq_ignore_ascii_case("GEO") => {
+                value.split(';').collect()
+            }
+            _ => value.split(',').collect(),
+        };
+
+        if value_type == ValueType::Recur {
+            let recur = value.parse().map_err(|reason| ValueError::InvalidRecur {
+                line: self.line(),
+                property: self.name.clone(),
+                value: value.to_string(),
+                reason,
+            })?;
+
+            return Ok(vec![Value::Recur(Box::new(recur))]);
+        }
+
+        values
+            .into_iter()
+            .map(|raw| {
+                let decoded = match value_type {
+                    // The vCard dates and times may be reduced or truncated.
+                    ValueType::Date | ValueType::Time | ValueType::DateAndOrTime
+                        if format == Format::Vcard =>
+                    {
+                        decode(raw, &value_type, tzid).or_else(|| {
+                            partial::parse_partial(raw, &value_type).map(Value::PartialDateTime)
+                        })
+                    }
+                    _ => decode(raw, &value_type, tzid),
+                };
+
+                decoded.ok_or_else(|| ValueError::InvalidValue {
+                    line: self.line(),
+                    property: self.name.clone(),
+                    value_type: value_type.clone(),
+                    value: raw.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+    use crate::property::PropertyParser;
+
+    fn property(name: &str, value: &str, params: &[(&str, &str)]) -> Property {
+        Property {
+            name: name.to_string(),
+            group: None,
+            params: if params.is_empty() {
+                None
+            } else {
+                Some(
+                    params
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
+                        .collect(),
+                )
+            },
+            value: Some(value.to_string()),
+            span: None,
+        }
+    }
+
+    fn naive(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").unwrap()
+    }
+
+    #[test]
+    fn decode_date_times() {
+        let utc = property("DTSTAMP", "20201201T120423Z", &[]);
+        assert_eq!(
+            utc.typed_value(Format::Ical).unwrap(),
+            Value::DateTime(DateTime::Utc(naive("20201201T120423")))
+        );
+
+        let zoned = property("DTSTART", "20201206T170000", &[("TZID", "Europe/Berlin")]);
+        assert_eq!(
+            zoned.typed_value(Format::Ical).unwrap(),
+            Value::DateTime(DateTime::Zoned {
+                date_time: naive("20201206T170000"),
+                tzid: String::from("Europe/Berlin"),
+            })
+        );
+
+        let date = property("DTSTART", "20070628", &[("VALUE", "DATE")]);
+        assert_eq!(
+            date.typed_value(Format::Ical).unwrap(),
+            Value::Date(NaiveDate::from_ymd_opt(2007, 6, 28).unwrap())
+        );
+
+        let exdates = property("EXDATE", "19960402T010000,19960404T010000", &[]);
+        assert_eq!(exdates.typed_values(Format::Ical).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn decode_durations_and_periods() {
+        let duration = parse_duration("PT2H45M0S").unwrap();
+        assert_eq!(duration.to_chrono(), chrono::Duration::minutes(165));
+        assert_eq!(duration.to_string(), "PT2H45M");
+        assert_eq!(parse_duration("-P1W").unwrap().to_string(), "-P1W");
+        assert!(parse_duration("P1H").is_none());
+        assert!(parse_duration("PT").is_none());
+
+        let period = parse_period("19970101T180000Z/PT5H30M", None).unwrap();
+        assert_eq!(period.to_string(), "19970101T180000Z/PT5H30M");
+    }
+
+    #[test]
+    fn decode_vcard_partial_dates() {
+        let partial = |name: &str, value: &str| match property(name, value, &[])
+            .typed_value(Format::Vcard)
+            .unwrap()
+        {
+            Value::PartialDateTime(partial) => partial,
+            value => panic!("{:?}", value),
+        };
+
+        let birthday = partial("BDAY", "--0203");
+        assert_eq!(
+            (birthday.year, birthday.month, birthday.day),
+            (None, Some(2), Some(3))
+        );
+        assert_eq!(birthday.to_string(), "--0203");
+        assert_eq!(partial("BDAY", "1985").year, Some(1985));
+        assert_eq!(partial("BDAY", "1985-04").to_string(), "1985-04");
+
+        let anniversary = partial("ANNIVERSARY", "19850412T1022");
+        assert_eq!(
+            (
+                anniversary.day,
+                anniversary.hour,
+                anniversary.minute,
+                anniversary.second
+            ),
+            (Some(12), Some(10), Some(22), None)
+        );
+        assert_eq!(anniversary.to_string(), "19850412T1022");
+        assert_eq!(partial("BDAY", "T-2200Z").to_string(), "-2200Z");
+
+        assert_eq!(
+            property("BDAY", "19850412", &[])
+                .typed_value(Format::Vcard)
+                .unwrap(),
+            Value::Date(NaiveDate::from_ymd_opt(1985, 4, 12).unwrap())
+        );
+        assert!(property("BDAY", "--0230", &[])
+            .typed_value(Format::Vcard)
+            .is_err());
+        assert!(property("DTSTART", "--0203", &[("VALUE", "DATE")])
+            .typed_value(Format::Ical)
+            .is_err());
+    }
+
+    #[test]
+    fn decode_scalars() {
+        assert_eq!(
+            decode("-0500", &ValueType::UtcOffset, None)
+                .unwrap()
+                .to_string(),
+            "-0500"
+        );
+        assert!(decode("-0000", &ValueType::UtcOffset, None).is_none());
+        assert_eq!(
+            decode("TRUE", &ValueType::Boolean, None),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            decode("Zm9vYmFy", &ValueType::Binary, None),
+            Some(Value::Binary(b"foobar".to_vec()))
+        );
+        assert_eq!(encode_base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(
+            property("GEO", "37.386013;-122.082932", &[])
+                .typed_values(Format::Ical)
+                .unwrap(),
+            vec![Value::Float(37.386013), Value::Float(-122.082932)]
+        );
+        assert_eq!(
+            property("geo", "37.386013;-122.082932", &[])
+                .typed_values(Format::Ical)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            property("REV", "19951031T222710Z", &[])
+                .typed_value(Format::Vcard)
+                .unwrap(),
+            Value::DateTime(DateTime::Utc(naive("19951031T222710")))
+        );
+    }
+
+    #[test]
+    fn decode_texts() {
+        let summary = property("SUMMARY", "Lunch\\, then\\nnap", &[]);
+        let value = summary.typed_value(Format::Ical).unwrap();
+        assert_eq!(value, Value::Text(String::from("Lunch, then\nnap")));
+        assert_eq!(value.to_string(), "Lunch\\, then\\nnap");
+
+        assert_eq!(
+            property("CATEGORIES", "WORK,Rock\\, Paper", &[])
+                .typed_values(Format::Ical)
+                .unwrap(),
+            vec![
+                Value::Text(String::from("WORK")),
+                Value::Text(String::from("Rock, Paper")),
+            ]
+        );
+        assert_eq!(
+            property("categories", "WORK,HOME", &[])
+                .typed_values(Format::Ical)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let note = property("NOTE", "Caf=C3=A9", &[("ENCODING", "QUOTED-PRINTABLE")]);
+        assert_eq!(
+            note.typed_value(Format::Vcard).unwrap(),
+            Value::Text(String::from("Café"))
+        );
+
+        let photo = property("PHOTO", "Zm9vYmFy", &[("ENCODING", "BASE64")]);
+        assert_eq!(
+            photo.typed_value(Format::Vcard).unwrap(),
+            Value::Binary(b"foobar".to_vec())
+        );
+    }
+
+    #[test]
+    fn report_invalid_values() {
+        let error = property("DTSTART", "20201306T170000", &[])
+            .typed_value(Format::Ical)
+            .unwrap_err()
+            .at_line(12);
+
+        assert_eq!(
+            error.to_string(),
+            "Line 12: Invalid DATE-TIME value \"20201306T170000\" for DTSTART."
+        );
+
+        let input: &[u8] = b"SUMMARY:Review\r\nDTSTART:2020-12-06\r\n";
+        let error = PropertyParser::from_reader(input)
+            .nth(1)
+            .unwrap()
+            .unwrap()
+            .typed_value(Format::Ical)
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Line 2: Invalid DATE-TIME value \"2020-12-06\" for DTSTART."
+        );
+    }
+}
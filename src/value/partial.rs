@@ -0,0 +1,218 @@
+//! The vCard dates and times, which may be reduced (`1985-04`, `1022`) or truncated
+//! (`--0412`, `-2200`) as described in
+//! [RFC6350 section 4.3](https://tools.ietf.org/html/rfc6350#section-4.3), in their basic
+//! format or in the extended one used by jCard and xCard.
+
+// Internal mods
+use super::{parse_utc_offset, PartialDateTime, ValueType};
+use chrono::{FixedOffset, NaiveDate};
+
+/// Split `value` in pairs of digits joined by `separator` (`102200` → `10:22:00`).
+fn group_digits(value: &str, separator: &str) -> Option<String> {
+    if value.is_empty()
+        || !value.len().is_multiple_of(2)
+        || !value.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let pairs: Vec<&str> = (0..value.len())
+        .step_by(2)
+        .map(|i| &value[i..i + 2])
+        .collect();
+
+    Some(pairs.join(separator))
+}
+
+/// Convert a vCard date, possibly reduced or truncated (`19850412`, `1985-04`, `--0412`,
+/// `---12`), to its extended format (`1985-04-12`, `1985-04`, `--04-12`, `---12`) or back to
+/// its basic one.
+fn vcard_date(date: &str, extended: bool) -> Option<String> {
+    let digits = date.trim_start_matches('-');
+    let leading = &date[..date.len() - digits.len()];
+    let digits = digits.replace('-', "");
+
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let separator = if extended { "-" } else { "" };
+    Some(match (leading, digits.len()) {
+        ("", 8) => format!(
+            "{}{}{}{}{}",
+            &digits[..4],
+            separator,
+            &digits[4..6],
+            separator,
+            &digits[6..]
+        ),
+        ("", 6) => format!("{}-{}", &digits[..4], &digits[4..]),
+        ("", 4) | ("--", 2) | ("---", 2) => date.to_string(),
+        ("--", 4) => format!("--{}{}{}", &digits[..2], separator, &digits[2..]),
+        _ => return None,
+    })
+}
+
+/// Convert a UTC offset or a time zone (`Z`, `-0500`, `+01`) to its extended format or back
+/// to its basic one.
+pub(crate) fn vcard_zone(zone: &str, extended: bool) -> Option<String> {
+    if zone == "Z" {
+        return Some(String::from("Z"));
+    }
+    if !zone.starts_with(['+', '-']) {
+        return None;
+    }
+
+    let (sign, digits) = zone.split_at(1);
+    let digits = digits.replace(':', "");
+
+    Some(format!(
+        "{}{}",
+        sign,
+        group_digits(&digits, if extended { ":" } else { "" })?
+    ))
+}
+
+/// Convert a vCard time, possibly reduced or truncated (`102200`, `1022`, `-2200`, `--00`),
+/// with its zone, to its extended format (`10:22:00`, `10:22`, `-22:00`, `--00`) or back to
+/// its basic one.
+fn vcard_time(time: &str, extended: bool) -> Option<String> {
+    let digits = time.trim_start_matches('-');
+    let leading = &time[..time.len() - digits.len()];
+
+    let (digits, zone) = match digits.find(['Z', '+', '-']) {
+        Some(index) => (
+            &digits[..index],
+            Some(vcard_zone(&digits[index..], extended)?),
+        ),
+        None => (digits, None),
+    };
+    let digits = group_digits(&digits.replace(':', ""), if extended { ":" } else { "" })?;
+
+    Some(format!("{}{}{}", leading, digits, zone.unwrap_or_default()))
+}
+
+/// Convert a vCard `DATE`, `TIME`, `DATE-TIME`, `DATE-AND-OR-TIME` or `TIMESTAMP` value to its
+/// extended format or back to its basic one.
+pub(crate) fn vcard_date_time(
+    value: &str,
+    value_type: &ValueType,
+    extended: bool,
+) -> Option<String> {
+    if *value_type == ValueType::Time {
+        return vcard_time(value, extended);
+    }
+
+    match value.split_once('T') {
+        Some(("", time)) if *value_type == ValueType::DateAndOrTime => {
+            Some(format!("T{}", vcard_time(time, extended)?))
+        }
+        Some((date, time)) if *value_type != ValueType::Date => Some(format!(
+            "{}T{}",
+            vcard_date(date, extended)?,
+            vcard_time(time, extended)?
+        )),
+        None if *value_type == ValueType::Date || *value_type == ValueType::DateAndOrTime => {
+            vcard_date(value, extended)
+        }
+        _ => None,
+    }
+}
+
+/// Parse the date part of an extended value (`1985-04`, `--04-12`, `---12`).
+fn parse_date_part(date: &str, partial: &mut PartialDateTime) -> Option<()> {
+    let number = |text: &str| text.parse::<u32>().ok();
+
+    match date.split('-').collect::<Vec<_>>().as_slice() {
+        [""] => {}
+        ["", "", "", day] => partial.day = Some(number(day)?),
+        ["", "", month] => partial.month = Some(number(month)?),
+        ["", "", month, day] => {
+            partial.month = Some(number(month)?);
+            partial.day = Some(number(day)?);
+        }
+        [year, rest @ ..] if !year.is_empty() && rest.len() <= 2 => {
+            let rest = rest
+                .iter()
+                .map(|part| number(part))
+                .collect::<Option<Vec<u32>>>()?;
+            partial.year = Some(year.parse().ok()?);
+            partial.month = rest.first().copied();
+            partial.day = rest.get(1).copied();
+        }
+        _ => return None,
+    }
+
+    Some(())
+}
+
+/// Parse the time part of an extended value (`10:22`, `-22:00`, `--00`), with its zone.
+fn parse_time_part(time: &str, partial: &mut PartialDateTime) -> Option<()> {
+    let digits = time.trim_start_matches('-');
+    let leading = time.len() - digits.len();
+
+    let (digits, zone) = match digits.find(['Z', '+', '-']) {
+        Some(index) => (&digits[..index], Some(&digits[index..])),
+        None => (digits, None),
+    };
+
+    let fields = digits
+        .split(':')
+        .map(|field| field.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    if leading + fields.len() > 3 {
+        return None;
+    }
+
+    let mut parts = [None; 3];
+    for (index, field) in fields.into_iter().enumerate() {
+        parts[leading + index] = Some(field);
+    }
+    partial.hour = parts[0];
+    partial.minute = parts[1];
+    partial.second = parts[2];
+
+    partial.offset = match zone {
+        Some("Z") => Some(FixedOffset::east_opt(0)?),
+        Some(zone) => Some(parse_utc_offset(zone)?),
+        None => None,
+    };
+
+    Some(())
+}
+
+/// Parse a vCard `DATE`, `TIME` or `DATE-AND-OR-TIME` value, which may be reduced or
+/// truncated (`--0412`, `19850412T1022`).
+pub(crate) fn parse_partial(value: &str, value_type: &ValueType) -> Option<PartialDateTime> {
+    let extended = vcard_date_time(value, value_type, true)?;
+    let (date, time) = match extended.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None if *value_type == ValueType::Time => ("", Some(extended.as_str())),
+        None => (extended.as_str(), None),
+    };
+
+    let mut partial = PartialDateTime::default();
+    parse_date_part(date, &mut partial)?;
+    if let Some(time) = time {
+        parse_time_part(time, &mut partial)?;
+    }
+
+    let valid = partial.month.is_none_or(|month| (1..=12).contains(&month))
+        && partial.day.is_none_or(|day| (1..=31).contains(&day))
+        && partial.hour.is_none_or(|hour| hour < 24)
+        && partial.minute.is_none_or(|minute| minute < 60)
+        && partial.second.is_none_or(|second| second <= 60);
+    // A day must exist in its month, in a leap year if the year is unknown.
+    let date_exists = match (partial.month, partial.day) {
+        (Some(month), Some(day)) => {
+            NaiveDate::from_ymd_opt(partial.year.unwrap_or(2000), month, day).is_some()
+        }
+        _ => true,
+    };
+
+    if valid && date_exists {
+        Some(partial)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,524 @@
+//! The `RECUR` value type.
+//!
+//! @see: https://tools.ietf.org/html/rfc5545#section-3.3.10
+//!
+//! `recur = recur-rule-part *( ";" recur-rule-part )`
+//!     The rule parts are not ordered in any particular sequence, the FREQ rule part is
+//!     REQUIRED, the UNTIL or COUNT rule parts are OPTIONAL, but they MUST NOT occur in the
+//!     same "recur".
+
+// Sys mods
+use std::fmt;
+use std::str::FromStr;
+
+// Internal mods
+use crate::value::{parse_date, parse_date_time, DateTime};
+use chrono::{NaiveDate, Weekday};
+
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum RecurError {
+    #[error("missing FREQ")]
+    MissingFrequency,
+    #[error("missing a value for {0}")]
+    MissingValue(String),
+    #[error("invalid value {value:?} for {name}")]
+    InvalidPart { name: String, value: String },
+    #[error("{0} is defined more than once")]
+    DuplicatedPart(String),
+    #[error("UNTIL and COUNT must not occur in the same rule")]
+    UntilWithCount,
+    #[error("{part} is not allowed with FREQ={frequency}")]
+    NotAllowed { part: String, frequency: Frequency },
+    #[error("BYSETPOS must be used with another BYxxx rule part")]
+    LonelySetPos,
+}
+
+/// The `FREQ` rule part.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Frequency::Secondly => "SECONDLY",
+            Frequency::Minutely => "MINUTELY",
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = RecurError;
+
+    fn from_str(value: &str) -> Result<Frequency, RecurError> {
+        match value.to_uppercase().as_str() {
+            "SECONDLY" => Ok(Frequency::Secondly),
+            "MINUTELY" => Ok(Frequency::Minutely),
+            "HOURLY" => Ok(Frequency::Hourly),
+            "DAILY" => Ok(Frequency::Daily),
+            "WEEKLY" => Ok(Frequency::Weekly),
+            "MONTHLY" => Ok(Frequency::Monthly),
+            "YEARLY" => Ok(Frequency::Yearly),
+            _ => Err(RecurError::InvalidPart {
+                name: String::from("FREQ"),
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// The `UNTIL` rule part: a `DATE` or a `DATE-TIME` (UTC or floating).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Until {
+    Date(NaiveDate),
+    DateTime(DateTime),
+}
+
+impl fmt::Display for Until {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Until::Date(date) => write!(f, "{}", date.format("%Y%m%d")),
+            Until::DateTime(date_time) => write!(f, "{}", date_time),
+        }
+    }
+}
+
+/// The bound of a rule: either an `UNTIL` or a `COUNT` rule part.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum RecurEnd {
+    Until(Until),
+    Count(u32),
+}
+
+/// An item of the `BYDAY` rule part, such as `MO`, `+2TU` or `-1FR`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct WeekdayNum {
+    /// The nth occurrence of the weekday within the month or the year.
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+impl WeekdayNum {
+    /// Every `weekday` of the period.
+    pub fn every(weekday: Weekday) -> WeekdayNum {
+        WeekdayNum {
+            ordinal: None,
+            weekday,
+        }
+    }
+
+    /// The `ordinal`th `weekday` of the period, counted backward if negative.
+    pub fn nth(ordinal: i32, weekday: Weekday) -> WeekdayNum {
+        WeekdayNum {
+            ordinal: Some(ordinal),
+            weekday,
+        }
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+impl fmt::Display for WeekdayNum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ordinal) = self.ordinal {
+            write!(f, "{}", ordinal)?;
+        }
+
+        write!(f, "{}", weekday_name(self.weekday))
+    }
+}
+
+/// A recurrence rule, as found in a `RRULE` property.
+///
+/// ```
+/// # extern crate chrono;
+/// # extern crate ical;
+/// use chrono::Weekday;
+/// use ical::value::recur::{Frequency, Recur, RecurEnd, WeekdayNum};
+///
+/// let mut rule = Recur::new(Frequency::Monthly);
+/// rule.end = Some(RecurEnd::Count(10));
+/// rule.by_day = vec![WeekdayNum::nth(-1, Weekday::Fri)];
+///
+/// assert_eq!(rule.to_string(), "FREQ=MONTHLY;COUNT=10;BYDAY=-1FR");
+/// assert_eq!("FREQ=MONTHLY;BYDAY=-1FR;COUNT=10".parse::<Recur>(), Ok(rule));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Recur {
+    pub freq: Frequency,
+    pub end: Option<RecurEnd>,
+    pub interval: Option<u32>,
+    pub by_second: Vec<u32>,
+    pub by_minute: Vec<u32>,
+    pub by_hour: Vec<u32>,
+    pub by_day: Vec<WeekdayNum>,
+    pub by_month_day: Vec<i32>,
+    pub by_year_day: Vec<i32>,
+    pub by_week_no: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+    pub wkst: Option<Weekday>,
+    /// The IANA or experimental rule parts, kept as found.
+    pub extensions: Vec<(String, String)>,
+}
+
+impl Recur {
+    /// Return a new rule with the given frequency and no other rule part.
+    pub fn new(freq: Frequency) -> Recur {
+        Recur {
+            freq,
+            end: None,
+            interval: None,
+            by_second: Vec::new(),
+            by_minute: Vec::new(),
+            by_hour: Vec::new(),
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_year_day: Vec::new(),
+            by_week_no: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            wkst: None,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Check the rule parts combinations forbidden by RFC 5545.
+    pub fn validate(&self) -> Result<(), RecurError> {
+        let not_allowed = |part: &str| RecurError::NotAllowed {
+            part: part.to_string(),
+            frequency: self.freq,
+        };
+
+        if self.interval == Some(0) {
+            return Err(RecurError::InvalidPart {
+                name: String::from("INTERVAL"),
+                value: String::from("0"),
+            });
+        }
+
+        if !self.by_week_no.is_empty() && self.freq != Frequency::Yearly {
+            return Err(not_allowed("BYWEEKNO"));
+        }
+
+        if !self.by_year_day.is_empty()
+            && [Frequency::Daily, Frequency::Weekly, Frequency::Monthly].contains(&self.freq)
+        {
+            return Err(not_allowed("BYYEARDAY"));
+        }
+
+        if !self.by_month_day.is_empty() && self.freq == Frequency::Weekly {
+            return Err(not_allowed("BYMONTHDAY"));
+        }
+
+        // The BYDAY ordinals are only meaningful within a month or a year.
+        let has_ordinal = self.by_day.iter().any(|day| day.ordinal.is_some());
+        if has_ordinal
+            && (!(self.freq == Frequency::Monthly || self.freq == Frequency::Yearly)
+                || (self.freq == Frequency::Yearly && !self.by_week_no.is_empty()))
+        {
+            return Err(not_allowed("BYDAY with an ordinal"));
+        }
+
+        if !self.by_set_pos.is_empty()
+            && self.by_second.is_empty()
+            && self.by_minute.is_empty()
+            && self.by_hour.is_empty()
+            && self.by_day.is_empty()
+            && self.by_month_day.is_empty()
+            && self.by_year_day.is_empty()
+            && self.by_week_no.is_empty()
+            && self.by_month.is_empty()
+        {
+            return Err(RecurError::LonelySetPos);
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_list<T, F>(name: &str, list: &str, parse: F) -> Result<Vec<T>, RecurError>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    list.split(',')
+        .map(|item| {
+            parse(item).ok_or_else(|| RecurError::InvalidPart {
+                name: name.to_string(),
+                value: item.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn in_range(value: &str, min: i32, max: i32, signed: bool) -> Option<i32> {
+    let number = value.trim_start_matches('+').parse::<i32>().ok()?;
+
+    if (min..=max).contains(&number) || (signed && (-max..=-min).contains(&number)) {
+        Some(number)
+    } else {
+        None
+    }
+}
+
+fn parse_until(value: &str) -> Option<Until> {
+    if value.contains('T') {
+        parse_date_time(value, None).map(Until::DateTime)
+    } else {
+        parse_date(value).map(Until::Date)
+    }
+}
+
+impl FromStr for Recur {
+    type Err = RecurError;
+
+    fn from_str(value: &str) -> Result<Recur, RecurError> {
+        let mut freq = None;
+        let mut until = None;
+        let mut count = None;
+        let mut rule = Recur::new(Frequency::Yearly);
+        let mut seen: Vec<String> = Vec::new();
+
+        for part in value.split(';').filter(|part| !part.is_empty()) {
+            let mut elements = part.splitn(2, '=');
+            let name = elements.next().unwrap_or_default().to_uppercase();
+            let part_value = elements
+                .next()
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| RecurError::MissingValue(name.clone()))?;
+            let invalid = || RecurError::InvalidPart {
+                name: name.clone(),
+                value: part_value.to_string(),
+            };
+
+            if seen.contains(&name) {
+                return Err(RecurError::DuplicatedPart(name));
+            }
+            seen.push(name.clone());
+
+            match name.as_str() {
+                "FREQ" => freq = Some(part_value.parse()?),
+                "UNTIL" => until = Some(parse_until(part_value).ok_or_else(invalid)?),
+                "COUNT" => count = Some(part_value.parse().map_err(|_| invalid())?),
+                "INTERVAL" => {
+                    rule.interval = Some(
+                        part_value
+                            .parse()
+                            .ok()
+                            .filter(|interval| *interval > 0)
+                            .ok_or_else(invalid)?,
+                    )
+                }
+                "BYSECOND" => {
+                    rule.by_second = parse_list(&name, part_value, |v| {
+                        in_range(v, 0, 60, false).map(|v| v as u32)
+                    })?
+                }
+                "BYMINUTE" => {
+                    rule.by_minute = parse_list(&name, part_value, |v| {
+                        in_range(v, 0, 59, false).map(|v| v as u32)
+                    })?
+                }
+                "BYHOUR" => {
+                    rule.by_hour = parse_list(&name, part_value, |v| {
+                        in_range(v, 0, 23, false).map(|v| v as u32)
+                    })?
+                }
+                "BYDAY" => {
+                    rule.by_day = parse_list(&name, part_value, |v| {
+                        let v = v.to_uppercase();
+                        let split = v.len().checked_sub(2)?;
+                        let weekday = parse_weekday(v.get(split..)?)?;
+                        let ordinal = match v.get(..split)? {
+                            "" => None,
+                            ordinal => Some(in_range(ordinal, 1, 53, true)?),
+                        };
+
+                        Some(WeekdayNum { ordinal, weekday })
+                    })?
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = parse_list(&name, part_value, |v| in_range(v, 1, 31, true))?
+                }
+                "BYYEARDAY" => {
+                    rule.by_year_day = parse_list(&name, part_value, |v| in_range(v, 1, 366, true))?
+                }
+                "BYWEEKNO" => {
+                    rule.by_week_no = parse_list(&name, part_value, |v| in_range(v, 1, 53, true))?
+                }
+                "BYMONTH" => {
+                    rule.by_month = parse_list(&name, part_value, |v| {
+                        in_range(v, 1, 12, false).map(|v| v as u32)
+                    })?
+                }
+                "BYSETPOS" => {
+                    rule.by_set_pos = parse_list(&name, part_value, |v| in_range(v, 1, 366, true))?
+                }
+                "WKST" => {
+                    rule.wkst = Some(parse_weekday(&part_value.to_uppercase()).ok_or_else(invalid)?)
+                }
+                _ => rule.extensions.push((name.clone(), part_value.to_string())),
+            }
+        }
+
+        rule.freq = freq.ok_or(RecurError::MissingFrequency)?;
+        rule.end = match (until, count) {
+            (Some(_), Some(_)) => return Err(RecurError::UntilWithCount),
+            (Some(until), None) => Some(RecurEnd::Until(until)),
+            (None, Some(count)) => Some(RecurEnd::Count(count)),
+            (None, None) => None,
+        };
+
+        rule.validate()?;
+
+        Ok(rule)
+    }
+}
+
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter, name: &str, list: &[T]) -> fmt::Result {
+    if list.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, ";{}=", name)?;
+    for (i, item) in list.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{}", item)?;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for Recur {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FREQ={}", self.freq)?;
+
+        match &self.end {
+            Some(RecurEnd::Until(until)) => write!(f, ";UNTIL={}", until)?,
+            Some(RecurEnd::Count(count)) => write!(f, ";COUNT={}", count)?,
+            None => {}
+        }
+        if let Some(interval) = self.interval {
+            write!(f, ";INTERVAL={}", interval)?;
+        }
+
+        write_list(f, "BYSECOND", &self.by_second)?;
+        write_list(f, "BYMINUTE", &self.by_minute)?;
+        write_list(f, "BYHOUR", &self.by_hour)?;
+        write_list(f, "BYDAY", &self.by_day)?;
+        write_list(f, "BYMONTHDAY", &self.by_month_day)?;
+        write_list(f, "BYYEARDAY", &self.by_year_day)?;
+        write_list(f, "BYWEEKNO", &self.by_week_no)?;
+        write_list(f, "BYMONTH", &self.by_month)?;
+        write_list(f, "BYSETPOS", &self.by_set_pos)?;
+
+        if let Some(wkst) = self.wkst {
+            write!(f, ";WKST={}", weekday_name(wkst))?;
+        }
+        for (name, value) in &self.extensions {
+            write!(f, ";{}={}", name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+
+    #[test]
+    fn round_trip_rules() {
+        for rule in &[
+            "FREQ=YEARLY;INTERVAL=2;BYMINUTE=30;BYHOUR=8,9;BYDAY=SU;BYMONTH=1",
+            "FREQ=MONTHLY;COUNT=10;BYDAY=1FR",
+            "FREQ=DAILY;UNTIL=19971224T000000Z",
+            "FREQ=YEARLY;UNTIL=20000131;BYDAY=SU,MO,TU,WE,TH,FR,SA;BYMONTH=1",
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            "FREQ=WEEKLY;COUNT=4;INTERVAL=2;BYDAY=TU,SU;WKST=SU",
+            "FREQ=YEARLY;BYMONTHDAY=-1;BYMONTH=2;RSCALE=GREGORIAN",
+        ] {
+            assert_eq!(rule.parse::<Recur>().unwrap().to_string(), *rule);
+        }
+    }
+
+    #[test]
+    fn reject_invalid_rules() {
+        assert_eq!(
+            "FREQ=DAILY;COUNT=3;UNTIL=19971224T000000Z".parse::<Recur>(),
+            Err(RecurError::UntilWithCount)
+        );
+        assert_eq!(
+            "INTERVAL=2".parse::<Recur>(),
+            Err(RecurError::MissingFrequency)
+        );
+        assert_eq!(
+            "FREQ=MONTHLY;BYWEEKNO=20".parse::<Recur>(),
+            Err(RecurError::NotAllowed {
+                part: String::from("BYWEEKNO"),
+                frequency: Frequency::Monthly,
+            })
+        );
+        assert_eq!(
+            "FREQ=WEEKLY;BYDAY=1MO".parse::<Recur>(),
+            Err(RecurError::NotAllowed {
+                part: String::from("BYDAY with an ordinal"),
+                frequency: Frequency::Weekly,
+            })
+        );
+        assert_eq!(
+            "FREQ=DAILY;BYSETPOS=1".parse::<Recur>(),
+            Err(RecurError::LonelySetPos)
+        );
+        assert_eq!(
+            "FREQ=DAILY;BYHOUR=24".parse::<Recur>(),
+            Err(RecurError::InvalidPart {
+                name: String::from("BYHOUR"),
+                value: String::from("24"),
+            })
+        );
+        assert_eq!(
+            "FREQ=DAILY;FREQ=WEEKLY".parse::<Recur>(),
+            Err(RecurError::DuplicatedPart(String::from("FREQ")))
+        );
+    }
+}
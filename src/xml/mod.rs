@@ -0,0 +1,812 @@
+//! Convert the calendars and the contacts to and from their XML representation, with the
+//! `xcal` feature.
+//!
+//! * xCal ([RFC6321](https://tools.ietf.org/html/rfc6321)) for the iCalendar calendars.
+//! * xCard ([RFC6351](https://tools.ietf.org/html/rfc6351)) for the vCard contacts.
+//!
+//! A component is an element holding its `properties` and its `components`. A property is an
+//! element holding its `parameters` and its value, in an element named after its type:
+//!
+//! ```xml
+//! <vevent>
+//!   <properties>
+//!     <dtstart>
+//!       <parameters><tzid><text>Europe/Paris</text></tzid></parameters>
+//!       <date-time>2024-01-02T12:00:00</date-time>
+//!     </dtstart>
+//!     <rrule><recur><freq>WEEKLY</freq><byday>MO</byday><byday>TH</byday></recur></rrule>
+//!     <summary><text>Meeting</text></summary>
+//!   </properties>
+//! </vevent>
+//! ```
+//!
+//! The `TEXT` values are unescaped, the dates and times written in their extended format
+//! (`2024-01-02T12:00:00`), the `PERIOD` and `RECUR` values and the structured ones (`GEO`,
+//! `N`, `ADR`...) split into their parts. A list of values is a list of elements. The `VALUE`
+//! parameter becomes the name of the value element and is only written back, as the last
+//! parameter, when it differs from the default type of the property. The order of the
+//! properties, of the other parameters and of the rule parts is kept, so that a calendar
+//! converted back and forth holds the same values, as with the [`json`](../json/index.html)
+//! module.
+//!
+//! As the XML elements are in lowercase, the names of the properties and of the parameters
+//! come back in uppercase. The quoted vCard lists (`TYPE="work,voice"`) come back as plain
+//! lists (`TYPE=work,voice`), which are equivalent. The `VALUE` parameters may move
+//! (`TEL;TYPE=work;VALUE=uri`), the ones naming the default type are dropped and the special
+//! characters of the `TEXT` values come back escaped (`SUMMARY:a\, b`).
+//!
+//! The properties of an xCard contact are held directly by the `vcard` element, the ones
+//! sharing a group being enclosed in a `group` element.
+
+pub mod xcal;
+pub mod xcard;
+
+pub use self::xcal::{from_xcal, to_xcal};
+pub use self::xcard::{from_xcard, to_xcard};
+
+// Internal mods
+use crate::parser::ical::component::IcalUnknownComponent;
+use crate::property::{
+    escape_component, escape_text, split_unescaped, Format, Property, VCARD_LIST_PARAMS,
+};
+use crate::value::extended::{
+    add_value_param, from_extended, recur_from_parts, recur_to_parts, to_extended,
+};
+use crate::value::{decode, parse_duration, Value, ValueError, ValueType, TEXT_LISTS};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+
+#[derive(Debug, Error)]
+pub enum XmlError {
+    #[error(transparent)]
+    Xml(#[from] quick_xml::Error),
+    #[error(transparent)]
+    Value(#[from] ValueError),
+    #[error("Invalid {kind}: {found}.")]
+    Invalid { kind: &'static str, found: String },
+    #[error("Expected a {expected} element, found {found}.")]
+    UnexpectedElement { expected: String, found: String },
+}
+
+impl XmlError {
+    fn invalid(kind: &'static str, found: &Element) -> XmlError {
+        XmlError::Invalid {
+            kind,
+            found: found.name.clone(),
+        }
+    }
+}
+
+/// An XML element, holding either some text or some child elements.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    pub fn new(name: &str) -> Element {
+        Element {
+            name: name.to_string(),
+            ..Element::default()
+        }
+    }
+
+    pub fn with_text(name: &str, text: &str) -> Element {
+        Element {
+            text: text.to_string(),
+            ..Element::new(name)
+        }
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Return an error if the element isn't named `expected`.
+    pub fn expect(&self, expected: &str) -> Result<(), XmlError> {
+        if self.name == expected {
+            Ok(())
+        } else {
+            Err(XmlError::UnexpectedElement {
+                expected: expected.to_string(),
+                found: self.name.clone(),
+            })
+        }
+    }
+
+    fn write(&self, writer: &mut Writer<Vec<u8>>) -> Result<(), XmlError> {
+        let mut start = BytesStart::new(self.name.as_str());
+        for (name, value) in &self.attributes {
+            start.push_attribute((name.as_str(), value.as_str()));
+        }
+
+        if self.children.is_empty() && self.text.is_empty() {
+            return write_event(writer, Event::Empty(start));
+        }
+
+        write_event(writer, Event::Start(start))?;
+        if !self.text.is_empty() {
+            write_event(writer, Event::Text(BytesText::new(&self.text)))?;
+        }
+        for child in &self.children {
+            child.write(writer)?;
+        }
+        write_event(writer, Event::End(BytesEnd::new(self.name.as_str())))
+    }
+}
+
+fn write_event(writer: &mut Writer<Vec<u8>>, event: Event) -> Result<(), XmlError> {
+    writer
+        .write_event(event)
+        .map_err(|err| XmlError::Xml(err.into()))
+}
+
+/// Write an XML document whose root element is `root`.
+pub(crate) fn write_document(root: &Element) -> Result<String, XmlError> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    write_event(
+        &mut writer,
+        Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)),
+    )?;
+    root.write(&mut writer)?;
+
+    let mut xml = String::from_utf8_lossy(&writer.into_inner()).into_owned();
+    xml.push('\n');
+    Ok(xml)
+}
+
+/// Read an XML document and return its root element.
+///
+/// The namespaces are ignored and only the text of the elements without children is kept.
+pub(crate) fn parse_document(xml: &str) -> Result<Element, XmlError> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Element> = Vec::new();
+
+    loop {
+        let (element, closed) = match reader.read_event()? {
+            Event::Start(start) => (start_element(&start)?, false),
+            Event::Empty(start) => (start_element(&start)?, true),
+            Event::Text(text) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.text.push_str(&text.unescape()?);
+                }
+                continue;
+            }
+            Event::CData(data) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent
+                        .text
+                        .push_str(&data.decode().map_err(quick_xml::Error::from)?);
+                }
+                continue;
+            }
+            Event::End(_) => match stack.pop() {
+                Some(element) => (element, true),
+                None => continue,
+            },
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        if !closed {
+            stack.push(element);
+            continue;
+        }
+
+        let mut element = element;
+        if !element.children.is_empty() {
+            element.text.clear();
+        }
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None => return Ok(element),
+        }
+    }
+
+    Err(XmlError::Invalid {
+        kind: "document",
+        found: String::from("no root element"),
+    })
+}
+
+fn start_element(start: &BytesStart) -> Result<Element, XmlError> {
+    let mut element = Element::new(&String::from_utf8_lossy(start.local_name().as_ref()));
+
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        let key = attribute.key;
+        if key.as_ref() == b"xmlns" || key.prefix().is_some_and(|p| p.as_ref() == b"xmlns") {
+            continue;
+        }
+
+        element.attributes.push((
+            String::from_utf8_lossy(key.local_name().as_ref()).into_owned(),
+            attribute.unescape_value()?.into_owned(),
+        ));
+    }
+
+    Ok(element)
+}
+
+/// Return the type of the values of a parameter.
+fn parameter_type(name: &str, format: Format) -> &'static str {
+    match (format, name) {
+        (Format::Ical, "ALTREP") | (Format::Ical, "DIR") | (Format::Vcard, "GEO") => "uri",
+        (Format::Ical, "DELEGATED-FROM")
+        | (Format::Ical, "DELEGATED-TO")
+        | (Format::Ical, "MEMBER")
+        | (Format::Ical, "SENT-BY") => "cal-address",
+        (Format::Ical, "RSVP") => "boolean",
+        (Format::Vcard, "PREF") => "integer",
+        (Format::Vcard, "LANGUAGE") => "language-tag",
+        _ => "text",
+    }
+}
+
+/// Return the elements holding the components of a structured value, `None` if the property
+/// doesn't hold one. The components of a vCard `ORG` are all `text` elements.
+fn structured_components(
+    name: &str,
+    value_type: &ValueType,
+    format: Format,
+) -> Option<&'static [&'static str]> {
+    match (format, value_type, name) {
+        (Format::Ical, ValueType::Float, "GEO") => Some(&["latitude", "longitude"]),
+        (Format::Ical, ValueType::Text, "REQUEST-STATUS") => Some(&["code", "description", "data"]),
+        (Format::Vcard, ValueType::Text, "N") => {
+            Some(&["surname", "given", "additional", "prefix", "suffix"])
+        }
+        (Format::Vcard, ValueType::Text, "ADR") => Some(&[
+            "pobox", "ext", "street", "locality", "region", "code", "country",
+        ]),
+        (Format::Vcard, ValueType::Text, "GENDER") => Some(&["sex", "identity"]),
+        (Format::Vcard, ValueType::Text, "CLIENTPIDMAP") => Some(&["sourceid", "uri"]),
+        (Format::Vcard, ValueType::Text, "ORG") => Some(&[]),
+        _ => None,
+    }
+}
+
+/// Return the name of the elements holding the values of a type.
+fn type_name(value_type: &ValueType) -> String {
+    value_type.to_string().to_lowercase()
+}
+
+/// Convert a single value from its iCalendar or vCard form.
+fn value_to_xml(raw: &str, value_type: &ValueType, format: Format) -> Option<Element> {
+    let name = type_name(value_type);
+
+    match value_type {
+        ValueType::Period => {
+            let extended = to_extended(raw, value_type, format)?;
+            let (start, end) = extended.split_once('/')?;
+            let end_name = if end.starts_with(['P', '+', '-']) {
+                "duration"
+            } else {
+                "end"
+            };
+
+            let mut period = Element::new(&name);
+            period.children = vec![
+                Element::with_text("start", start),
+                Element::with_text(end_name, end),
+            ];
+            return Some(period);
+        }
+        ValueType::Recur => {
+            let mut recur = Element::new(&name);
+            for (part, values) in recur_to_parts(raw)? {
+                let part = part.to_lowercase();
+                recur
+                    .children
+                    .extend(values.iter().map(|value| Element::with_text(&part, value)));
+            }
+            return Some(recur);
+        }
+        _ => {}
+    }
+
+    if let Some(extended) = to_extended(raw, value_type, format) {
+        return Some(Element::with_text(&name, &extended));
+    }
+
+    let text = match decode(raw, value_type, None)? {
+        Value::Boolean(boolean) => boolean.to_string(),
+        Value::Text(text) => text,
+        _ => raw.to_string(),
+    };
+
+    Some(Element::with_text(&name, &text))
+}
+
+/// Convert a single value element back to its iCalendar or vCard form.
+fn value_from_xml(element: &Element, value_type: &ValueType, format: Format) -> Option<String> {
+    let text = element.text.as_str();
+
+    Some(match value_type {
+        ValueType::Period => {
+            let start = element.children.iter().find(|c| c.name == "start")?;
+            let end = element
+                .children
+                .iter()
+                .find(|c| c.name == "end" || c.name == "duration")?;
+
+            from_extended(&format!("{}/{}", start.text, end.text), value_type, format)?
+        }
+        ValueType::Recur => {
+            let mut parts: Vec<(String, Vec<String>)> = Vec::new();
+            for part in &element.children {
+                match parts.last_mut() {
+                    Some((name, values)) if *name == part.name => values.push(part.text.clone()),
+                    _ => parts.push((part.name.clone(), vec![part.text.clone()])),
+                }
+            }
+
+            recur_from_parts(&parts)?
+        }
+        ValueType::Date | ValueType::DateTime | ValueType::Time | ValueType::UtcOffset => {
+            from_extended(text, value_type, format)?
+        }
+        ValueType::DateAndOrTime | ValueType::Timestamp if format == Format::Vcard => {
+            from_extended(text, value_type, format)?
+        }
+        ValueType::Boolean => match text.to_lowercase().as_str() {
+            "true" | "1" => String::from("TRUE"),
+            "false" | "0" => String::from("FALSE"),
+            _ => return None,
+        },
+        ValueType::Float => {
+            text.parse::<f64>().ok()?;
+            text.to_string()
+        }
+        ValueType::Integer => {
+            text.parse::<i64>().ok()?;
+            text.to_string()
+        }
+        ValueType::Duration => {
+            parse_duration(text)?;
+            text.to_string()
+        }
+        ValueType::Text => escape_text(text, format),
+        ValueType::Binary
+        | ValueType::CalAddress
+        | ValueType::DateAndOrTime
+        | ValueType::LanguageTag
+        | ValueType::Timestamp
+        | ValueType::Uri
+        | ValueType::Unknown(_) => text.to_string(),
+    })
+}
+
+/// Convert the value of `property`, as a `value_type`, into its value elements.
+fn values_to_xml(
+    property: &Property,
+    value_type: &ValueType,
+    format: Format,
+) -> Result<Vec<Element>, ValueError> {
+    let name = property.name.to_uppercase();
+    let value = property.value.as_deref().unwrap_or_default();
+    let invalid = |raw: &str| ValueError::InvalidValue {
+        line: property.line(),
+        property: property.name.clone(),
+        value_type: value_type.clone(),
+        value: raw.to_string(),
+    };
+    let convert = |raw: &str| value_to_xml(raw, value_type, format).ok_or_else(|| invalid(raw));
+
+    if value.is_empty() {
+        return Ok(vec![Element::new(&type_name(value_type))]);
+    }
+
+    if let Some(names) = structured_components(&name, value_type, format) {
+        let components = split_unescaped(value, ';');
+
+        // A value with more components than known is kept whole.
+        if components.len() > names.len() && !names.is_empty() {
+            return Ok(vec![Element::with_text("unknown", value)]);
+        }
+
+        let mut elements = Vec::new();
+        for (index, component) in components.into_iter().enumerate() {
+            let element_name = names.get(index).unwrap_or(&"text");
+            let values = match format {
+                Format::Vcard if !names.is_empty() => split_unescaped(component, ','),
+                _ => vec![component],
+            };
+
+            for raw in values {
+                let mut element = convert(raw)?;
+                element.name = element_name.to_string();
+                elements.push(element);
+            }
+        }
+
+        return Ok(elements);
+    }
+
+    let raws = match value_type {
+        ValueType::Text if TEXT_LISTS.contains(&name.as_str()) => split_unescaped(value, ','),
+        ValueType::Binary
+        | ValueType::CalAddress
+        | ValueType::LanguageTag
+        | ValueType::Recur
+        | ValueType::Text
+        | ValueType::Unknown(_)
+        | ValueType::Uri => vec![value],
+        _ => value.split(',').collect(),
+    };
+
+    raws.into_iter().map(convert).collect()
+}
+
+/// Convert the value elements of a property, as a `value_type`, into its value.
+fn values_from_xml(
+    property: &Property,
+    elements: &[&Element],
+    value_type: &ValueType,
+    format: Format,
+) -> Result<String, ValueError> {
+    let invalid = |element: &Element| ValueError::InvalidValue {
+        line: None,
+        property: property.name.clone(),
+        value_type: value_type.clone(),
+        value: element.text.clone(),
+    };
+    let convert = |element: &Element| {
+        if element.text.is_empty() && element.children.is_empty() {
+            Ok(String::new())
+        } else {
+            value_from_xml(element, value_type, format).ok_or_else(|| invalid(element))
+        }
+    };
+
+    let names = match structured_components(&property.name, value_type, format) {
+        Some(names) => names,
+        None => {
+            return elements
+                .iter()
+                .map(|element| convert(element))
+                .collect::<Result<Vec<String>, ValueError>>()
+                .map(|values| values.join(","));
+        }
+    };
+
+    // The `;` and `,` separating the components and their values are escaped in each value.
+    let convert_component = |element: &Element| match value_type {
+        ValueType::Text => Ok(escape_component(&element.text)),
+        _ => convert(element),
+    };
+
+    let mut components: Vec<Vec<String>> = Vec::new();
+    for element in elements {
+        let index = match names.iter().position(|name| *name == element.name) {
+            Some(index) => index,
+            None if names.is_empty() => components.len(),
+            None => return Err(invalid(element)),
+        };
+
+        if components.len() <= index {
+            components.resize(index + 1, Vec::new());
+        }
+        components[index].push(convert_component(element)?);
+    }
+
+    Ok(components
+        .iter()
+        .map(|values| values.join(","))
+        .collect::<Vec<String>>()
+        .join(";"))
+}
+
+/// Convert a property into an XML element.
+pub(crate) fn property_to_xml(property: &Property, format: Format) -> Result<Element, XmlError> {
+    let value_type = property.value_type(format);
+    let mut element = Element::new(&property.name.to_lowercase());
+
+    let mut parameters = Element::new("parameters");
+    for (name, values) in property.params.iter().flatten() {
+        let name = name.to_uppercase();
+        if name == "VALUE" {
+            continue;
+        }
+
+        let parameter_type = parameter_type(&name, format);
+        let mut parameter = Element::new(&name.to_lowercase());
+        for value in values {
+            let values = match format {
+                Format::Vcard if VCARD_LIST_PARAMS.contains(&name.as_str()) => {
+                    value.split(',').collect()
+                }
+                _ => vec![value.as_str()],
+            };
+
+            parameter.children.extend(values.into_iter().map(|value| {
+                let value = match parameter_type {
+                    "boolean" => value.to_lowercase(),
+                    _ => value.to_string(),
+                };
+                Element::with_text(parameter_type, &value)
+            }));
+        }
+        parameters.children.push(parameter);
+    }
+    if !parameters.children.is_empty() {
+        element.children.push(parameters);
+    }
+
+    element
+        .children
+        .extend(values_to_xml(property, &value_type, format)?);
+
+    Ok(element)
+}
+
+/// Convert an XML element into a property.
+///
+/// The `VALUE` parameter is added when the type of the value isn't the default one or when
+/// it's required.
+pub(crate) fn property_from_xml(element: &Element, format: Format) -> Result<Property, XmlError> {
+    let mut property = Property::new();
+    property.name = element.name.to_uppercase();
+
+    let mut params = Vec::new();
+    let mut values = Vec::new();
+    for child in &element.children {
+        if child.name != "parameters" {
+            values.push(child);
+            continue;
+        }
+
+        for parameter in &child.children {
+            let name = parameter.name.to_uppercase();
+            if name == "VALUE" {
+                continue;
+            }
+
+            let values = parameter
+                .children
+                .iter()
+                .map(|value| match value.name.as_str() {
+                    "boolean" => value.text.to_uppercase(),
+                    _ => value.text.clone(),
+                })
+                .collect();
+            params.push((name, values));
+        }
+    }
+    if !params.is_empty() {
+        property.params = Some(params);
+    }
+
+    let first = values
+        .first()
+        .ok_or_else(|| XmlError::invalid("property", element))?;
+    let value = if first.name == "unknown" {
+        first.text.clone()
+    } else {
+        let default_type = property.value_type(format);
+        let value_type = match structured_components(&property.name, &default_type, format) {
+            Some(names) if names.contains(&first.name.as_str()) => default_type,
+            Some(names) if names.is_empty() && first.name == "text" => default_type,
+            _ => ValueType::from_name(&first.name),
+        };
+        let value = values_from_xml(&property, &values, &value_type, format)?;
+        add_value_param(&mut property, &value_type, format);
+
+        value
+    };
+
+    // As when parsing, an empty value is missing.
+    if !value.is_empty() {
+        property.value = Some(value);
+    }
+
+    Ok(property)
+}
+
+/// Convert a component into an XML element, holding its properties and, if it has some, its
+/// sub-components.
+pub(crate) fn component_to_xml(
+    name: &str,
+    properties: &[Property],
+    components: Vec<Element>,
+    format: Format,
+) -> Result<Element, XmlError> {
+    let mut element = Element::new(&name.to_lowercase());
+
+    let mut properties_element = Element::new("properties");
+    properties_element.children = properties
+        .iter()
+        .map(|property| property_to_xml(property, format))
+        .collect::<Result<Vec<Element>, XmlError>>()?;
+    element.children.push(properties_element);
+
+    if !components.is_empty() {
+        let mut components_element = Element::new("components");
+        components_element.children = components;
+        element.children.push(components_element);
+    }
+
+    Ok(element)
+}
+
+/// Split an XML component into its name, in uppercase, its properties and its
+/// sub-components, which may be missing.
+pub(crate) fn component_from_xml(
+    element: &Element,
+    format: Format,
+) -> Result<(String, Vec<Property>, &[Element]), XmlError> {
+    let mut properties = Vec::new();
+    let mut components = &[][..];
+
+    for child in &element.children {
+        match child.name.as_str() {
+            "properties" => {
+                for property in &child.children {
+                    properties.push(property_from_xml(property, format)?);
+                }
+            }
+            "components" => components = &child.children,
+            _ => return Err(XmlError::invalid("component", child)),
+        }
+    }
+
+    Ok((element.name.to_uppercase(), properties, components))
+}
+
+pub(crate) fn unknown_to_xml(
+    component: &IcalUnknownComponent,
+    format: Format,
+) -> Result<Element, XmlError> {
+    let components = component
+        .sub_components
+        .iter()
+        .map(|sub_component| unknown_to_xml(sub_component, format))
+        .collect::<Result<Vec<Element>, XmlError>>()?;
+
+    component_to_xml(&component.name, &component.properties, components, format)
+}
+
+pub(crate) fn unknown_from_xml(
+    element: &Element,
+    format: Format,
+) -> Result<IcalUnknownComponent, XmlError> {
+    let (name, properties, components) = component_from_xml(element, format)?;
+
+    let mut component = IcalUnknownComponent::new(&name);
+    component.properties = properties;
+    component.sub_components = components
+        .iter()
+        .map(|sub_component| unknown_from_xml(sub_component, format))
+        .collect::<Result<Vec<IcalUnknownComponent>, XmlError>>()?;
+
+    Ok(component)
+}
+
+#[allow(unused)]
+mod should {
+    use super::*;
+
+    fn property(name: &str, value: &str, params: &[(&str, &str)]) -> Property {
+        let mut property = Property::new();
+        property.name = name.to_string();
+        property.value = Some(value.to_string());
+        if !params.is_empty() {
+            property.params = Some(
+                params
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), vec![v.to_string()]))
+                    .collect(),
+            );
+        }
+
+        property
+    }
+
+    /// Convert `property` into XML, check the written element and convert it back.
+    fn round_trip_in(format: Format, property: Property, expected: &str) {
+        let element = property_to_xml(&property, format).unwrap();
+        let xml = write_document(&element).unwrap();
+        let xml = xml.split_once("?>").unwrap().1;
+        let compact: String = xml.lines().map(str::trim).collect();
+        assert_eq!(compact, expected);
+
+        let parsed = parse_document(xml).unwrap();
+        assert_eq!(property_from_xml(&parsed, format).unwrap(), property);
+    }
+
+    fn round_trip(property: Property, expected: &str) {
+        round_trip_in(Format::Ical, property, expected)
+    }
+
+    #[test]
+    fn convert_the_value_types() {
+        round_trip(
+            property("GEO", "37.386013;-122.082932", &[]),
+            "<geo><latitude>37.386013</latitude><longitude>-122.082932</longitude></geo>",
+        );
+        round_trip(
+            property("REQUEST-STATUS", "2.0;Success", &[]),
+            "<request-status><code>2.0</code><description>Success</description></request-status>",
+        );
+        round_trip(
+            property("CATEGORIES", "Work,Rock\\, Paper", &[]),
+            "<categories><text>Work</text><text>Rock, Paper</text></categories>",
+        );
+        round_trip(
+            property("DTSTART", "20240102T120000", &[("TZID", "Europe/Paris")]),
+            "<dtstart><parameters><tzid><text>Europe/Paris</text></tzid></parameters>\
+             <date-time>2024-01-02T12:00:00</date-time></dtstart>",
+        );
+        round_trip(
+            property("RDATE", "19970308T160000Z/PT8H30M", &[("VALUE", "PERIOD")]),
+            "<rdate><period><start>1997-03-08T16:00:00Z</start>\
+             <duration>PT8H30M</duration></period></rdate>",
+        );
+        round_trip(
+            property(
+                "RRULE",
+                "FREQ=WEEKLY;UNTIL=20240301T000000Z;BYDAY=MO,TH",
+                &[],
+            ),
+            "<rrule><recur><freq>WEEKLY</freq><until>2024-03-01T00:00:00Z</until>\
+             <byday>MO</byday><byday>TH</byday></recur></rrule>",
+        );
+        round_trip(
+            property("ATTENDEE", "mailto:a@example.com", &[("RSVP", "TRUE")]),
+            "<attendee><parameters><rsvp><boolean>true</boolean></rsvp></parameters>\
+             <cal-address>mailto:a@example.com</cal-address></attendee>",
+        );
+        round_trip(
+            property("DESCRIPTION", "<b>1 & 2</b>", &[]),
+            "<description><text>&lt;b&gt;1 &amp; 2&lt;/b&gt;</text></description>",
+        );
+        round_trip(
+            property("X-CUSTOM", "raw", &[("VALUE", "X-TYPE")]),
+            "<x-custom><x-type>raw</x-type></x-custom>",
+        );
+    }
+
+    #[test]
+    fn convert_the_vcard_values() {
+        round_trip_in(
+            Format::Vcard,
+            property("N", "Perreault;Simon;;;ing. jr,M.Sc.", &[]),
+            "<n><surname>Perreault</surname><given>Simon</given><additional/><prefix/>\
+             <suffix>ing. jr</suffix><suffix>M.Sc.</suffix></n>",
+        );
+        round_trip_in(
+            Format::Vcard,
+            property("ORG", "ABC\\, Inc.;North American Division", &[]),
+            "<org><text>ABC, Inc.</text><text>North American Division</text></org>",
+        );
+        round_trip_in(
+            Format::Vcard,
+            property("BDAY", "--0203", &[]),
+            "<bday><date-and-or-time>--02-03</date-and-or-time></bday>",
+        );
+        round_trip_in(
+            Format::Vcard,
+            property(
+                "TEL",
+                "tel:+1-555-555-5555",
+                &[("TYPE", "work"), ("VALUE", "uri")],
+            ),
+            "<tel><parameters><type><text>work</text></type></parameters>\
+             <uri>tel:+1-555-555-5555</uri></tel>",
+        )
+    }
+
+    #[test]
+    fn reject_the_invalid_values() {
+        let element = parse_document("<dtstart><date-time>tomorrow</date-time></dtstart>").unwrap();
+
+        assert!(property_from_xml(&element, Format::Ical).is_err());
+        assert!(property_to_xml(&property("DTSTART", "tomorrow", &[]), Format::Ical).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! xCal ([RFC6321](https://tools.ietf.org/html/rfc6321)), the XML format of iCalendar.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["xcal"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use ical::xml::{from_xcal, to_xcal};
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/ical_example_1.ics")
+//!     .unwrap());
+//!
+//! let calendars = ical::IcalParser::new(buf)
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! let xcal = to_xcal(&calendars).unwrap();
+//!
+//! assert_eq!(to_xcal(&from_xcal(&xcal).unwrap()).unwrap(), xcal);
+//! ```
+
+// Internal mods
+use super::{
+    component_from_xml, component_to_xml, parse_document, unknown_from_xml, unknown_to_xml,
+    write_document, Element, XmlError,
+};
+use crate::parser::ical::component::{
+    IcalAlarm, IcalAvailability, IcalAvailable, IcalCalendar, IcalEvent, IcalFreeBusy, IcalJournal,
+    IcalTimeZone, IcalTimeZoneTransition, IcalTimeZoneTransitionType, IcalTodo,
+};
+use crate::property::Format;
+
+/// The namespace of the xCal elements.
+pub const NAMESPACE: &str = "urn:ietf:params:xml:ns:icalendar-2.0";
+
+/// Convert the calendars into an xCal document.
+pub fn to_xcal(calendars: &[IcalCalendar]) -> Result<String, XmlError> {
+    let mut root = Element::new("icalendar");
+    root.attributes
+        .push((String::from("xmlns"), NAMESPACE.to_string()));
+    root.children = calendars
+        .iter()
+        .map(XcalComponent::to_xml)
+        .collect::<Result<Vec<Element>, XmlError>>()?;
+
+    write_document(&root)
+}
+
+/// Read the calendars of an xCal document.
+pub fn from_xcal(xcal: &str) -> Result<Vec<IcalCalendar>, XmlError> {
+    let root = parse_document(xcal)?;
+    root.expect("icalendar")?;
+
+    root.children.iter().map(XcalComponent::from_xml).collect()
+}
+
+/// Converts a component to and from its xCal element.
+trait XcalComponent: Sized {
+    fn to_xml(&self) -> Result<Element, XmlError>;
+
+    fn from_xml(element: &Element) -> Result<Self, XmlError>;
+}
+
+impl XcalComponent for IcalTimeZoneTransition {
+    fn to_xml(&self) -> Result<Element, XmlError> {
+        let name = match &self.transition {
+            IcalTimeZoneTransitionType::STANDARD => "STANDARD",
+            IcalTimeZoneTransitionType::DAYLIGHT => "DAYLIGHT",
+        };
+        let components = self
+            .unknown_components
+            .iter()
+            .map(|component| unknown_to_xml(component, Format::Ical))
+            .collect::<Result<Vec<Element>, XmlError>>()?;
+
+        component_to_xml(name, &self.properties, components, Format::Ical)
+    }
+
+    fn from_xml(element: &Element) -> Result<IcalTimeZoneTransition, XmlError> {
+        let (name, properties, components) = component_from_xml(element, Format::Ical)?;
+
+        let mut transition = match name.as_str() {
+            "STANDARD" => IcalTimeZoneTransition::new(IcalTimeZoneTransitionType::STANDARD),
+            "DAYLIGHT" => IcalTimeZoneTransition::new(IcalTimeZoneTransitionType::DAYLIGHT),
+            _ => {
+                return Err(XmlError::UnexpectedElement {
+                    expected: String::from("standard or daylight"),
+                    found: element.name.clone(),
+                })
+            }
+        };
+        transition.properties = properties;
+        transition.unknown_components = components
+            .iter()
+            .map(|component| unknown_from_xml(component, Format::Ical))
+            .collect::<Result<_, XmlError>>()?;
+
+        Ok(transition)
+    }
+}
+
+macro_rules! xcal_component {
+    ($struct:ident, $name:literal, $($($sub:literal)|+ => $field:ident),*) => {
+        impl XcalComponent for $struct {
+            fn to_xml(&self) -> Result<Element, XmlError> {
+                let mut components = Vec::new();
+                $(for component in &self.$field {
+                    components.push(component.to_xml()?);
+                })*
+                for component in &self.unknown_components {
+                    components.push(unknown_to_xml(component, Format::Ical)?);
+                }
+
+                component_to_xml($name, &self.properties, components, Format::Ical)
+            }
+
+            fn from_xml(element: &Element) -> Result<$struct, XmlError> {
+                element.expect(&$name.to_lowercase())?;
+                let (_, properties, components) = component_from_xml(element, Format::Ical)?;
+
+                let mut component = $struct::new();
+                component.properties = properties;
+                for sub_component in components {
+                    match sub_component.name.to_uppercase().as_str() {
+                        $($($sub)|+ => component
+                            .$field
+                            .push(XcalComponent::from_xml(sub_component)?),)*
+                        _ => component
+                            .unknown_components
+                            .push(unknown_from_xml(sub_component, Format::Ical)?),
+                    }
+                }
+
+                Ok(component)
+            }
+        }
+    };
+}
+
+xcal_component!(IcalAlarm, "VALARM",);
+xcal_component!(IcalFreeBusy, "VFREEBUSY",);
+xcal_component!(IcalJournal, "VJOURNAL",);
+xcal_component!(IcalEvent, "VEVENT", "VALARM" => alarms);
+xcal_component!(IcalTodo, "VTODO", "VALARM" => alarms);
+xcal_component!(IcalAvailable, "AVAILABLE",);
+xcal_component!(IcalAvailability, "VAVAILABILITY", "AVAILABLE" => available);
+xcal_component!(
+    IcalTimeZone,
+    "VTIMEZONE",
+    "STANDARD" | "DAYLIGHT" => transitions
+);
+xcal_component!(
+    IcalCalendar,
+    "VCALENDAR",
+    "VTIMEZONE" => timezones,
+    "VEVENT" => events,
+    "VALARM" => alarms,
+    "VTODO" => todos,
+    "VJOURNAL" => journals,
+    "VFREEBUSY" => free_busys,
+    "VAVAILABILITY" => availabilities
+);
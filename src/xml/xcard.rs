@@ -0,0 +1,140 @@
+//! xCard ([RFC6351](https://tools.ietf.org/html/rfc6351)), the XML format of vCard.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [dependencies.ical]
+//! version = "0.11.*"
+//! default-features = false
+//! features = ["xcal"]
+//! ```
+//!
+//! ```rust
+//! extern crate ical;
+//!
+//! use ical::xml::{from_xcard, to_xcard};
+//! use std::io::BufReader;
+//! use std::fs::File;
+//!
+//! let buf = BufReader::new(File::open("./tests/ressources/vcard_input.vcf")
+//!     .unwrap());
+//!
+//! let contacts = ical::VcardParser::new(buf)
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! let xcard = to_xcard(&contacts).unwrap();
+//!
+//! assert_eq!(to_xcard(&from_xcard(&xcard).unwrap()).unwrap(), xcard);
+//! ```
+
+// Internal mods
+use super::{
+    parse_document, property_from_xml, property_to_xml, unknown_from_xml, unknown_to_xml,
+    write_document, Element, XmlError,
+};
+use crate::parser::vcard::component::VcardContact;
+use crate::property::Format;
+
+/// The namespace of the xCard elements.
+pub const NAMESPACE: &str = "urn:ietf:params:xml:ns:vcard-4.0";
+
+/// Convert the contacts into an xCard document.
+pub fn to_xcard(contacts: &[VcardContact]) -> Result<String, XmlError> {
+    let mut root = Element::new("vcards");
+    root.attributes
+        .push((String::from("xmlns"), NAMESPACE.to_string()));
+    root.children = contacts
+        .iter()
+        .map(contact_to_xml)
+        .collect::<Result<Vec<Element>, XmlError>>()?;
+
+    write_document(&root)
+}
+
+/// Read the contacts of an xCard document.
+pub fn from_xcard(xcard: &str) -> Result<Vec<VcardContact>, XmlError> {
+    let root = parse_document(xcard)?;
+    root.expect("vcards")?;
+
+    root.children.iter().map(contact_from_xml).collect()
+}
+
+/// Convert a contact into a `vcard` element.
+///
+/// The consecutive properties of a group are enclosed in a `group` element. The unknown
+/// sub-components, which xCard doesn't define, are written as the xCal ones in a `components`
+/// element, only if there are some.
+fn contact_to_xml(contact: &VcardContact) -> Result<Element, XmlError> {
+    let mut element = Element::new("vcard");
+
+    for property in &contact.properties {
+        let property_element = property_to_xml(property, Format::Vcard)?;
+
+        let group = match &property.group {
+            Some(group) => group,
+            None => {
+                element.children.push(property_element);
+                continue;
+            }
+        };
+
+        match element.children.last_mut() {
+            Some(last) if last.name == "group" && last.attribute("name") == Some(group) => {
+                last.children.push(property_element)
+            }
+            _ => {
+                let mut group_element = Element::new("group");
+                group_element
+                    .attributes
+                    .push((String::from("name"), group.clone()));
+                group_element.children.push(property_element);
+                element.children.push(group_element);
+            }
+        }
+    }
+
+    if !contact.unknown_components.is_empty() {
+        let mut components = Element::new("components");
+        components.children = contact
+            .unknown_components
+            .iter()
+            .map(|component| unknown_to_xml(component, Format::Vcard))
+            .collect::<Result<Vec<Element>, XmlError>>()?;
+        element.children.push(components);
+    }
+
+    Ok(element)
+}
+
+fn contact_from_xml(element: &Element) -> Result<VcardContact, XmlError> {
+    element.expect("vcard")?;
+
+    let mut contact = VcardContact::new();
+    for child in &element.children {
+        match child.name.as_str() {
+            "group" => {
+                let group = child
+                    .attribute("name")
+                    .ok_or_else(|| XmlError::invalid("group", child))?;
+
+                for property in &child.children {
+                    let mut property = property_from_xml(property, Format::Vcard)?;
+                    property.group = Some(group.to_string());
+                    contact.properties.push(property);
+                }
+            }
+            "components" => {
+                for component in &child.children {
+                    contact
+                        .unknown_components
+                        .push(unknown_from_xml(component, Format::Vcard)?);
+                }
+            }
+            _ => contact
+                .properties
+                .push(property_from_xml(child, Format::Vcard)?),
+        }
+    }
+
+    Ok(contact)
+}
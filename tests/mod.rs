@@ -68,6 +68,30 @@ pub mod property {
             assert_eq!(output, valids.next().unwrap().unwrap());
         }
     }
+
+    #[test]
+    fn lenient_errors() {
+        use self::ical::property::Severity;
+
+        let input = BufReader::new(File::open("./tests/ressources/property_error.vcf").unwrap());
+
+        let valids =
+            BufReader::new(File::open("./tests/ressources/property_error.res").unwrap()).lines();
+
+        let mut reader = ical::PropertyParser::lenient(ical::LineReader::new(input));
+
+        assert!(reader.next().is_none());
+
+        let diagnostics = reader.take_diagnostics();
+        assert_eq!(diagnostics.len(), 7);
+
+        for (i, (diagnostic, valid)) in diagnostics.iter().zip(valids).enumerate() {
+            assert_eq!(diagnostic.severity, Severity::Error);
+            assert_eq!(diagnostic.line, i + 1);
+            assert_eq!(diagnostic.span.unwrap().start_line, i + 1);
+            assert_eq!(diagnostic.message, valid.unwrap());
+        }
+    }
 }
 
 #[cfg(feature = "line")]
@@ -75,8 +99,7 @@ pub mod line {
     extern crate ical;
 
     use std::fs::File;
-    use std::io::BufRead;
-    use std::io::BufReader;
+    use std::io::{self, BufRead, BufReader, Read};
 
     #[test]
     fn ical() {
@@ -87,7 +110,12 @@ pub mod line {
 
         let reader = ical::LineReader::new(input);
 
-        for line in reader {
+        for res in reader {
+            let line = match res {
+                Ok(res) => res,
+                Err(err) => panic!("Throw error: {}", err),
+            };
+
             let output = format!("{:?}", line);
 
             assert_eq!(output, valids.next().unwrap().unwrap());
@@ -103,12 +131,165 @@ pub mod line {
 
         let reader = ical::LineReader::new(input);
 
-        for line in reader {
+        for res in reader {
+            let line = match res {
+                Ok(res) => res,
+                Err(err) => panic!("Throw error: {}", err),
+            };
+
             let output = format!("{:?}", line);
 
             assert_eq!(output, valids.next().unwrap().unwrap());
         }
     }
+
+    #[test]
+    fn invalid_utf8() {
+        let input: &[u8] = b"BEGIN:VCARD\r\nFN:J. Doe\r\nNOTE:caf\xe9\r\nEND:VCARD\r\n";
+
+        let mut reader = ical::LineReader::new(input);
+
+        assert_eq!(reader.next().unwrap().unwrap().as_str(), "BEGIN:VCARD");
+        assert_eq!(reader.next().unwrap().unwrap().as_str(), "FN:J. Doe");
+
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), 3);
+        assert_eq!(
+            err.to_string(),
+            "Line 3: Invalid UTF-8 sequence at octet 9."
+        );
+
+        let line = reader.next().unwrap().unwrap();
+        assert_eq!((line.as_str(), line.number()), ("END:VCARD", 4));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn utf16() {
+        let input = BufReader::new(File::open("./tests/ressources/vcard_utf16.vcf").unwrap());
+
+        let lines: Vec<String> = ical::LineReader::new(input)
+            .map(|line| line.unwrap().as_str().to_string())
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "BEGIN:VCARD",
+                "VERSION:3.0",
+                "FN:Zoë Ørsted",
+                "N:Ørsted;Zoë;;;",
+                "NOTE:A note folded on two lines",
+                "END:VCARD",
+            ]
+        );
+
+        let input: Vec<u8> = "BEGIN:VCARD\r\nFN:Zoë\r\n"
+            .encode_utf16()
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect();
+        let mut reader = ical::LineReader::new(input.as_slice());
+
+        assert_eq!(reader.next().unwrap().unwrap().as_str(), "BEGIN:VCARD");
+        assert_eq!(reader.next().unwrap().unwrap().as_str(), "FN:Zoë");
+    }
+
+    #[test]
+    fn utf8_bom() {
+        let input: &[u8] = b"\xEF\xBB\xBFBEGIN:VCARD\r\nEND:VCARD\r\n";
+
+        let mut reader = ical::LineReader::new(input);
+
+        assert_eq!(reader.next().unwrap().unwrap().as_str(), "BEGIN:VCARD");
+    }
+
+    #[test]
+    fn quoted_printable_soft_breaks() {
+        let input: &[u8] = b"BEGIN:VCARD\r\nNOTE;QUOTED-PRINTABLE:first=0D=0A=\r\n\
+                             second=\r\n\r\nTEL:+1 555\r\nEND:VCARD\r\n";
+
+        let lines: Vec<_> = ical::LineReader::new(input)
+            .map(|line| line.unwrap())
+            .map(|line| (line.number(), line.as_str().to_string()))
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                (1, String::from("BEGIN:VCARD")),
+                (2, String::from("NOTE;QUOTED-PRINTABLE:first=0D=0Asecond")),
+                (5, String::from("TEL:+1 555")),
+                (6, String::from("END:VCARD")),
+            ]
+        );
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    #[test]
+    fn io_error() {
+        let input = b"BEGIN:VCARD\r\n".chain(FailingReader);
+
+        let mut reader = ical::LineReader::new(BufReader::new(input));
+
+        assert_eq!(reader.next().unwrap().unwrap().as_str(), "BEGIN:VCARD");
+        assert_eq!(
+            reader.next().unwrap().unwrap_err().to_string(),
+            "Line 2: disk on fire"
+        );
+        assert!(reader.next().is_none());
+    }
+}
+
+#[cfg(feature = "charset")]
+pub mod charset {
+    extern crate encoding_rs;
+    extern crate ical;
+
+    use std::fs::File;
+    use std::io::BufReader;
+
+    #[test]
+    fn transcode_vcard_21() {
+        let input = BufReader::new(File::open("./tests/ressources/vcard_charset.vcf").unwrap());
+
+        let line_reader = ical::LineReader::with_fallback(input, encoding_rs::WINDOWS_1252);
+        let contact = ical::VcardParser::from_line_reader(line_reader)
+            .next()
+            .unwrap()
+            .unwrap();
+
+        let values: Vec<_> = contact
+            .properties
+            .iter()
+            .map(|property| property.value.as_deref().unwrap())
+            .collect();
+
+        assert_eq!(
+            values,
+            vec!["2.1", "Müller;Jürgen", "Jürgen Müller €", "Café crème"]
+        );
+    }
+
+    #[test]
+    fn report_unknown_charsets() {
+        let input: &[u8] = b"BEGIN:VCARD\r\nFN;CHARSET=KLINGON:Qapla\xff\r\nEND:VCARD\r\n";
+
+        let mut reader = ical::LineReader::new(input);
+        reader.next();
+
+        assert_eq!(
+            reader.next().unwrap().unwrap_err().to_string(),
+            "Line 2: Unknown charset \"KLINGON\"."
+        );
+        assert!(reader.next().unwrap().is_ok());
+    }
 }
 
 #[cfg(any(feature = "ical", feature = "vcard"))]
@@ -226,12 +407,240 @@ pub mod parser {
             assert_eq!(output, valids.next().unwrap().unwrap());
         }
     }
+
+    #[test]
+    fn vcard_groups() {
+        use self::ical::parser::Component;
+
+        let input = BufReader::new(File::open("./tests/ressources/vcard_group.vcf").unwrap());
+
+        let contact = ical::VcardParser::new(input).next().unwrap().unwrap();
+
+        let email = contact.get_property("EMAIL").unwrap();
+        assert_eq!(email.group.as_deref(), Some("item1"));
+        assert_eq!(email.value.as_deref(), Some("johnny@example.com"));
+
+        let item2: Vec<_> = contact
+            .group("ITEM2")
+            .iter()
+            .map(|p| (p.name.as_str(), p.value.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            item2,
+            vec![("URL", "https://example.com/johnny"), ("X-ABLabel", "blog")]
+        );
+    }
+
+    #[test]
+    fn vcard_caret_encoding() {
+        let input = BufReader::new(File::open("./tests/ressources/vcard_caret.vcf").unwrap());
+
+        let contact = ical::VcardParser::new(input).next().unwrap().unwrap();
+        let param =
+            |index: usize| contact.properties[index].params.as_ref().unwrap()[0].1[0].as_str();
+
+        assert_eq!(param(2), "Ruth, George Herman \"Babe\"");
+        assert_eq!(param(3), "216 E. 46th St.\nNew York, NY");
+        assert_eq!(param(4), "^_^");
+    }
+
+    #[test]
+    fn vcard_21() {
+        let input = BufReader::new(File::open("./tests/ressources/vcard_21.vcf").unwrap());
+
+        let contact = ical::VcardParser::new(input).next().unwrap().unwrap();
+        let property = |name: &str| {
+            contact
+                .properties
+                .iter()
+                .find(|property| property.name == name)
+                .unwrap()
+        };
+
+        assert_eq!(contact.properties.len(), 6);
+        assert_eq!(
+            property("NOTE").text().unwrap(),
+            "Café au lait, deux sucres\r\nMerci =)"
+        );
+        assert_eq!(
+            property("ADR").structured_text()[2..],
+            [
+                vec!["12 rue de la Paix"],
+                vec!["Paris"],
+                vec![""],
+                vec!["75002"],
+                vec!["France"]
+            ]
+        );
+        assert!(property("PHOTO")
+            .decoded_value()
+            .unwrap()
+            .starts_with(b"GIF89a"));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let input: &[u8] = b"BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:caf\xe9\r\n\
+                             END:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let mut reader = ical::IcalParser::new(input);
+
+        match reader.next() {
+            Some(Err(ical::parser::ParserError::Line(err))) => assert_eq!(err.line(), 3),
+            res => panic!("Should return a line error: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn spans() {
+        let input: &[u8] = b"BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Annual\r\n  review\r\n\r\n\
+                             END:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let calendar = ical::IcalParser::new(input).next().unwrap().unwrap();
+        let event = &calendar.events[0];
+
+        let span = event.properties[0].span.unwrap();
+        assert_eq!((span.start_line, span.end_line), (3, 4));
+        assert_eq!(&input[span.start..span.end], b"SUMMARY:Annual\r\n  review");
+
+        let span = event.span.unwrap();
+        assert_eq!((span.start_line, span.end_line), (2, 6));
+        assert_eq!(span.start, 17);
+        assert!(input[..span.end].ends_with(b"\r\nEND:VEVENT"));
+        assert_eq!(calendar.span.unwrap().end, input.len() - 2);
+    }
+
+    #[test]
+    fn error_locations() {
+        use self::ical::parser::ParserError;
+
+        let input: &[u8] = b"BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Review\n";
+        match ical::IcalParser::new(input).next() {
+            Some(Err(err @ ParserError::NotComplete { .. })) => {
+                assert_eq!(err.line(), 2);
+                assert_eq!(err.to_string(), "Line 2: Incomplete object.");
+            }
+            res => panic!("Should return an incomplete object error: {:?}", res),
+        }
+
+        let input: &[u8] = b"\nBEGIN:VCARD\nFN:Alice\nEND:VCARD\n";
+        match ical::IcalParser::new(input).next() {
+            Some(Err(err @ ParserError::MissingHeader { .. })) => assert_eq!(err.line(), 2),
+            res => panic!("Should return a missing header error: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn unknown_components() {
+        let input = BufReader::new(File::open("./tests/ressources/ical_unknown.ics").unwrap());
+
+        let calendar = ical::IcalParser::new(input).next().unwrap().unwrap();
+
+        let location = &calendar.events[0].unknown_components[0];
+        assert_eq!(location.name, "VLOCATION");
+        assert_eq!(location.properties[1].value.as_deref(), Some("Room 3"));
+        assert_eq!(location.span.unwrap().start_line, 9);
+
+        let names: Vec<&str> = calendar
+            .unknown_components
+            .iter()
+            .map(|c| &c.name[..])
+            .collect();
+        assert_eq!(names, ["VPOLL", "X-WR-NOTE"]);
+
+        let poll = &calendar.unknown_components[0];
+        assert_eq!(poll.properties.len(), 4);
+        assert_eq!(poll.sub_components.len(), 2);
+        assert_eq!(poll.sub_components[1].name, "VEVENT");
+        assert_eq!(poll.sub_components[1].properties[1].name, "POLL-ITEM-ID");
+
+        let input: &[u8] =
+            b"BEGIN:VCARD\nFN:Alice\nBEGIN:X-AGENT\nFN:Bob\nEND:X-AGENT\nEND:VCARD\n";
+        let contact = ical::VcardParser::new(input).next().unwrap().unwrap();
+        assert_eq!(contact.properties.len(), 1);
+        assert_eq!(contact.unknown_components[0].name, "X-AGENT");
+    }
+
+    #[test]
+    fn lenient_vcard() {
+        use std::io::Read;
+
+        let mut input = String::from("BEGIN:VCARD\nVERSION:4.0\n");
+        File::open("./tests/ressources/property_error.vcf")
+            .unwrap()
+            .read_to_string(&mut input)
+            .unwrap();
+        input.push_str("FN:Alice\nEND:VCARD\n");
+
+        let mut reader = ical::VcardParser::new(input.as_bytes()).lenient();
+
+        let (contact, diagnostics) = reader.next().unwrap();
+        let names: Vec<&str> = contact.properties.iter().map(|p| &p.name[..]).collect();
+        assert_eq!(names, ["VERSION", "FN"]);
+        assert_eq!(contact.span.unwrap().end_line, 11);
+
+        // The errors of the fixture, two lines further.
+        let lines: Vec<usize> = diagnostics.iter().map(|d| d.line).collect();
+        assert_eq!(lines, [3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(diagnostics[0].message, "Line 3: Missing a closing quote.");
+
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn lenient_ical() {
+        use self::ical::parser::Severity;
+
+        let input: &[u8] = b"PRODID:junk\nBEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Review\n\
+                             BEGIN:VFOO\nBEGIN:VBAR\nEND:VBAR\nEND:VFOO\nDTSTART\nEND:VEVENT\n\
+                             BEGIN:VTODO\nSUMMARY:Write notes\n";
+
+        let mut reader = ical::IcalParser::new(input).lenient();
+
+        let (calendar, diagnostics) = reader.next().unwrap();
+        assert_eq!(calendar.events[0].properties.len(), 1);
+        assert_eq!(calendar.events[0].unknown_components[0].name, "VFOO");
+        assert_eq!(calendar.events[0].span.unwrap().end_line, 10);
+        assert_eq!(calendar.todos[0].properties.len(), 1);
+        assert_eq!(calendar.todos[0].span.unwrap().start_line, 11);
+
+        let summary: Vec<(Severity, usize, String)> = diagnostics
+            .into_iter()
+            .map(|d| (d.severity, d.line, d.message))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Severity::Error, 1, String::from("Line 1: Missing header.")),
+                (
+                    Severity::Error,
+                    9,
+                    String::from("Line 9: Missing property name.")
+                ),
+                (
+                    Severity::Warning,
+                    11,
+                    String::from("Line 11: Incomplete object.")
+                ),
+                (
+                    Severity::Warning,
+                    2,
+                    String::from("Line 2: Incomplete object.")
+                ),
+            ]
+        );
+
+        assert!(reader.next().is_none());
+    }
 }
 
 #[cfg(all(feature = "ical", feature = "generator"))]
 pub mod generator {
     extern crate ical;
+    extern crate proptest;
     use self::ical::generator::Emitter;
+    use self::ical::property::Property;
+    use self::proptest::prelude::*;
     use std::fs::File;
     use std::io::BufRead;
     use std::io::BufReader;
@@ -267,4 +676,1044 @@ pub mod generator {
 
         assert_eq!(&generated, &original);
     }
+
+    #[test]
+    fn generate_availability() {
+        let filename = "./tests/ressources/ical_availability.ics";
+
+        let original = std::fs::read_to_string(filename).unwrap();
+
+        let input = BufReader::new(File::open(filename).unwrap());
+        let mut reader = ical::IcalParser::new(input);
+        let generated = reader.next().unwrap().ok().unwrap().generate();
+
+        assert_eq!(&generated, &original);
+    }
+
+    #[test]
+    fn generate_unknown_components() {
+        let filename = "./tests/ressources/ical_unknown.ics";
+
+        let original = std::fs::read_to_string(filename).unwrap();
+
+        let input = BufReader::new(File::open(filename).unwrap());
+        let mut reader = ical::IcalParser::new(input);
+        let generated = reader.next().unwrap().ok().unwrap().generate();
+
+        assert_eq!(&generated, &original);
+    }
+
+    #[test]
+    fn generate_vcard_caret_encoding() {
+        let filename = "./tests/ressources/vcard_caret.vcf";
+
+        let original = std::fs::read_to_string(filename).unwrap();
+
+        let input = BufReader::new(File::open(filename).unwrap());
+        let mut reader = ical::VcardParser::new(input);
+        let generated = reader.next().unwrap().ok().unwrap().generate();
+
+        assert_eq!(&generated, &original);
+    }
+
+    #[test]
+    fn generate_vcard_groups() {
+        let filename = "./tests/ressources/vcard_group.vcf";
+
+        let original = std::fs::read_to_string(filename).unwrap();
+
+        let input = BufReader::new(File::open(filename).unwrap());
+        let mut reader = ical::VcardParser::new(input);
+        let generated = reader.next().unwrap().ok().unwrap().generate();
+
+        assert_eq!(&generated, &original);
+    }
+
+    fn property() -> impl Strategy<Value = Property> {
+        // Favor the delimiters and the characters the caret encoding has to escape.
+        let param = (
+            "X-[A-Z0-9-]{1,8}",
+            prop::collection::vec("([:;,\"^ a-z]|\n|[^\\x00-\\x1f\\x7f]){0,20}", 1..15),
+        );
+
+        (
+            "[A-Z][A-Z0-9-]{0,10}",
+            prop::option::of("[a-z0-9][a-z0-9-]{0,5}"),
+            prop::option::of(prop::collection::vec(param, 1..5)),
+            prop::option::of("[^\\x00-\\x1f\\x7f]{0,80}[^\\x00-\\x20\\x7f]"),
+        )
+            .prop_map(|(name, group, params, value)| Property {
+                name,
+                group,
+                params,
+                value,
+                span: None,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_parameters(property in property()) {
+            let generated = property.generate();
+
+            let mut reader = ical::PropertyParser::from_reader(generated.as_bytes());
+            let parsed = reader.next().unwrap().unwrap();
+
+            prop_assert_eq!(Property { span: None, ..parsed }, property);
+            prop_assert!(reader.next().is_none());
+        }
+    }
+}
+
+#[cfg(all(feature = "ical", feature = "vcard", feature = "generator"))]
+pub mod document {
+    extern crate ical;
+    use self::ical::generator::Emitter;
+    use self::ical::parser::document::DocumentComponent;
+    use self::ical::property::Property;
+
+    fn parse(filename: &str) -> (Vec<u8>, Vec<DocumentComponent>) {
+        let original = std::fs::read(filename).unwrap();
+        let documents = ical::DocumentParser::new(&original[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        (original, documents)
+    }
+
+    #[test]
+    fn write_back_unchanged() {
+        for filename in &[
+            "./tests/ressources/document.ics",
+            "./tests/ressources/ical_example_1.ics",
+            "./tests/ressources/o365_meeting.ics",
+            "./tests/ressources/vcard_21.vcf",
+            "./tests/ressources/vcard_input.vcf",
+            "./tests/ressources/vcard_lowercase.vcf",
+        ] {
+            let (original, documents) = parse(filename);
+
+            let mut generated = Vec::new();
+            for document in &documents {
+                document.write_to(&mut generated).unwrap();
+            }
+
+            assert_eq!(
+                String::from_utf8_lossy(&generated),
+                String::from_utf8_lossy(&original),
+                "{}",
+                filename
+            );
+        }
+    }
+
+    #[test]
+    fn keep_order() {
+        let (_, documents) = parse("./tests/ressources/document.ics");
+        let calendar = &documents[0];
+
+        assert_eq!(calendar.name(), "VCALENDAR");
+        assert_eq!(
+            calendar
+                .components()
+                .map(|component| component.name())
+                .collect::<Vec<_>>(),
+            vec!["VEVENT", "VTIMEZONE", "VTODO"]
+        );
+        assert_eq!(calendar.properties().last().unwrap().name, "X-WR-CALNAME");
+    }
+
+    #[test]
+    fn find_the_properties_in_any_case() {
+        let (_, mut documents) = parse("./tests/ressources/vcard_lowercase.vcf");
+        let contact = &mut documents[0];
+
+        assert_eq!(
+            contact.get_property("FN").unwrap().value.as_deref(),
+            Some("Alice Foobar")
+        );
+        contact.get_property_mut("N").unwrap().value = Some(String::from("Foobar;Alicia"));
+
+        assert_eq!(contact.get_property("n").unwrap().name, "n");
+        assert!(contact.generate().contains("n:Foobar;Alicia\r\n"));
+    }
+
+    #[test]
+    fn write_changes_only() {
+        let (original, mut documents) = parse("./tests/ressources/document.ics");
+        let calendar = &mut documents[0];
+
+        {
+            let mut components = calendar.components_mut();
+
+            let event = components.next().unwrap();
+            event.add_property(Property {
+                name: String::from("LOCATION"),
+                value: Some(String::from("Room 3")),
+                ..Property::new()
+            });
+
+            let todo = components.nth(1).unwrap();
+            todo.get_property_mut("SUMMARY").unwrap().value =
+                Some(String::from("Submit the 2006 tax return"));
+        }
+
+        let expected = String::from_utf8(original)
+            .unwrap()
+            .replace(
+                "HUMAN RESOURCES\r\nEND:VEVENT",
+                "HUMAN RESOURCES\r\nLOCATION:Room 3\r\nEND:VEVENT",
+            )
+            .replace(
+                "SUMMARY:Submit Quebec Income Tax Return for 2006\n",
+                "SUMMARY:Submit the 2006 tax return\r\n",
+            );
+
+        assert_eq!(calendar.generate(), expected);
+    }
+}
+
+#[cfg(feature = "recurrence")]
+pub mod recurrence {
+    extern crate chrono;
+    extern crate ical;
+
+    use self::chrono::NaiveDate;
+    use self::ical::recurrence::Recurrent;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    #[test]
+    fn expand_event_and_todo() {
+        let input = BufReader::new(File::open("./tests/ressources/ical_recurrence.ics").unwrap());
+
+        let calendar = ical::IcalParser::new(input).next().unwrap().unwrap();
+
+        let start = NaiveDate::from_ymd_opt(2023, 1, 9)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let end = NaiveDate::from_ymd_opt(2023, 1, 21)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        let events = calendar.events[0]
+            .occurrences(&calendar, start, end)
+            .unwrap()
+            .map(|o| format!("{} {}", o.start, o.recurrence_id))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                "2023-01-09 09:30:00 2023-01-09 09:30:00",
+                "2023-01-17 14:00:00 2023-01-16 09:30:00",
+                "2023-01-19 09:30:00 2023-01-19 09:30:00",
+            ]
+        );
+
+        let end = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        let todos = calendar.todos[0]
+            .occurrences(&calendar, start, end)
+            .unwrap()
+            .map(|o| format!("{} {}", o.start.date(), o.all_day))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            todos,
+            vec!["2023-01-31 true", "2023-02-28 true", "2023-03-31 true"]
+        );
+    }
+
+    #[test]
+    fn available_intervals() {
+        let input = BufReader::new(File::open("./tests/ressources/ical_availability.ics").unwrap());
+
+        let calendar = ical::IcalParser::new(input).next().unwrap().unwrap();
+        assert_eq!(calendar.availabilities[0].available.len(), 2);
+
+        let start = NaiveDate::from_ymd_opt(2006, 10, 5)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let end = NaiveDate::from_ymd_opt(2006, 10, 9)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+
+        let intervals = calendar
+            .available_intervals(start, end)
+            .unwrap()
+            .into_iter()
+            .map(|range| format!("{} {}", range.start, range.end))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            intervals,
+            vec![
+                "2006-10-05 09:00:00 2006-10-05 17:00:00",
+                "2006-10-06 09:00:00 2006-10-06 12:00:00",
+                "2006-10-09 09:00:00 2006-10-09 12:00:00",
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "timezone")]
+pub mod timezone {
+    extern crate ical;
+
+    use self::ical::parser::Component;
+    use self::ical::value::{Format, Value};
+    use std::fs::File;
+    use std::io::BufReader;
+
+    #[test]
+    fn resolve_with_calendar_vtimezone() {
+        let input = BufReader::new(File::open("./tests/ressources/ical_example_1.ics").unwrap());
+
+        let calendar = ical::IcalParser::new(input).next().unwrap().unwrap();
+        let dtstart = calendar.events[0].get_property("DTSTART").unwrap();
+
+        let utc = match dtstart.typed_value(Format::Ical).unwrap() {
+            Value::DateTime(date_time) => calendar.to_utc(&date_time).unwrap(),
+            value => panic!("unexpected value {:?}", value),
+        };
+
+        assert_eq!(utc.to_string(), "2021-05-27 08:30:00 UTC");
+    }
+}
+
+#[cfg(feature = "tzdb")]
+pub mod tzdb {
+    extern crate ical;
+
+    use self::ical::parser::Component;
+    use self::ical::value::{Format, Value};
+    use std::fs::File;
+    use std::io::BufReader;
+
+    #[test]
+    fn resolve_without_vtimezone() {
+        let input = BufReader::new(File::open("./tests/ressources/ical_tzdb.ics").unwrap());
+
+        let calendar = ical::IcalParser::new(input).next().unwrap().unwrap();
+        assert!(calendar.timezones.is_empty());
+
+        let starts = calendar
+            .events
+            .iter()
+            .map(|event| {
+                match event
+                    .get_property("DTSTART")
+                    .unwrap()
+                    .typed_value(Format::Ical)
+                    .unwrap()
+                {
+                    Value::DateTime(date_time) => calendar.to_utc(&date_time).unwrap().to_string(),
+                    value => panic!("unexpected value {:?}", value),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            starts,
+            vec!["2023-07-01 08:00:00 UTC", "2023-01-01 09:00:00 UTC"]
+        );
+    }
+}
+
+#[cfg(feature = "jcal")]
+pub mod jcal {
+    extern crate ical;
+    extern crate serde_json;
+    use self::ical::json::Jcal;
+    use self::ical::parser::ical::component::IcalCalendar;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn parse(filename: &str) -> IcalCalendar {
+        let input = BufReader::new(File::open(filename).unwrap());
+
+        ical::IcalParser::new(input).next().unwrap().unwrap()
+    }
+
+    fn example() -> serde_json::Value {
+        let input = File::open("./tests/ressources/jcal_example.json").unwrap();
+
+        serde_json::from_reader(input).unwrap()
+    }
+
+    #[test]
+    fn to_jcal() {
+        let calendar = parse("./tests/ressources/jcal_example.ics");
+
+        assert_eq!(calendar.to_jcal().unwrap(), example());
+    }
+
+    #[test]
+    fn from_jcal() {
+        let calendar = IcalCalendar::from_jcal(&example()).unwrap();
+
+        assert_eq!(calendar.events.len(), 2);
+        assert_eq!(calendar.timezones[0].transitions.len(), 2);
+        assert_eq!(calendar.to_jcal().unwrap(), example());
+    }
+
+    #[test]
+    fn unexpected_component() {
+        let error = IcalCalendar::from_jcal_str(r#"["vcard", [], []]"#).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Expected a VCALENDAR component, found VCARD."
+        );
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn round_trip() {
+        use self::ical::generator::Emitter;
+
+        for filename in &[
+            "./tests/ressources/jcal_example.ics",
+            "./tests/ressources/ical_example_1.ics",
+            "./tests/ressources/ical_availability.ics",
+            "./tests/ressources/ical_unknown.ics",
+            "./tests/ressources/o365_meeting.ics",
+            "./tests/ressources/sabre_test.ics",
+        ] {
+            let calendar = parse(filename);
+            let jcal = calendar.to_jcal_string().unwrap();
+
+            assert_eq!(
+                IcalCalendar::from_jcal_str(&jcal).unwrap().generate(),
+                calendar.generate(),
+                "{}",
+                filename
+            );
+        }
+    }
+}
+
+#[cfg(feature = "jcard")]
+pub mod jcard {
+    extern crate ical;
+    extern crate serde_json;
+    use self::ical::json::Jcard;
+    use self::ical::parser::vcard::component::VcardContact;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn parse(filename: &str) -> Vec<VcardContact> {
+        let input = BufReader::new(File::open(filename).unwrap());
+
+        ical::VcardParser::new(input).map(Result::unwrap).collect()
+    }
+
+    fn example() -> serde_json::Value {
+        let input = File::open("./tests/ressources/jcard_example.json").unwrap();
+
+        serde_json::from_reader(input).unwrap()
+    }
+
+    #[test]
+    fn to_jcard() {
+        // The example of RFC 6350, converted in RFC 7095 appendix B.1, where the time zone
+        // is a `utc-offset` and the anniversary has seconds.
+        let contact = &parse("./tests/ressources/jcard_example.vcf")[0];
+
+        assert_eq!(contact.to_jcard().unwrap(), example());
+    }
+
+    #[test]
+    fn from_jcard() {
+        let contact = VcardContact::from_jcard(&example()).unwrap();
+
+        assert_eq!(contact.properties.len(), 17);
+        assert_eq!(contact.to_jcard().unwrap(), example());
+    }
+
+    #[test]
+    fn unexpected_component() {
+        let error = VcardContact::from_jcard_str(r#"["vcalendar", [], []]"#).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Expected a VCARD component, found VCALENDAR."
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for filename in &[
+            "./tests/ressources/jcard_example.vcf",
+            "./tests/ressources/vcard_input.vcf",
+            "./tests/ressources/vcard_group.vcf",
+            "./tests/ressources/vcard_lowercase.vcf",
+        ] {
+            for contact in parse(filename) {
+                let jcard = contact.to_jcard().unwrap();
+                let converted = VcardContact::from_jcard(&jcard).unwrap();
+
+                assert_eq!(converted.to_jcard().unwrap(), jcard, "{}", filename);
+            }
+        }
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn generate_the_same_contact() {
+        use self::ical::generator::Emitter;
+
+        let contact = &parse("./tests/ressources/jcard_example.vcf")[0];
+        let converted = VcardContact::from_jcard(&contact.to_jcard().unwrap()).unwrap();
+
+        // The `VALUE` parameters come last, the one of the default type is dropped and the
+        // quotes of the lists are lost.
+        let expected = contact
+            .generate()
+            .replace(
+                "TEL;VALUE=uri;TYPE=\"work,voice\";PREF=1:",
+                "TEL;TYPE=work,voice;PREF=1;VALUE=uri:",
+            )
+            .replace(
+                "TEL;VALUE=uri;TYPE=\"work,cell,voice,video,text\":",
+                "TEL;TYPE=work,cell,voice,video,text;VALUE=uri:",
+            )
+            .replace("KEY;TYPE=work;VALUE=uri:", "KEY;TYPE=work:");
+        assert_eq!(converted.generate(), expected);
+    }
+}
+
+#[cfg(feature = "jscalendar")]
+pub mod jscalendar {
+    extern crate ical;
+    extern crate serde_json;
+    use self::ical::json::jscalendar::{from_group, to_group, Unsupported};
+    use self::ical::json::JsCalendar;
+    use self::ical::parser::ical::component::{IcalCalendar, IcalEvent, IcalTodo};
+    use self::ical::parser::Component;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn parse(filename: &str) -> IcalCalendar {
+        let input = BufReader::new(File::open(filename).unwrap());
+
+        ical::IcalParser::new(input).next().unwrap().unwrap()
+    }
+
+    fn example() -> serde_json::Value {
+        let input = File::open("./tests/ressources/jscalendar_example.json").unwrap();
+
+        serde_json::from_reader(input).unwrap()
+    }
+
+    #[test]
+    fn to_jscalendar_group() {
+        let calendar = parse("./tests/ressources/jscalendar_example.ics");
+        let group = to_group(&calendar).unwrap();
+
+        assert_eq!(group.result, example());
+        let unsupported: Vec<String> = group.unsupported.iter().map(|u| u.to_string()).collect();
+        assert_eq!(
+            unsupported,
+            vec![
+                "Unsupported X-WR-CALNAME property in VCALENDAR.",
+                "Unsupported COMMENT property in VEVENT.",
+                "Unsupported X-FLOOR parameter of ATTENDEE in VEVENT.",
+                "Unsupported REPEAT property in VALARM.",
+                "Unsupported DURATION property in VALARM.",
+                "Unsupported DTSTAMP property in VTODO.",
+                "Unsupported value \"AUDIO\" of ACTION in VALARM.",
+                "Unsupported VTIMEZONE component.",
+                "Unsupported VJOURNAL component.",
+            ]
+        );
+    }
+
+    #[test]
+    fn from_jscalendar_group() {
+        let converted = from_group(&example()).unwrap();
+        let calendar = converted.result;
+
+        assert!(converted.unsupported.is_empty());
+        assert_eq!(
+            calendar.get_property("METHOD").unwrap().value,
+            Some(String::from("REQUEST"))
+        );
+        assert_eq!(calendar.events.len(), 2);
+        assert_eq!(calendar.todos.len(), 1);
+
+        let event = &calendar.events[0];
+        let values = |name: &str| -> Vec<String> {
+            event
+                .properties
+                .iter()
+                .filter(|p| p.name == name)
+                .map(|p| p.value.clone().unwrap())
+                .collect()
+        };
+        assert_eq!(values("DURATION"), vec!["PT1H30M"]);
+        assert_eq!(values("EXDATE"), vec!["20240115T100000"]);
+        assert_eq!(values("ORGANIZER"), vec!["mailto:alice@example.com"]);
+        assert_eq!(values("ATTENDEE").len(), 4);
+        assert_eq!(
+            calendar.events[1]
+                .get_property("RECURRENCE-ID")
+                .unwrap()
+                .value,
+            Some(String::from("20240122T100000"))
+        );
+
+        assert_eq!(to_group(&calendar).unwrap().result, example());
+    }
+
+    #[test]
+    fn convert_a_single_event() {
+        let calendar = parse("./tests/ressources/ical_recurrence.ics");
+        let event = calendar.events[0].to_jscalendar(&calendar).unwrap().result;
+
+        assert_eq!(event["recurrenceOverrides"].as_object().unwrap().len(), 2);
+        assert_eq!(
+            event["recurrenceOverrides"]["2023-01-16T09:30:00"]["start"],
+            "2023-01-17T14:00:00"
+        );
+
+        let events = IcalEvent::from_jscalendar(&event).unwrap().result;
+        assert_eq!(events.len(), 2);
+        assert!(events[1].get_property("RECURRENCE-ID").is_some());
+
+        let error = IcalTodo::from_jscalendar(&event).unwrap_err();
+        assert_eq!(error.to_string(), "Expected a Task component, found Event.");
+    }
+
+    #[test]
+    fn identify_the_participants_in_any_case() {
+        let content = "BEGIN:VCALENDAR\n\
+                       BEGIN:VEVENT\n\
+                       UID:party@example.com\n\
+                       DTSTART:20240301T180000\n\
+                       ORGANIZER:mailto:Ünïcode@example.com\n\
+                       ATTENDEE:mailto:ÜNÏCODE@example.com\n\
+                       ATTENDEE;PARTSTAT=X-MAYBE;RSVP=TRUE;\n \
+                        DELEGATED-TO=\"mailto:ünïcode@example.com\":mailto:bob@example.com\n\
+                       END:VEVENT\n\
+                       END:VCALENDAR\n";
+        let calendar = ical::IcalParser::new(content.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+
+        let event = calendar.events[0].to_jscalendar(&calendar).unwrap().result;
+        let participants = event["participants"].as_object().unwrap();
+
+        assert_eq!(participants.len(), 2);
+        assert_eq!(participants["1"]["roles"]["owner"], true);
+        assert_eq!(participants["1"]["roles"]["attendee"], true);
+        assert_eq!(participants["2"]["delegatedTo"]["1"], true);
+        assert_eq!(participants["2"]["expectReply"], true);
+    }
+
+    #[test]
+    fn report_the_unknown_properties() {
+        let json = serde_json::json!({
+            "@type": "Task",
+            "uid": "task@example.com",
+            "title": "Task",
+            "mayInviteSelf": true,
+        });
+        let converted = IcalTodo::from_jscalendar(&json).unwrap();
+
+        assert_eq!(converted.result[0].properties.len(), 2);
+        assert_eq!(
+            converted.unsupported,
+            vec![Unsupported::Property {
+                component: String::from("Task"),
+                name: String::from("mayInviteSelf"),
+            }]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for filename in &[
+            "./tests/ressources/jscalendar_example.ics",
+            "./tests/ressources/jcal_example.ics",
+            "./tests/ressources/ical_recurrence.ics",
+            "./tests/ressources/ical_example_1.ics",
+            "./tests/ressources/ical_input.ics",
+            "./tests/ressources/o365_meeting.ics",
+            "./tests/ressources/sabre_test.ics",
+        ] {
+            let group = to_group(&parse(filename)).unwrap().result;
+            let calendar = from_group(&group).unwrap().result;
+
+            assert_eq!(to_group(&calendar).unwrap().result, group, "{}", filename);
+        }
+    }
+}
+
+#[cfg(feature = "jscontact")]
+pub mod jscontact {
+    extern crate ical;
+    extern crate serde_json;
+    use self::ical::json::JsContact;
+    use self::ical::parser::vcard::component::VcardContact;
+    use self::ical::parser::Component;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn parse(filename: &str) -> Vec<VcardContact> {
+        let input = BufReader::new(File::open(filename).unwrap());
+
+        ical::VcardParser::new(input).map(Result::unwrap).collect()
+    }
+
+    fn example() -> serde_json::Value {
+        let input = File::open("./tests/ressources/jscontact_example.json").unwrap();
+
+        serde_json::from_reader(input).unwrap()
+    }
+
+    #[test]
+    fn to_jscontact() {
+        let contact = &parse("./tests/ressources/jcard_example.vcf")[0];
+
+        assert_eq!(contact.to_jscontact().unwrap(), example());
+    }
+
+    #[test]
+    fn from_jscontact() {
+        let contact = VcardContact::from_jscontact(&example()).unwrap();
+        let values = |name: &str| -> Vec<String> {
+            contact
+                .properties
+                .iter()
+                .filter(|p| p.name == name)
+                .map(|p| p.value.clone().unwrap())
+                .collect()
+        };
+
+        assert_eq!(contact.properties.len(), 17);
+        assert_eq!(values("N"), vec!["Perreault;Simon;;;ing. jr,M.Sc."]);
+        assert_eq!(
+            values("ADR"),
+            vec![";Suite D2-630;2875 Laurier;Quebec;QC;G1V 2M2;Canada"]
+        );
+        assert!(values("JSPROP").is_empty());
+        assert_eq!(contact.to_jscontact().unwrap(), example());
+    }
+
+    #[test]
+    fn keep_the_jscontact_properties() {
+        // The example of RFC 9553 section 2.2.1, with a name component vCard can't hold.
+        let card = serde_json::json!({
+            "@type": "Card",
+            "version": "1.0",
+            "uid": "22B2C7DF-9120-4969-8460-05956FE6B065",
+            "kind": "individual",
+            "name": {
+                "components": [
+                    {"kind": "given", "value": "Vincent"},
+                    {"kind": "separator", "value": " "},
+                    {"kind": "surname", "value": "van Gogh"}
+                ],
+                "isOrdered": true
+            },
+            "organizations": {
+                "o1": {"name": "ABC, Inc.", "units": [{"name": "North American Division"}]}
+            },
+            "phones": {
+                "tel0": {"number": "+1-555-555-5555", "features": {"voice": true}, "label": "cell"}
+            }
+        });
+        let contact = VcardContact::from_jscontact(&card).unwrap();
+
+        assert_eq!(
+            contact.get_property("ORG").unwrap().value,
+            Some(String::from("ABC\\, Inc.;North American Division"))
+        );
+        let pointers: Vec<String> = contact
+            .properties
+            .iter()
+            .filter(|p| p.name == "JSPROP")
+            .map(|p| p.params.as_ref().unwrap()[0].1[0].clone())
+            .collect();
+        assert_eq!(
+            pointers,
+            vec!["name/components", "name/isOrdered", "phones/tel0/label"]
+        );
+        assert_eq!(contact.to_jscontact().unwrap(), card);
+    }
+
+    #[test]
+    fn unexpected_component() {
+        let error = VcardContact::from_jscontact_str(r#"{"@type": "Group"}"#).unwrap_err();
+
+        assert_eq!(error.to_string(), "Expected a Card component, found Group.");
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn round_trip() {
+        use self::ical::generator::Emitter;
+
+        // The lines of the properties, whose order isn't kept.
+        let lines = |contact: &VcardContact| {
+            let mut lines: Vec<String> = contact.properties.iter().map(|p| p.generate()).collect();
+            lines.sort();
+            lines
+        };
+
+        // The changes of the properties converted back and forth: the contacts come back in
+        // vCard 4.0, the names and the lists are normalized.
+        let cases: &[(&str, &[(&str, &str)])] = &[
+            (
+                "./tests/ressources/jcard_example.vcf",
+                &[
+                    ("KEY;TYPE=work;VALUE=uri:", "KEY;TYPE=work:"),
+                    ("TYPE=\"work,voice\"", "TYPE=work,voice"),
+                    (
+                        "TYPE=\"work,cell,voice,video,text\"",
+                        "TYPE=work,voice,cell,video,text",
+                    ),
+                ],
+            ),
+            ("./tests/ressources/vcard_input.vcf", &[]),
+            (
+                "./tests/ressources/vcard_group.vcf",
+                &[
+                    ("VERSION:3.0", "VERSION:4.0"),
+                    ("TYPE=INTERNET;TYPE=HOME", "TYPE=home,internet"),
+                    ("TYPE=INTERNET", "TYPE=internet"),
+                    ("X-ABLabel", "X-ABLABEL"),
+                ],
+            ),
+            (
+                "./tests/ressources/vcard_lowercase.vcf",
+                &[
+                    ("version:", "VERSION:"),
+                    ("email;", "EMAIL;"),
+                    ("fn:", "FN:"),
+                    ("n:Foobar;Alice", "N:Foobar;Alice;;;"),
+                ],
+            ),
+            ("./tests/ressources/vcard_caret.vcf", &[]),
+        ];
+
+        for (filename, changes) in cases {
+            for contact in parse(filename) {
+                let card = contact.to_jscontact().unwrap();
+                let converted = VcardContact::from_jscontact(&card).unwrap();
+
+                assert_eq!(converted.to_jscontact().unwrap(), card, "{}", filename);
+
+                let mut expected: Vec<String> = lines(&contact)
+                    .into_iter()
+                    .map(|line| {
+                        changes
+                            .iter()
+                            .fold(line, |line, (from, to)| line.replace(from, to))
+                    })
+                    .collect();
+                expected.sort();
+                assert_eq!(lines(&converted), expected, "{}", filename);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "xcal")]
+pub mod xcal {
+    extern crate ical;
+    use self::ical::parser::ical::component::IcalCalendar;
+    use self::ical::xml::{from_xcal, to_xcal};
+    use std::fs::{read_to_string, File};
+    use std::io::BufReader;
+
+    fn parse(filename: &str) -> Vec<IcalCalendar> {
+        let input = BufReader::new(File::open(filename).unwrap());
+
+        ical::IcalParser::new(input).map(Result::unwrap).collect()
+    }
+
+    fn example() -> String {
+        read_to_string("./tests/ressources/xcal_example.xml").unwrap()
+    }
+
+    #[test]
+    fn to_xcal_document() {
+        // The example of RFC 6321 appendix B.2, also used by jCal.
+        let calendars = parse("./tests/ressources/jcal_example.ics");
+
+        assert_eq!(to_xcal(&calendars).unwrap(), example());
+    }
+
+    #[test]
+    fn from_xcal_document() {
+        let calendars = from_xcal(&example()).unwrap();
+
+        assert_eq!(calendars.len(), 1);
+        assert_eq!(calendars[0].events.len(), 2);
+        assert_eq!(calendars[0].timezones[0].transitions.len(), 2);
+        assert_eq!(to_xcal(&calendars).unwrap(), example());
+    }
+
+    #[test]
+    fn unexpected_element() {
+        let error = from_xcal("<vcards><vcard/></vcards>").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Expected a icalendar element, found vcards."
+        );
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn round_trip() {
+        use self::ical::generator::Emitter;
+
+        for filename in &[
+            "./tests/ressources/jcal_example.ics",
+            "./tests/ressources/ical_example_1.ics",
+            "./tests/ressources/ical_example_2.ics",
+            "./tests/ressources/ical_availability.ics",
+            "./tests/ressources/ical_recurrence.ics",
+            "./tests/ressources/ical_unknown.ics",
+            "./tests/ressources/o365_meeting.ics",
+            "./tests/ressources/sabre_test.ics",
+        ] {
+            let calendars = parse(filename);
+            let converted = from_xcal(&to_xcal(&calendars).unwrap()).unwrap();
+
+            assert_eq!(converted.len(), calendars.len(), "{}", filename);
+            for (converted, calendar) in converted.iter().zip(&calendars) {
+                assert_eq!(converted.generate(), calendar.generate(), "{}", filename);
+            }
+        }
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn generate_the_same_values() {
+        use self::ical::generator::Emitter;
+
+        let calendars = parse("./tests/ressources/ical_input.ics");
+        let converted = from_xcal(&to_xcal(&calendars).unwrap()).unwrap();
+
+        // The special characters of the text values come back escaped.
+        assert_eq!(converted.len(), calendars.len());
+        for (converted, calendar) in converted.iter().zip(&calendars) {
+            let expected = calendar
+                .generate()
+                .replace(":FOO;BAR", ":FOO\\;BAR")
+                .replace(":BAZ;BAR", ":BAZ\\;BAR")
+                .replace("value, but", "value\\, but");
+            assert_eq!(converted.generate(), expected);
+        }
+    }
+}
+
+#[cfg(feature = "xcal")]
+pub mod xcard {
+    extern crate ical;
+    use self::ical::parser::vcard::component::VcardContact;
+    use self::ical::xml::{from_xcard, to_xcard};
+    use std::fs::{read_to_string, File};
+    use std::io::BufReader;
+
+    fn parse(filename: &str) -> Vec<VcardContact> {
+        let input = BufReader::new(File::open(filename).unwrap());
+
+        ical::VcardParser::new(input).map(Result::unwrap).collect()
+    }
+
+    fn example() -> String {
+        read_to_string("./tests/ressources/xcard_example.xml").unwrap()
+    }
+
+    #[test]
+    fn to_xcard_document() {
+        let contacts = parse("./tests/ressources/jcard_example.vcf");
+
+        assert_eq!(to_xcard(&contacts).unwrap(), example());
+    }
+
+    #[test]
+    fn from_xcard_document() {
+        let contacts = from_xcard(&example()).unwrap();
+
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].properties.len(), 17);
+        assert_eq!(to_xcard(&contacts).unwrap(), example());
+    }
+
+    #[test]
+    fn keep_the_groups() {
+        let contacts = parse("./tests/ressources/vcard_group.vcf");
+        let xcard = to_xcard(&contacts).unwrap();
+        let groups = |contact: &VcardContact| -> Vec<Option<String>> {
+            contact.properties.iter().map(|p| p.group.clone()).collect()
+        };
+
+        assert_eq!(xcard.matches("<group name=\"").count(), 2);
+        assert_eq!(
+            groups(&from_xcard(&xcard).unwrap()[0]),
+            groups(&contacts[0])
+        );
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn round_trip() {
+        use self::ical::generator::Emitter;
+
+        for filename in &[
+            "./tests/ressources/jcard_example.vcf",
+            "./tests/ressources/vcard_input.vcf",
+            "./tests/ressources/vcard_group.vcf",
+            "./tests/ressources/vcard_lowercase.vcf",
+        ] {
+            // The names of the properties, the quotes of the lists and the `VALUE`
+            // parameters are normalized by the first conversion.
+            let contacts = from_xcard(&to_xcard(&parse(filename)).unwrap()).unwrap();
+            let converted = from_xcard(&to_xcard(&contacts).unwrap()).unwrap();
+
+            assert_eq!(converted.len(), contacts.len(), "{}", filename);
+            for (converted, contact) in converted.iter().zip(&contacts) {
+                assert_eq!(converted.generate(), contact.generate(), "{}", filename);
+            }
+        }
+    }
+
+    #[cfg(feature = "generator")]
+    #[test]
+    fn generate_the_same_contact() {
+        use self::ical::generator::Emitter;
+
+        let contacts = parse("./tests/ressources/jcard_example.vcf");
+        let converted = from_xcard(&to_xcard(&contacts).unwrap()).unwrap();
+
+        // The `VALUE` parameters come last, the one of the default type is dropped and the
+        // quotes of the lists are lost.
+        let expected = contacts[0]
+            .generate()
+            .replace(
+                "TEL;VALUE=uri;TYPE=\"work,voice\";PREF=1:",
+                "TEL;TYPE=work,voice;PREF=1;VALUE=uri:",
+            )
+            .replace(
+                "TEL;VALUE=uri;TYPE=\"work,cell,voice,video,text\":",
+                "TEL;TYPE=work,cell,voice,video,text;VALUE=uri:",
+            )
+            .replace("KEY;TYPE=work;VALUE=uri:", "KEY;TYPE=work:");
+        assert_eq!(converted[0].generate(), expected);
+    }
 }
@@ -1,35 +1,35 @@
 IcalCalendar {
  properties: [
-Property { name: "PRODID", params: None, value: Some("-//Microsoft Corporation//Outlook 16.0 MIMEDIR//EN") },
- Property { name: "VERSION", params: None, value: Some("2.0") },
- Property { name: "METHOD", params: None, value: Some("PUBLISH") },
- Property { name: "X-MS-OLK-FORCEINSPECTOROPEN", params: None, value: Some("TRUE") }],
+Property { name: "PRODID", group: None, params: None, value: Some("-//Microsoft Corporation//Outlook 16.0 MIMEDIR//EN"), span: Some(Span { start_line: 2, end_line: 2, start: 16, end: 73 }) },
+ Property { name: "VERSION", group: None, params: None, value: Some("2.0"), span: Some(Span { start_line: 3, end_line: 3, start: 74, end: 85 }) },
+ Property { name: "METHOD", group: None, params: None, value: Some("PUBLISH"), span: Some(Span { start_line: 4, end_line: 4, start: 86, end: 100 }) },
+ Property { name: "X-MS-OLK-FORCEINSPECTOROPEN", group: None, params: None, value: Some("TRUE"), span: Some(Span { start_line: 5, end_line: 5, start: 101, end: 133 }) }],
  events: [
 IcalEvent { properties: [
-Property { name: "CLASS", params: None, value: Some("PUBLIC") },
- Property { name: "CREATED", params: None, value: Some("20210511T063845Z") },
- Property { name: "DESCRIPTION", params: None, value: Some("Einwahldaten folgen in der Veranstaltungswoche \\nSeminartitel:Software-QS-Cast - Application Performance Monitoring\\nDatum: 27.Mai 2021\\nUhrzeit: 10:30 - ca.12:00 Uhr  \\n \\n") },
- Property { name: "DTEND", params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T120000") },
- Property { name: "DTSTAMP", params: None, value: Some("20210511T063845Z") },
- Property { name: "DTSTART", params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T103000") },
- Property { name: "LAST-MODIFIED", params: None, value: Some("20210511T063845Z") },
- Property { name: "PRIORITY", params: None, value: Some("5") }, Property { name: "SEQUENCE", params: None, value: Some("0") },
- Property { name: "SUMMARY", params: Some([("LANGUAGE", ["de"])]), value: Some("Software-QS-Cast Application Performance Monitoring") },
- Property { name: "TRANSP", params: None, value: Some("OPAQUE") },
- Property { name: "UID", params: None, value: Some("040000008200E000*************00800000000*****************00000000000000010000000********************************") },
- Property { name: "X-MICROSOFT-CDO-BUSYSTATUS", params: None, value: Some("BUSY") },
- Property { name: "X-MICROSOFT-CDO-IMPORTANCE", params: None, value: Some("1") },
- Property { name: "X-MICROSOFT-DISALLOW-COUNTER", params: None, value: Some("FALSE") },
- Property { name: "X-MS-OLK-CONFTYPE", params: None, value: Some("0") }],
+Property { name: "CLASS", group: None, params: None, value: Some("PUBLIC"), span: Some(Span { start_line: 22, end_line: 22, start: 461, end: 473 }) },
+ Property { name: "CREATED", group: None, params: None, value: Some("20210511T063845Z"), span: Some(Span { start_line: 23, end_line: 23, start: 474, end: 498 }) },
+ Property { name: "DESCRIPTION", group: None, params: None, value: Some("Einwahldaten folgen in der Veranstaltungswoche \\nSeminartitel: Software-QS-Cast - Application Performance Monitoring\\nDatum: 27.Mai 2021\\nUhrzeit: 10:30 - ca.12:00 Uhr  \\n \\n"), span: Some(Span { start_line: 24, end_line: 26, start: 499, end: 689 }) },
+ Property { name: "DTEND", group: None, params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T120000"), span: Some(Span { start_line: 27, end_line: 27, start: 690, end: 742 }) },
+ Property { name: "DTSTAMP", group: None, params: None, value: Some("20210511T063845Z"), span: Some(Span { start_line: 28, end_line: 28, start: 743, end: 767 }) },
+ Property { name: "DTSTART", group: None, params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T103000"), span: Some(Span { start_line: 29, end_line: 29, start: 768, end: 822 }) },
+ Property { name: "LAST-MODIFIED", group: None, params: None, value: Some("20210511T063845Z"), span: Some(Span { start_line: 30, end_line: 30, start: 823, end: 853 }) },
+ Property { name: "PRIORITY", group: None, params: None, value: Some("5"), span: Some(Span { start_line: 31, end_line: 31, start: 854, end: 864 }) }, Property { name: "SEQUENCE", group: None, params: None, value: Some("0"), span: Some(Span { start_line: 32, end_line: 32, start: 865, end: 875 }) },
+ Property { name: "SUMMARY", group: None, params: Some([("LANGUAGE", ["de"])]), value: Some("Software-QS-Cast Application Performance Monitoring"), span: Some(Span { start_line: 33, end_line: 33, start: 876, end: 947 }) },
+ Property { name: "TRANSP", group: None, params: None, value: Some("OPAQUE"), span: Some(Span { start_line: 34, end_line: 34, start: 948, end: 961 }) },
+ Property { name: "UID", group: None, params: None, value: Some("040000008200E000*************00800000000*****************00000000000000010000000********************************"), span: Some(Span { start_line: 35, end_line: 36, start: 962, end: 1080 }) },
+ Property { name: "X-MICROSOFT-CDO-BUSYSTATUS", group: None, params: None, value: Some("BUSY"), span: Some(Span { start_line: 37, end_line: 37, start: 1081, end: 1112 }) },
+ Property { name: "X-MICROSOFT-CDO-IMPORTANCE", group: None, params: None, value: Some("1"), span: Some(Span { start_line: 38, end_line: 38, start: 1113, end: 1141 }) },
+ Property { name: "X-MICROSOFT-DISALLOW-COUNTER", group: None, params: None, value: Some("FALSE"), span: Some(Span { start_line: 39, end_line: 39, start: 1142, end: 1176 }) },
+ Property { name: "X-MS-OLK-CONFTYPE", group: None, params: None, value: Some("0"), span: Some(Span { start_line: 40, end_line: 40, start: 1177, end: 1196 }) }],
  alarms: [
 IcalAlarm { properties: [
-Property { name: "TRIGGER", params: None, value: Some("-PT15M") },
- Property { name: "ACTION", params: None, value: Some("DISPLAY") },
- Property { name: "DESCRIPTION", params: None, value: Some("Reminder") }] }] }],
+Property { name: "TRIGGER", group: None, params: None, value: Some("-PT15M"), span: Some(Span { start_line: 42, end_line: 42, start: 1210, end: 1224 }) },
+ Property { name: "ACTION", group: None, params: None, value: Some("DISPLAY"), span: Some(Span { start_line: 43, end_line: 43, start: 1225, end: 1239 }) },
+ Property { name: "DESCRIPTION", group: None, params: None, value: Some("Reminder"), span: Some(Span { start_line: 44, end_line: 44, start: 1240, end: 1260 }) }], unknown_components: [], span: Some(Span { start_line: 41, end_line: 45, start: 1197, end: 1271 }) }], unknown_components: [], span: Some(Span { start_line: 21, end_line: 46, start: 448, end: 1282 }) }],
  alarms: [], todos: [], journals: [], free_busys: [],
- timezones: [IcalTimeZone { properties: [Property { name: "TZID", params: None, value: Some("W. Europe Standard Time") }],
- transitions: [IcalTimeZoneTransition { transition: STANDARD, properties: [Property { name: "DTSTART", params: None, value: Some("16011028T030000") },
- Property { name: "RRULE", params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10") },
- Property { name: "TZOFFSETFROM", params: None, value: Some("+0200") },
- Property { name: "TZOFFSETTO", params: None, value: Some("+0100") }] },
- IcalTimeZoneTransition { transition: DAYLIGHT, properties: [Property { name: "DTSTART", params: None, value: Some("16010325T020000") }, Property { name: "RRULE", params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3") }, Property { name: "TZOFFSETFROM", params: None, value: Some("+0100") }, Property { name: "TZOFFSETTO", params: None, value: Some("+0200") }] }] }] }
+ timezones: [IcalTimeZone { properties: [Property { name: "TZID", group: None, params: None, value: Some("W. Europe Standard Time"), span: Some(Span { start_line: 7, end_line: 7, start: 150, end: 178 }) }],
+ transitions: [IcalTimeZoneTransition { transition: STANDARD, properties: [Property { name: "DTSTART", group: None, params: None, value: Some("16011028T030000"), span: Some(Span { start_line: 9, end_line: 9, start: 194, end: 217 }) },
+ Property { name: "RRULE", group: None, params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10"), span: Some(Span { start_line: 10, end_line: 10, start: 218, end: 257 }) },
+ Property { name: "TZOFFSETFROM", group: None, params: None, value: Some("+0200"), span: Some(Span { start_line: 11, end_line: 11, start: 258, end: 276 }) },
+ Property { name: "TZOFFSETTO", group: None, params: None, value: Some("+0100"), span: Some(Span { start_line: 12, end_line: 12, start: 277, end: 293 }) }], unknown_components: [], span: Some(Span { start_line: 8, end_line: 13, start: 179, end: 306 }) },
+ IcalTimeZoneTransition { transition: DAYLIGHT, properties: [Property { name: "DTSTART", group: None, params: None, value: Some("16010325T020000"), span: Some(Span { start_line: 15, end_line: 15, start: 322, end: 345 }) }, Property { name: "RRULE", group: None, params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3"), span: Some(Span { start_line: 16, end_line: 16, start: 346, end: 384 }) }, Property { name: "TZOFFSETFROM", group: None, params: None, value: Some("+0100"), span: Some(Span { start_line: 17, end_line: 17, start: 385, end: 403 }) }, Property { name: "TZOFFSETTO", group: None, params: None, value: Some("+0200"), span: Some(Span { start_line: 18, end_line: 18, start: 404, end: 420 }) }], unknown_components: [], span: Some(Span { start_line: 14, end_line: 19, start: 307, end: 433 }) }], unknown_components: [], span: Some(Span { start_line: 6, end_line: 20, start: 134, end: 447 }) }], availabilities: [], unknown_components: [], span: Some(Span { start_line: 1, end_line: 47, start: 0, end: 1296 }) }
@@ -1,35 +1,35 @@
 IcalCalendar {
  properties: [
-Property { name: "PRODID", params: None, value: Some("-//Microsoft Corporation//Outlook 16.0 MIMEDIR//EN") },
- Property { name: "VERSION", params: None, value: Some("2.0") },
- Property { name: "METHOD", params: None, value: Some("PUBLISH") },
- Property { name: "X-MS-OLK-FORCEINSPECTOROPEN", params: None, value: Some("TRUE") }],
+Property { name: "PRODID", group: None, params: None, value: Some("-//Microsoft Corporation//Outlook 16.0 MIMEDIR//EN"), span: Some(Span { start_line: 2, end_line: 2, start: 17, end: 74 }) },
+ Property { name: "VERSION", group: None, params: None, value: Some("2.0"), span: Some(Span { start_line: 3, end_line: 3, start: 76, end: 87 }) },
+ Property { name: "METHOD", group: None, params: None, value: Some("PUBLISH"), span: Some(Span { start_line: 4, end_line: 4, start: 89, end: 103 }) },
+ Property { name: "X-MS-OLK-FORCEINSPECTOROPEN", group: None, params: None, value: Some("TRUE"), span: Some(Span { start_line: 5, end_line: 5, start: 105, end: 137 }) }],
  events: [
 IcalEvent { properties: [
-Property { name: "CLASS", params: None, value: Some("PUBLIC") },
- Property { name: "CREATED", params: None, value: Some("20210511T063845Z") },
- Property { name: "DESCRIPTION", params: None, value: Some("Einwahldaten folgen in der Veranstaltungswoche \\nSeminartitel:Software-QS-Cast - Application Performance Monitoring\\nDatum: 27.Mai 2021\\nUhrzeit: 10:30 - ca.12:00 Uhr  \\n \\n") },
- Property { name: "DTEND", params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T120000") },
- Property { name: "DTSTAMP", params: None, value: Some("20210511T063845Z") },
- Property { name: "DTSTART", params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T103000") },
- Property { name: "LAST-MODIFIED", params: None, value: Some("20210511T063845Z") },
- Property { name: "PRIORITY", params: None, value: Some("5") }, Property { name: "SEQUENCE", params: None, value: Some("0") },
- Property { name: "SUMMARY", params: Some([("LANGUAGE", ["de"])]), value: Some("Software-QS-Cast Application Performance Monitoring") },
- Property { name: "TRANSP", params: None, value: Some("OPAQUE") },
- Property { name: "UID", params: None, value: Some("040000008200E000*************00800000000*****************00000000000000010000000********************************") },
- Property { name: "X-MICROSOFT-CDO-BUSYSTATUS", params: None, value: Some("BUSY") },
- Property { name: "X-MICROSOFT-CDO-IMPORTANCE", params: None, value: Some("1") },
- Property { name: "X-MICROSOFT-DISALLOW-COUNTER", params: None, value: Some("FALSE") },
- Property { name: "X-MS-OLK-CONFTYPE", params: None, value: Some("0") }],
+Property { name: "CLASS", group: None, params: None, value: Some("PUBLIC"), span: Some(Span { start_line: 22, end_line: 22, start: 482, end: 494 }) },
+ Property { name: "CREATED", group: None, params: None, value: Some("20210511T063845Z"), span: Some(Span { start_line: 23, end_line: 23, start: 496, end: 520 }) },
+ Property { name: "DESCRIPTION", group: None, params: None, value: Some("Einwahldaten folgen in der Veranstaltungswoche \\nSeminartitel: Software-QS-Cast - Application Performance Monitoring\\nDatum: 27.Mai 2021\\nUhrzeit: 10:30 - ca.12:00 Uhr  \\n \\n"), span: Some(Span { start_line: 24, end_line: 26, start: 522, end: 714 }) },
+ Property { name: "DTEND", group: None, params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T120000"), span: Some(Span { start_line: 27, end_line: 27, start: 716, end: 768 }) },
+ Property { name: "DTSTAMP", group: None, params: None, value: Some("20210511T063845Z"), span: Some(Span { start_line: 28, end_line: 28, start: 770, end: 794 }) },
+ Property { name: "DTSTART", group: None, params: Some([("TZID", ["W. Europe Standard Time"])]), value: Some("20210527T103000"), span: Some(Span { start_line: 29, end_line: 29, start: 796, end: 850 }) },
+ Property { name: "LAST-MODIFIED", group: None, params: None, value: Some("20210511T063845Z"), span: Some(Span { start_line: 30, end_line: 30, start: 852, end: 882 }) },
+ Property { name: "PRIORITY", group: None, params: None, value: Some("5"), span: Some(Span { start_line: 31, end_line: 31, start: 884, end: 894 }) }, Property { name: "SEQUENCE", group: None, params: None, value: Some("0"), span: Some(Span { start_line: 32, end_line: 32, start: 896, end: 906 }) },
+ Property { name: "SUMMARY", group: None, params: Some([("LANGUAGE", ["de"])]), value: Some("Software-QS-Cast Application Performance Monitoring"), span: Some(Span { start_line: 33, end_line: 33, start: 908, end: 979 }) },
+ Property { name: "TRANSP", group: None, params: None, value: Some("OPAQUE"), span: Some(Span { start_line: 34, end_line: 34, start: 981, end: 994 }) },
+ Property { name: "UID", group: None, params: None, value: Some("040000008200E000*************00800000000*****************00000000000000010000000********************************"), span: Some(Span { start_line: 35, end_line: 36, start: 996, end: 1115 }) },
+ Property { name: "X-MICROSOFT-CDO-BUSYSTATUS", group: None, params: None, value: Some("BUSY"), span: Some(Span { start_line: 37, end_line: 37, start: 1117, end: 1148 }) },
+ Property { name: "X-MICROSOFT-CDO-IMPORTANCE", group: None, params: None, value: Some("1"), span: Some(Span { start_line: 38, end_line: 38, start: 1150, end: 1178 }) },
+ Property { name: "X-MICROSOFT-DISALLOW-COUNTER", group: None, params: None, value: Some("FALSE"), span: Some(Span { start_line: 39, end_line: 39, start: 1180, end: 1214 }) },
+ Property { name: "X-MS-OLK-CONFTYPE", group: None, params: None, value: Some("0"), span: Some(Span { start_line: 40, end_line: 40, start: 1216, end: 1235 }) }],
  alarms: [
 IcalAlarm { properties: [
-Property { name: "TRIGGER", params: None, value: Some("-PT15M") },
- Property { name: "ACTION", params: None, value: Some("DISPLAY") },
- Property { name: "DESCRIPTION", params: None, value: Some("Reminder") }] }] }],
+Property { name: "TRIGGER", group: None, params: None, value: Some("-PT15M"), span: Some(Span { start_line: 42, end_line: 42, start: 1251, end: 1265 }) },
+ Property { name: "ACTION", group: None, params: None, value: Some("DISPLAY"), span: Some(Span { start_line: 43, end_line: 43, start: 1267, end: 1281 }) },
+ Property { name: "DESCRIPTION", group: None, params: None, value: Some("Reminder"), span: Some(Span { start_line: 44, end_line: 44, start: 1283, end: 1303 }) }], unknown_components: [], span: Some(Span { start_line: 41, end_line: 45, start: 1237, end: 1315 }) }], unknown_components: [], span: Some(Span { start_line: 21, end_line: 46, start: 468, end: 1327 }) }],
  alarms: [], todos: [], journals: [], free_busys: [],
- timezones: [IcalTimeZone { properties: [Property { name: "TZID", params: None, value: Some("W. Europe Standard Time") }],
- transitions: [IcalTimeZoneTransition { transition: STANDARD, properties: [Property { name: "DTSTART", params: None, value: Some("16011028T030000") },
- Property { name: "RRULE", params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10") },
- Property { name: "TZOFFSETFROM", params: None, value: Some("+0200") },
- Property { name: "TZOFFSETTO", params: None, value: Some("+0100") }] },
- IcalTimeZoneTransition { transition: DAYLIGHT, properties: [Property { name: "DTSTART", params: None, value: Some("16010325T020000") }, Property { name: "RRULE", params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3") }, Property { name: "TZOFFSETFROM", params: None, value: Some("+0100") }, Property { name: "TZOFFSETTO", params: None, value: Some("+0200") }] }] }] }
+ timezones: [IcalTimeZone { properties: [Property { name: "TZID", group: None, params: None, value: Some("W. Europe Standard Time"), span: Some(Span { start_line: 7, end_line: 7, start: 156, end: 184 }) }],
+ transitions: [IcalTimeZoneTransition { transition: STANDARD, properties: [Property { name: "DTSTART", group: None, params: None, value: Some("16011028T030000"), span: Some(Span { start_line: 9, end_line: 9, start: 202, end: 225 }) },
+ Property { name: "RRULE", group: None, params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10"), span: Some(Span { start_line: 10, end_line: 10, start: 227, end: 266 }) },
+ Property { name: "TZOFFSETFROM", group: None, params: None, value: Some("+0200"), span: Some(Span { start_line: 11, end_line: 11, start: 268, end: 286 }) },
+ Property { name: "TZOFFSETTO", group: None, params: None, value: Some("+0100"), span: Some(Span { start_line: 12, end_line: 12, start: 288, end: 304 }) }], unknown_components: [], span: Some(Span { start_line: 8, end_line: 13, start: 186, end: 318 }) },
+ IcalTimeZoneTransition { transition: DAYLIGHT, properties: [Property { name: "DTSTART", group: None, params: None, value: Some("16010325T020000"), span: Some(Span { start_line: 15, end_line: 15, start: 336, end: 359 }) }, Property { name: "RRULE", group: None, params: None, value: Some("FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3"), span: Some(Span { start_line: 16, end_line: 16, start: 361, end: 399 }) }, Property { name: "TZOFFSETFROM", group: None, params: None, value: Some("+0100"), span: Some(Span { start_line: 17, end_line: 17, start: 401, end: 419 }) }, Property { name: "TZOFFSETTO", group: None, params: None, value: Some("+0200"), span: Some(Span { start_line: 18, end_line: 18, start: 421, end: 437 }) }], unknown_components: [], span: Some(Span { start_line: 14, end_line: 19, start: 320, end: 451 }) }], unknown_components: [], span: Some(Span { start_line: 6, end_line: 20, start: 139, end: 466 }) }], availabilities: [], unknown_components: [], span: Some(Span { start_line: 1, end_line: 47, start: 0, end: 1342 }) }
@@ -1,40 +1,40 @@
-Line { inner: "BEGIN:VCALENDAR", number: 3 }
-Line { inner: "CALSCALE:GREGORIAN", number: 4 }
-Line { inner: "PRODID:-//Example Inc.//Example Calendar//EN", number: 5 }
-Line { inner: "VERSION:2.0", number: 6 }
-Line { inner: "BEGIN:VEVENT", number: 7 }
-Line { inner: "DTSTAMP:20080205T191224Z", number: 8 }
-Line { inner: "DTSTART;VALUE=DATE:20081006", number: 9 }
-Line { inner: "SUMMARY:Planning meeting", number: 10 }
-Line { inner: "UID:4088E990AD89CB3DBB484909", number: 11 }
-Line { inner: "BEGIN:VALARM", number: 12 }
-Line { inner: "SUMMARY:escaped\\, comma and\\; semicolon\\nnewline", number: 13 }
-Line { inner: "END:VALARM", number: 14 }
-Line { inner: "END:VEVENT", number: 15 }
-Line { inner: "END:VCALENDAR", number: 16 }
-Line { inner: "BEGIN:VCALENDAR", number: 19 }
-Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\",\"mailto:foo8@bar\";CN=\"Foo, Bar\":mailto:foo1@bar", number: 20 }
-Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\",\"mailto:foo8@bar\";CN=\"Foo; Bar\":mailto:foo2@bar", number: 22 }
-Line { inner: "ATTENDEE;CN=\"Foo, Bar\":mailto:foo3@bar", number: 24 }
-Line { inner: "ATTENDEE;CN=\"Foo; Bar\":mailto:foo4@bar", number: 25 }
-Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\";CN=\"Foo, Bar\":mailto:foo5@bar", number: 26 }
-Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\";CN=\"Foo; Bar\":mailto:foo6@bar", number: 27 }
-Line { inner: "ATTENDEE;ROLE=\"REQ-PARTICIPANT;foo\";DELEGATED-FROM=\"mailto:bar@baz.com\";PARTSTAT=ACCEPTED;RSVP=TRUE:mailto:foo@bar.com", number: 28 }
-Line { inner: "X-FOO;PARAM1=VAL1:FOO;BAR", number: 30 }
-Line { inner: "X-FOO2;PARAM1=VAL1;PARAM2=VAL2:FOO;BAR", number: 31 }
-Line { inner: "X-BAR;PARAM1=\"VAL1:FOO\":BAZ;BAR", number: 32 }
-Line { inner: "X-BAZ;PARAM1=\"VAL1:FOO\";PARAM2=VAL2:BAZ;BAR", number: 33 }
-Line { inner: "X-BAZ2;PARAM1=VAL1;PARAM2=\"VAL2:FOO\":BAZ;BAR", number: 34 }
-Line { inner: "END:VCALENDAR", number: 35 }
-Line { inner: "BEGIN:VCALENDAR", number: 38 }
-Line { inner: "CALSCALE:GREGORIAN", number: 39 }
-Line { inner: "PRODID:-//Example Inc.//Example Calendar//EN", number: 40 }
-Line { inner: "VERSION:2.0", number: 41 }
-Line { inner: "BEGIN:VEVENT", number: 42 }
-Line { inner: "DTSTAMP:20080205T191224Z", number: 43 }
-Line { inner: "DTSTART;VALUE=DATE:20081006", number: 44 }
-Line { inner: "SUMMARY:Missing description value, but includes header", number: 45 }
-Line { inner: "DESCRIPTION:", number: 46 }
-Line { inner: "UID:4088E990AD89CB3DBB484909", number: 47 }
-Line { inner: "END:VEVENT", number: 48 }
-Line { inner: "END:VCALENDAR", number: 49 }
+Line { inner: "BEGIN:VCALENDAR", number: 3, span: Span { start_line: 3, end_line: 3, start: 2, end: 17 } }
+Line { inner: "CALSCALE:GREGORIAN", number: 4, span: Span { start_line: 4, end_line: 4, start: 18, end: 36 } }
+Line { inner: "PRODID:-//Example Inc.//Example Calendar//EN", number: 5, span: Span { start_line: 5, end_line: 5, start: 37, end: 81 } }
+Line { inner: "VERSION:2.0", number: 6, span: Span { start_line: 6, end_line: 6, start: 82, end: 93 } }
+Line { inner: "BEGIN:VEVENT", number: 7, span: Span { start_line: 7, end_line: 7, start: 94, end: 106 } }
+Line { inner: "DTSTAMP:20080205T191224Z", number: 8, span: Span { start_line: 8, end_line: 8, start: 107, end: 131 } }
+Line { inner: "DTSTART;VALUE=DATE:20081006", number: 9, span: Span { start_line: 9, end_line: 9, start: 132, end: 159 } }
+Line { inner: "SUMMARY:Planning meeting", number: 10, span: Span { start_line: 10, end_line: 10, start: 160, end: 184 } }
+Line { inner: "UID:4088E990AD89CB3DBB484909", number: 11, span: Span { start_line: 11, end_line: 11, start: 185, end: 213 } }
+Line { inner: "BEGIN:VALARM", number: 12, span: Span { start_line: 12, end_line: 12, start: 214, end: 226 } }
+Line { inner: "SUMMARY:escaped\\, comma and\\; semicolon\\nnewline", number: 13, span: Span { start_line: 13, end_line: 13, start: 227, end: 275 } }
+Line { inner: "END:VALARM", number: 14, span: Span { start_line: 14, end_line: 14, start: 276, end: 286 } }
+Line { inner: "END:VEVENT", number: 15, span: Span { start_line: 15, end_line: 15, start: 287, end: 297 } }
+Line { inner: "END:VCALENDAR", number: 16, span: Span { start_line: 16, end_line: 16, start: 298, end: 311 } }
+Line { inner: "BEGIN:VCALENDAR", number: 19, span: Span { start_line: 19, end_line: 19, start: 314, end: 329 } }
+Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\",\"mailto:foo8@bar\";CN=\"Foo, Bar\":mailto:foo1@bar", number: 20, span: Span { start_line: 20, end_line: 21, start: 330, end: 419 } }
+Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\",\"mailto:foo8@bar\";CN=\"Foo; Bar\":mailto:foo2@bar", number: 22, span: Span { start_line: 22, end_line: 23, start: 420, end: 509 } }
+Line { inner: "ATTENDEE;CN=\"Foo, Bar\":mailto:foo3@bar", number: 24, span: Span { start_line: 24, end_line: 24, start: 510, end: 548 } }
+Line { inner: "ATTENDEE;CN=\"Foo; Bar\":mailto:foo4@bar", number: 25, span: Span { start_line: 25, end_line: 25, start: 549, end: 587 } }
+Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\";CN=\"Foo, Bar\":mailto:foo5@bar", number: 26, span: Span { start_line: 26, end_line: 26, start: 588, end: 657 } }
+Line { inner: "ATTENDEE;DELEGATED-TO=\"mailto:foo7@bar\";CN=\"Foo; Bar\":mailto:foo6@bar", number: 27, span: Span { start_line: 27, end_line: 27, start: 658, end: 727 } }
+Line { inner: "ATTENDEE;ROLE=\"REQ-PARTICIPANT;foo\";DELEGATED-FROM=\"mailto:bar@baz.com\";PARTSTAT=ACCEPTED;RSVP=TRUE:mailto:foo@bar.com", number: 28, span: Span { start_line: 28, end_line: 29, start: 728, end: 848 } }
+Line { inner: "X-FOO;PARAM1=VAL1:FOO;BAR", number: 30, span: Span { start_line: 30, end_line: 30, start: 849, end: 874 } }
+Line { inner: "X-FOO2;PARAM1=VAL1;PARAM2=VAL2:FOO;BAR", number: 31, span: Span { start_line: 31, end_line: 31, start: 875, end: 913 } }
+Line { inner: "X-BAR;PARAM1=\"VAL1:FOO\":BAZ;BAR", number: 32, span: Span { start_line: 32, end_line: 32, start: 914, end: 945 } }
+Line { inner: "X-BAZ;PARAM1=\"VAL1:FOO\";PARAM2=VAL2:BAZ;BAR", number: 33, span: Span { start_line: 33, end_line: 33, start: 946, end: 989 } }
+Line { inner: "X-BAZ2;PARAM1=VAL1;PARAM2=\"VAL2:FOO\":BAZ;BAR", number: 34, span: Span { start_line: 34, end_line: 34, start: 990, end: 1034 } }
+Line { inner: "END:VCALENDAR", number: 35, span: Span { start_line: 35, end_line: 35, start: 1035, end: 1048 } }
+Line { inner: "BEGIN:VCALENDAR", number: 38, span: Span { start_line: 38, end_line: 38, start: 1051, end: 1066 } }
+Line { inner: "CALSCALE:GREGORIAN", number: 39, span: Span { start_line: 39, end_line: 39, start: 1067, end: 1085 } }
+Line { inner: "PRODID:-//Example Inc.//Example Calendar//EN", number: 40, span: Span { start_line: 40, end_line: 40, start: 1086, end: 1130 } }
+Line { inner: "VERSION:2.0", number: 41, span: Span { start_line: 41, end_line: 41, start: 1131, end: 1142 } }
+Line { inner: "BEGIN:VEVENT", number: 42, span: Span { start_line: 42, end_line: 42, start: 1143, end: 1155 } }
+Line { inner: "DTSTAMP:20080205T191224Z", number: 43, span: Span { start_line: 43, end_line: 43, start: 1156, end: 1180 } }
+Line { inner: "DTSTART;VALUE=DATE:20081006", number: 44, span: Span { start_line: 44, end_line: 44, start: 1181, end: 1208 } }
+Line { inner: "SUMMARY:Missing description value, but includes header", number: 45, span: Span { start_line: 45, end_line: 45, start: 1209, end: 1263 } }
+Line { inner: "DESCRIPTION:", number: 46, span: Span { start_line: 46, end_line: 46, start: 1264, end: 1276 } }
+Line { inner: "UID:4088E990AD89CB3DBB484909", number: 47, span: Span { start_line: 47, end_line: 47, start: 1277, end: 1305 } }
+Line { inner: "END:VEVENT", number: 48, span: Span { start_line: 48, end_line: 48, start: 1306, end: 1316 } }
+Line { inner: "END:VCALENDAR", number: 49, span: Span { start_line: 49, end_line: 49, start: 1317, end: 1330 } }
@@ -1,3 +1,3 @@
-IcalCalendar { properties: [Property { name: "CALSCALE", params: None, value: Some("GREGORIAN") }, Property { name: "PRODID", params: None, value: Some("-//Example Inc.//Example Calendar//EN") }, Property { name: "VERSION", params: None, value: Some("2.0") }], events: [IcalEvent { properties: [Property { name: "DTSTAMP", params: None, value: Some("20080205T191224Z") }, Property { name: "DTSTART", params: Some([("VALUE", ["DATE"])]), value: Some("20081006") }, Property { name: "SUMMARY", params: None, value: Some("Planning meeting") }, Property { name: "UID", params: None, value: Some("4088E990AD89CB3DBB484909") }], alarms: [IcalAlarm { properties: [Property { name: "SUMMARY", params: None, value: Some("escaped\\, comma and\\; semicolon\\nnewline") }] }] }], alarms: [], todos: [], journals: [], free_busys: [], timezones: [] }
-IcalCalendar { properties: [Property { name: "ATTENDEE", params: Some([("DELEGATED-TO", ["mailto:foo7@bar", "mailto:foo8@bar"]), ("CN", ["Foo, Bar"])]), value: Some("mailto:foo1@bar") }, Property { name: "ATTENDEE", params: Some([("DELEGATED-TO", ["mailto:foo7@bar", "mailto:foo8@bar"]), ("CN", ["Foo; Bar"])]), value: Some("mailto:foo2@bar") }, Property { name: "ATTENDEE", params: Some([("CN", ["Foo, Bar"])]), value: Some("mailto:foo3@bar") }, Property { name: "ATTENDEE", params: Some([("CN", ["Foo; Bar"])]), value: Some("mailto:foo4@bar") }, Property { name: "ATTENDEE", params: Some([("DELEGATED-TO", ["mailto:foo7@bar"]), ("CN", ["Foo, Bar"])]), value: Some("mailto:foo5@bar") }, Property { name: "ATTENDEE", params: Some([("DELEGATED-TO", ["mailto:foo7@bar"]), ("CN", ["Foo; Bar"])]), value: Some("mailto:foo6@bar") }, Property { name: "ATTENDEE", params: Some([("ROLE", ["REQ-PARTICIPANT;foo"]), ("DELEGATED-FROM", ["mailto:bar@baz.com"]), ("PARTSTAT", ["ACCEPTED"]), ("RSVP", ["TRUE"])]), value: Some("mailto:foo@bar.com") }, Property { name: "X-FOO", params: Some([("PARAM1", ["VAL1"])]), value: Some("FOO;BAR") }, Property { name: "X-FOO2", params: Some([("PARAM1", ["VAL1"]), ("PARAM2", ["VAL2"])]), value: Some("FOO;BAR") }, Property { name: "X-BAR", params: Some([("PARAM1", ["VAL1:FOO"])]), value: Some("BAZ;BAR") }, Property { name: "X-BAZ", params: Some([("PARAM1", ["VAL1:FOO"]), ("PARAM2", ["VAL2"])]), value: Some("BAZ;BAR") }, Property { name: "X-BAZ2", params: Some([("PARAM1", ["VAL1"]), ("PARAM2", ["VAL2:FOO"])]), value: Some("BAZ;BAR") }], events: [], alarms: [], todos: [], journals: [], free_busys: [], timezones: [] }
-IcalCalendar { properties: [Property { name: "CALSCALE", params: None, value: Some("GREGORIAN") }, Property { name: "PRODID", params: None, value: Some("-//Example Inc.//Example Calendar//EN") }, Property { name: "VERSION", params: None, value: Some("2.0") }], events: [IcalEvent { properties: [Property { name: "DTSTAMP", params: None, value: Some("20080205T191224Z") }, Property { name: "DTSTART", params: Some([("VALUE", ["DATE"])]), value: Some("20081006") }, Property { name: "SUMMARY", params: None, value: Some("Missing description value, but includes header") }, Property { name: "DESCRIPTION", params: None, value: None }, Property { name: "UID", params: None, value: Some("4088E990AD89CB3DBB484909") }], alarms: [] }], alarms: [], todos: [], journals: [], free_busys: [], timezones: [] }
+IcalCalendar { properties: [Property { name: "CALSCALE", group: None, params: None, value: Some("GREGORIAN"), span: Some(Span { start_line: 4, end_line: 4, start: 18, end: 36 }) }, Property { name: "PRODID", group: None, params: None, value: Some("-//Example Inc.//Example Calendar//EN"), span: Some(Span { start_line: 5, end_line: 5, start: 37, end: 81 }) }, Property { name: "VERSION", group: None, params: None, value: Some("2.0"), span: Some(Span { start_line: 6, end_line: 6, start: 82, end: 93 }) }], events: [IcalEvent { properties: [Property { name: "DTSTAMP", group: None, params: None, value: Some("20080205T191224Z"), span: Some(Span { start_line: 8, end_line: 8, start: 107, end: 131 }) }, Property { name: "DTSTART", group: None, params: Some([("VALUE", ["DATE"])]), value: Some("20081006"), span: Some(Span { start_line: 9, end_line: 9, start: 132, end: 159 }) }, Property { name: "SUMMARY", group: None, params: None, value: Some("Planning meeting"), span: Some(Span { start_line: 10, end_line: 10, start: 160, end: 184 }) }, Property { name: "UID", group: None, params: None, value: Some("4088E990AD89CB3DBB484909"), span: Some(Span { start_line: 11, end_line: 11, start: 185, end: 213 }) }], alarms: [IcalAlarm { properties: [Property { name: "SUMMARY", group: None, params: None, value: Some("escaped\\, comma and\\; semicolon\\nnewline"), span: Some(Span { start_line: 13, end_line: 13, start: 227, end: 275 }) }], unknown_components: [], span: Some(Span { start_line: 12, end_line: 14, start: 214, end: 286 }) }], unknown_components: [], span: Some(Span { start_line: 7, end_line: 15, start: 94, end: 297 }) }], alarms: [], todos: [], journals: [], free_busys: [], timezones: [], availabilities: [], unknown_components: [], span: Some(Span { start_line: 3, end_line: 16, start: 2, end: 311 }) }
+IcalCalendar { properties: [Property { name: "ATTENDEE", group: None, params: Some([("DELEGATED-TO", ["mailto:foo7@bar", "mailto:foo8@bar"]), ("CN", ["Foo, Bar"])]), value: Some("mailto:foo1@bar"), span: Some(Span { start_line: 20, end_line: 21, start: 330, end: 419 }) }, Property { name: "ATTENDEE", group: None, params: Some([("DELEGATED-TO", ["mailto:foo7@bar", "mailto:foo8@bar"]), ("CN", ["Foo; Bar"])]), value: Some("mailto:foo2@bar"), span: Some(Span { start_line: 22, end_line: 23, start: 420, end: 509 }) }, Property { name: "ATTENDEE", group: None, params: Some([("CN", ["Foo, Bar"])]), value: Some("mailto:foo3@bar"), span: Some(Span { start_line: 24, end_line: 24, start: 510, end: 548 }) }, Property { name: "ATTENDEE", group: None, params: Some([("CN", ["Foo; Bar"])]), value: Some("mailto:foo4@bar"), span: Some(Span { start_line: 25, end_line: 25, start: 549, end: 587 }) }, Property { name: "ATTENDEE", group: None, params: Some([("DELEGATED-TO", ["mailto:foo7@bar"]), ("CN", ["Foo, Bar"])]), value: Some("mailto:foo5@bar"), span: Some(Span { start_line: 26, end_line: 26, start: 588, end: 657 }) }, Property { name: "ATTENDEE", group: None, params: Some([("DELEGATED-TO", ["mailto:foo7@bar"]), ("CN", ["Foo; Bar"])]), value: Some("mailto:foo6@bar"), span: Some(Span { start_line: 27, end_line: 27, start: 658, end: 727 }) }, Property { name: "ATTENDEE", group: None, params: Some([("ROLE", ["REQ-PARTICIPANT;foo"]), ("DELEGATED-FROM", ["mailto:bar@baz.com"]), ("PARTSTAT", ["ACCEPTED"]), ("RSVP", ["TRUE"])]), value: Some("mailto:foo@bar.com"), span: Some(Span { start_line: 28, end_line: 29, start: 728, end: 848 }) }, Property { name: "X-FOO", group: None, params: Some([("PARAM1", ["VAL1"])]), value: Some("FOO;BAR"), span: Some(Span { start_line: 30, end_line: 30, start: 849, end: 874 }) }, Property { name: "X-FOO2", group: None, params: Some([("PARAM1", ["VAL1"]), ("PARAM2", ["VAL2"])]), value: Some("FOO;BAR"), span: Some(Span { start_line: 31, end_line: 31, start: 875, end: 913 }) }, Property { name: "X-BAR", group: None, params: Some([("PARAM1", ["VAL1:FOO"])]), value: Some("BAZ;BAR"), span: Some(Span { start_line: 32, end_line: 32, start: 914, end: 945 }) }, Property { name: "X-BAZ", group: None, params: Some([("PARAM1", ["VAL1:FOO"]), ("PARAM2", ["VAL2"])]), value: Some("BAZ;BAR"), span: Some(Span { start_line: 33, end_line: 33, start: 946, end: 989 }) }, Property { name: "X-BAZ2", group: None, params: Some([("PARAM1", ["VAL1"]), ("PARAM2", ["VAL2:FOO"])]), value: Some("BAZ;BAR"), span: Some(Span { start_line: 34, end_line: 34, start: 990, end: 1034 }) }], events: [], alarms: [], todos: [], journals: [], free_busys: [], timezones: [], availabilities: [], unknown_components: [], span: Some(Span { start_line: 19, end_line: 35, start: 314, end: 1048 }) }
+IcalCalendar { properties: [Property { name: "CALSCALE", group: None, params: None, value: Some("GREGORIAN"), span: Some(Span { start_line: 39, end_line: 39, start: 1067, end: 1085 }) }, Property { name: "PRODID", group: None, params: None, value: Some("-//Example Inc.//Example Calendar//EN"), span: Some(Span { start_line: 40, end_line: 40, start: 1086, end: 1130 }) }, Property { name: "VERSION", group: None, params: None, value: Some("2.0"), span: Some(Span { start_line: 41, end_line: 41, start: 1131, end: 1142 }) }], events: [IcalEvent { properties: [Property { name: "DTSTAMP", group: None, params: None, value: Some("20080205T191224Z"), span: Some(Span { start_line: 43, end_line: 43, start: 1156, end: 1180 }) }, Property { name: "DTSTART", group: None, params: Some([("VALUE", ["DATE"])]), value: Some("20081006"), span: Some(Span { start_line: 44, end_line: 44, start: 1181, end: 1208 }) }, Property { name: "SUMMARY", group: None, params: None, value: Some("Missing description value, but includes header"), span: Some(Span { start_line: 45, end_line: 45, start: 1209, end: 1263 }) }, Property { name: "DESCRIPTION", group: None, params: None, value: None, span: Some(Span { start_line: 46, end_line: 46, start: 1264, end: 1276 }) }, Property { name: "UID", group: None, params: None, value: Some("4088E990AD89CB3DBB484909"), span: Some(Span { start_line: 47, end_line: 47, start: 1277, end: 1305 }) }], alarms: [], unknown_components: [], span: Some(Span { start_line: 42, end_line: 48, start: 1143, end: 1316 }) }], alarms: [], todos: [], journals: [], free_busys: [], timezones: [], availabilities: [], unknown_components: [], span: Some(Span { start_line: 38, end_line: 49, start: 1051, end: 1330 }) }
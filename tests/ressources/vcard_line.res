@@ -1,35 +1,35 @@
-Line { inner: "BEGIN:VCARD", number: 3 }
-Line { inner: "VERSION:4.0", number: 4 }
-Line { inner: "ADR;TYPE=work:pobox;apt;street;city;state;zipcode;country", number: 5 }
-Line { inner: "ANNIVERSARY:19960415", number: 6 }
-Line { inner: "BDAY:--0203", number: 7 }
-Line { inner: "CALADRURI:http://example.com/calendar/jdoe", number: 8 }
-Line { inner: "CALURI;MEDIATYPE=text/calendar:ftp://ftp.example.com/calA.ics", number: 9 }
-Line { inner: "CLIENTPIDMAP:1;urn:uuid:3df403f4-5924-4bb7-b077-3c711d9eb34b", number: 10 }
-Line { inner: "EMAIL;TYPE=work:jqpublic@xyz.example.com", number: 11 }
-Line { inner: "FBURL;MEDIATYPE=text/calendar:ftp://example.com/busy/project-a.ifb", number: 12 }
-Line { inner: "FN:J. Doe", number: 14 }
-Line { inner: "GENDER:M;Fellow", number: 15 }
-Line { inner: "GEO:geo:37.386013\\,-122.082932", number: 16 }
-Line { inner: "IMPP;PREF=1:xmpp:alice@example.com", number: 19 }
-Line { inner: "KEY:http://www.example.com/keys/jdoe.cer", number: 20 }
-Line { inner: "KIND:individual", number: 21 }
-Line { inner: "LANG;PREF=1:fr", number: 22 }
-Line { inner: "LOGO:http://www.example.com/pub/logos/abccorp.jpg", number: 23 }
-Line { inner: "MEMBER:urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af", number: 27 }
-Line { inner: "N:Stevenson;John;Philip,Paul;Dr.;Jr.,M.D.,A.C.P.", number: 28 }
-Line { inner: "NICKNAME;TYPE=work:Boss", number: 29 }
-Line { inner: "NOTE:This fax number is operational 0800 to 1715 EST\\, Mon-Fri", number: 30 }
-Line { inner: "ORG:ABC\\, Inc.;North American Division;Marketing", number: 31 }
-Line { inner: "PHOTO:http://www.example.com/pub/photos/jqpublic.gif", number: 32 }
-Line { inner: "RELATED;TYPE=friend:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6", number: 33 }
-Line { inner: "REV:19951031T222710Z", number: 34 }
-Line { inner: "ROLE:Project Leader", number: 35 }
-Line { inner: "SOUND:CID:JOHNQPUBLIC.part8.19960229T080000.xyzMail@example.com", number: 36 }
-Line { inner: "SOURCE:ldap://ldap.example.com/cn=Babs%20Jensen\\,%20o=Babsco\\,%20c=US", number: 37 }
-Line { inner: "TEL;VALUE=uri;TYPE=home:tel:+33-01-23-45-67", number: 38 }
-Line { inner: "TITLE:Research Scientist", number: 39 }
-Line { inner: "TZ;VALUE=utc-offset:-0500", number: 40 }
-Line { inner: "XML:<html xmlns=\"http://www.w3.org/1999/xhtml\"></html>", number: 41 }
-Line { inner: "END:VCARD", number: 42 }
+Line { inner: "BEGIN:VCARD", number: 3, span: Span { start_line: 3, end_line: 3, start: 2, end: 13 } }
+Line { inner: "VERSION:4.0", number: 4, span: Span { start_line: 4, end_line: 4, start: 14, end: 25 } }
+Line { inner: "ADR;TYPE=work:pobox;apt;street;city;state;zipcode;country", number: 5, span: Span { start_line: 5, end_line: 5, start: 26, end: 83 } }
+Line { inner: "ANNIVERSARY:19960415", number: 6, span: Span { start_line: 6, end_line: 6, start: 84, end: 104 } }
+Line { inner: "BDAY:--0203", number: 7, span: Span { start_line: 7, end_line: 7, start: 105, end: 116 } }
+Line { inner: "CALADRURI:http://example.com/calendar/jdoe", number: 8, span: Span { start_line: 8, end_line: 8, start: 117, end: 159 } }
+Line { inner: "CALURI;MEDIATYPE=text/calendar:ftp://ftp.example.com/calA.ics", number: 9, span: Span { start_line: 9, end_line: 9, start: 160, end: 221 } }
+Line { inner: "CLIENTPIDMAP:1;urn:uuid:3df403f4-5924-4bb7-b077-3c711d9eb34b", number: 10, span: Span { start_line: 10, end_line: 10, start: 222, end: 282 } }
+Line { inner: "EMAIL;TYPE=work:jqpublic@xyz.example.com", number: 11, span: Span { start_line: 11, end_line: 11, start: 283, end: 323 } }
+Line { inner: "FBURL;MEDIATYPE=text/calendar:ftp://example.com/busy/project-a.ifb", number: 12, span: Span { start_line: 12, end_line: 13, start: 324, end: 392 } }
+Line { inner: "FN:J. Doe", number: 14, span: Span { start_line: 14, end_line: 14, start: 393, end: 402 } }
+Line { inner: "GENDER:M;Fellow", number: 15, span: Span { start_line: 15, end_line: 15, start: 403, end: 418 } }
+Line { inner: "GEO:geo:37.386013\\,-122.082932", number: 16, span: Span { start_line: 16, end_line: 18, start: 419, end: 452 } }
+Line { inner: "IMPP;PREF=1:xmpp:alice@example.com", number: 19, span: Span { start_line: 19, end_line: 19, start: 453, end: 487 } }
+Line { inner: "KEY:http://www.example.com/keys/jdoe.cer", number: 20, span: Span { start_line: 20, end_line: 20, start: 488, end: 528 } }
+Line { inner: "KIND:individual", number: 21, span: Span { start_line: 21, end_line: 21, start: 529, end: 544 } }
+Line { inner: "LANG;PREF=1:fr", number: 22, span: Span { start_line: 22, end_line: 22, start: 545, end: 559 } }
+Line { inner: "LOGO:http://www.example.com/pub/logos/abccorp.jpg", number: 23, span: Span { start_line: 23, end_line: 23, start: 560, end: 609 } }
+Line { inner: "MEMBER:urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af", number: 27, span: Span { start_line: 27, end_line: 27, start: 613, end: 665 } }
+Line { inner: "N:Stevenson;John;Philip,Paul;Dr.;Jr.,M.D.,A.C.P.", number: 28, span: Span { start_line: 28, end_line: 28, start: 666, end: 714 } }
+Line { inner: "NICKNAME;TYPE=work:Boss", number: 29, span: Span { start_line: 29, end_line: 29, start: 715, end: 738 } }
+Line { inner: "NOTE:This fax number is operational 0800 to 1715 EST\\, Mon-Fri", number: 30, span: Span { start_line: 30, end_line: 30, start: 739, end: 801 } }
+Line { inner: "ORG:ABC\\, Inc.;North American Division;Marketing", number: 31, span: Span { start_line: 31, end_line: 31, start: 802, end: 850 } }
+Line { inner: "PHOTO:http://www.example.com/pub/photos/jqpublic.gif", number: 32, span: Span { start_line: 32, end_line: 32, start: 851, end: 903 } }
+Line { inner: "RELATED;TYPE=friend:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6", number: 33, span: Span { start_line: 33, end_line: 33, start: 904, end: 969 } }
+Line { inner: "REV:19951031T222710Z", number: 34, span: Span { start_line: 34, end_line: 34, start: 970, end: 990 } }
+Line { inner: "ROLE:Project Leader", number: 35, span: Span { start_line: 35, end_line: 35, start: 991, end: 1010 } }
+Line { inner: "SOUND:CID:JOHNQPUBLIC.part8.19960229T080000.xyzMail@example.com", number: 36, span: Span { start_line: 36, end_line: 36, start: 1011, end: 1074 } }
+Line { inner: "SOURCE:ldap://ldap.example.com/cn=Babs%20Jensen\\,%20o=Babsco\\,%20c=US", number: 37, span: Span { start_line: 37, end_line: 37, start: 1075, end: 1144 } }
+Line { inner: "TEL;VALUE=uri;TYPE=home:tel:+33-01-23-45-67", number: 38, span: Span { start_line: 38, end_line: 38, start: 1145, end: 1188 } }
+Line { inner: "TITLE:Research Scientist", number: 39, span: Span { start_line: 39, end_line: 39, start: 1189, end: 1213 } }
+Line { inner: "TZ;VALUE=utc-offset:-0500", number: 40, span: Span { start_line: 40, end_line: 40, start: 1214, end: 1239 } }
+Line { inner: "XML:<html xmlns=\"http://www.w3.org/1999/xhtml\"></html>", number: 41, span: Span { start_line: 41, end_line: 41, start: 1240, end: 1294 } }
+Line { inner: "END:VCARD", number: 42, span: Span { start_line: 42, end_line: 42, start: 1295, end: 1304 } }
 
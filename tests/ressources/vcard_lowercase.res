@@ -1 +1 @@
-VcardContact { properties: [Property { name: "version", params: None, value: Some("4.0") }, Property { name: "fn", params: None, value: Some("Alice Foobar") }, Property { name: "n", params: None, value: Some("Foobar;Alice") }, Property { name: "email", params: Some([("TYPE", ["internet"])]), value: Some("alice@example.org") }] }
+VcardContact { properties: [Property { name: "version", group: None, params: None, value: Some("4.0"), span: Some(Span { start_line: 2, end_line: 2, start: 12, end: 23 }) }, Property { name: "fn", group: None, params: None, value: Some("Alice Foobar"), span: Some(Span { start_line: 3, end_line: 3, start: 24, end: 39 }) }, Property { name: "n", group: None, params: None, value: Some("Foobar;Alice"), span: Some(Span { start_line: 4, end_line: 4, start: 40, end: 54 }) }, Property { name: "email", group: None, params: Some([("TYPE", ["internet"])]), value: Some("alice@example.org"), span: Some(Span { start_line: 5, end_line: 5, start: 55, end: 92 }) }], unknown_components: [], span: Some(Span { start_line: 1, end_line: 6, start: 0, end: 102 }) }
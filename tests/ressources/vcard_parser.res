@@ -1 +1 @@
-VcardContact { properties: [Property { name: "VERSION", params: None, value: Some("4.0") }, Property { name: "ADR", params: Some([("TYPE", ["work"])]), value: Some("pobox;apt;street;city;state;zipcode;country") }, Property { name: "ANNIVERSARY", params: None, value: Some("19960415") }, Property { name: "BDAY", params: None, value: Some("--0203") }, Property { name: "CALADRURI", params: None, value: Some("http://example.com/calendar/jdoe") }, Property { name: "CALURI", params: Some([("MEDIATYPE", ["text/calendar"])]), value: Some("ftp://ftp.example.com/calA.ics") }, Property { name: "CLIENTPIDMAP", params: None, value: Some("1;urn:uuid:3df403f4-5924-4bb7-b077-3c711d9eb34b") }, Property { name: "EMAIL", params: Some([("TYPE", ["work"])]), value: Some("jqpublic@xyz.example.com") }, Property { name: "FBURL", params: Some([("MEDIATYPE", ["text/calendar"])]), value: Some("ftp://example.com/busy/project-a.ifb") }, Property { name: "FN", params: None, value: Some("J. Doe") }, Property { name: "GENDER", params: None, value: Some("M;Fellow") }, Property { name: "GEO", params: None, value: Some("geo:37.386013\\,-122.082932") }, Property { name: "IMPP", params: Some([("PREF", ["1"])]), value: Some("xmpp:alice@example.com") }, Property { name: "KEY", params: None, value: Some("http://www.example.com/keys/jdoe.cer") }, Property { name: "KIND", params: None, value: Some("individual") }, Property { name: "LANG", params: Some([("PREF", ["1"])]), value: Some("fr") }, Property { name: "LOGO", params: None, value: Some("http://www.example.com/pub/logos/abccorp.jpg") }, Property { name: "MEMBER", params: None, value: Some("urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af") }, Property { name: "N", params: None, value: Some("Stevenson;John;Philip,Paul;Dr.;Jr.,M.D.,A.C.P.") }, Property { name: "NICKNAME", params: Some([("TYPE", ["work"])]), value: Some("Boss") }, Property { name: "NOTE", params: None, value: Some("This fax number is operational 0800 to 1715 EST\\, Mon-Fri") }, Property { name: "ORG", params: None, value: Some("ABC\\, Inc.;North American Division;Marketing") }, Property { name: "PHOTO", params: None, value: Some("http://www.example.com/pub/photos/jqpublic.gif") }, Property { name: "RELATED", params: Some([("TYPE", ["friend"])]), value: Some("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6") }, Property { name: "REV", params: None, value: Some("19951031T222710Z") }, Property { name: "ROLE", params: None, value: Some("Project Leader") }, Property { name: "SOUND", params: None, value: Some("CID:JOHNQPUBLIC.part8.19960229T080000.xyzMail@example.com") }, Property { name: "SOURCE", params: None, value: Some("ldap://ldap.example.com/cn=Babs%20Jensen\\,%20o=Babsco\\,%20c=US") }, Property { name: "TEL", params: Some([("VALUE", ["uri"]), ("TYPE", ["home"])]), value: Some("tel:+33-01-23-45-67") }, Property { name: "TITLE", params: None, value: Some("Research Scientist") }, Property { name: "TZ", params: Some([("VALUE", ["utc-offset"])]), value: Some("-0500") }, Property { name: "XML", params: None, value: Some("<html xmlns=\"http://www.w3.org/1999/xhtml\"></html>") }] }
+VcardContact { properties: [Property { name: "VERSION", group: None, params: None, value: Some("4.0"), span: Some(Span { start_line: 4, end_line: 4, start: 14, end: 25 }) }, Property { name: "ADR", group: None, params: Some([("TYPE", ["work"])]), value: Some("pobox;apt;street;city;state;zipcode;country"), span: Some(Span { start_line: 5, end_line: 5, start: 26, end: 83 }) }, Property { name: "ANNIVERSARY", group: None, params: None, value: Some("19960415"), span: Some(Span { start_line: 6, end_line: 6, start: 84, end: 104 }) }, Property { name: "BDAY", group: None, params: None, value: Some("--0203"), span: Some(Span { start_line: 7, end_line: 7, start: 105, end: 116 }) }, Property { name: "CALADRURI", group: None, params: None, value: Some("http://example.com/calendar/jdoe"), span: Some(Span { start_line: 8, end_line: 8, start: 117, end: 159 }) }, Property { name: "CALURI", group: None, params: Some([("MEDIATYPE", ["text/calendar"])]), value: Some("ftp://ftp.example.com/calA.ics"), span: Some(Span { start_line: 9, end_line: 9, start: 160, end: 221 }) }, Property { name: "CLIENTPIDMAP", group: None, params: None, value: Some("1;urn:uuid:3df403f4-5924-4bb7-b077-3c711d9eb34b"), span: Some(Span { start_line: 10, end_line: 10, start: 222, end: 282 }) }, Property { name: "EMAIL", group: None, params: Some([("TYPE", ["work"])]), value: Some("jqpublic@xyz.example.com"), span: Some(Span { start_line: 11, end_line: 11, start: 283, end: 323 }) }, Property { name: "FBURL", group: None, params: Some([("MEDIATYPE", ["text/calendar"])]), value: Some("ftp://example.com/busy/project-a.ifb"), span: Some(Span { start_line: 12, end_line: 13, start: 324, end: 392 }) }, Property { name: "FN", group: None, params: None, value: Some("J. Doe"), span: Some(Span { start_line: 14, end_line: 14, start: 393, end: 402 }) }, Property { name: "GENDER", group: None, params: None, value: Some("M;Fellow"), span: Some(Span { start_line: 15, end_line: 15, start: 403, end: 418 }) }, Property { name: "GEO", group: None, params: None, value: Some("geo:37.386013\\,-122.082932"), span: Some(Span { start_line: 16, end_line: 18, start: 419, end: 452 }) }, Property { name: "IMPP", group: None, params: Some([("PREF", ["1"])]), value: Some("xmpp:alice@example.com"), span: Some(Span { start_line: 19, end_line: 19, start: 453, end: 487 }) }, Property { name: "KEY", group: None, params: None, value: Some("http://www.example.com/keys/jdoe.cer"), span: Some(Span { start_line: 20, end_line: 20, start: 488, end: 528 }) }, Property { name: "KIND", group: None, params: None, value: Some("individual"), span: Some(Span { start_line: 21, end_line: 21, start: 529, end: 544 }) }, Property { name: "LANG", group: None, params: Some([("PREF", ["1"])]), value: Some("fr"), span: Some(Span { start_line: 22, end_line: 22, start: 545, end: 559 }) }, Property { name: "LOGO", group: None, params: None, value: Some("http://www.example.com/pub/logos/abccorp.jpg"), span: Some(Span { start_line: 23, end_line: 23, start: 560, end: 609 }) }, Property { name: "MEMBER", group: None, params: None, value: Some("urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af"), span: Some(Span { start_line: 27, end_line: 27, start: 613, end: 665 }) }, Property { name: "N", group: None, params: None, value: Some("Stevenson;John;Philip,Paul;Dr.;Jr.,M.D.,A.C.P."), span: Some(Span { start_line: 28, end_line: 28, start: 666, end: 714 }) }, Property { name: "NICKNAME", group: None, params: Some([("TYPE", ["work"])]), value: Some("Boss"), span: Some(Span { start_line: 29, end_line: 29, start: 715, end: 738 }) }, Property { name: "NOTE", group: None, params: None, value: Some("This fax number is operational 0800 to 1715 EST\\, Mon-Fri"), span: Some(Span { start_line: 30, end_line: 30, start: 739, end: 801 }) }, Property { name: "ORG", group: None, params: None, value: Some("ABC\\, Inc.;North American Division;Marketing"), span: Some(Span { start_line: 31, end_line: 31, start: 802, end: 850 }) }, Property { name: "PHOTO", group: None, params: None, value: Some("http://www.example.com/pub/photos/jqpublic.gif"), span: Some(Span { start_line: 32, end_line: 32, start: 851, end: 903 }) }, Property { name: "RELATED", group: None, params: Some([("TYPE", ["friend"])]), value: Some("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6"), span: Some(Span { start_line: 33, end_line: 33, start: 904, end: 969 }) }, Property { name: "REV", group: None, params: None, value: Some("19951031T222710Z"), span: Some(Span { start_line: 34, end_line: 34, start: 970, end: 990 }) }, Property { name: "ROLE", group: None, params: None, value: Some("Project Leader"), span: Some(Span { start_line: 35, end_line: 35, start: 991, end: 1010 }) }, Property { name: "SOUND", group: None, params: None, value: Some("CID:JOHNQPUBLIC.part8.19960229T080000.xyzMail@example.com"), span: Some(Span { start_line: 36, end_line: 36, start: 1011, end: 1074 }) }, Property { name: "SOURCE", group: None, params: None, value: Some("ldap://ldap.example.com/cn=Babs%20Jensen\\,%20o=Babsco\\,%20c=US"), span: Some(Span { start_line: 37, end_line: 37, start: 1075, end: 1144 }) }, Property { name: "TEL", group: None, params: Some([("VALUE", ["uri"]), ("TYPE", ["home"])]), value: Some("tel:+33-01-23-45-67"), span: Some(Span { start_line: 38, end_line: 38, start: 1145, end: 1188 }) }, Property { name: "TITLE", group: None, params: None, value: Some("Research Scientist"), span: Some(Span { start_line: 39, end_line: 39, start: 1189, end: 1213 }) }, Property { name: "TZ", group: None, params: Some([("VALUE", ["utc-offset"])]), value: Some("-0500"), span: Some(Span { start_line: 40, end_line: 40, start: 1214, end: 1239 }) }, Property { name: "XML", group: None, params: None, value: Some("<html xmlns=\"http://www.w3.org/1999/xhtml\"></html>"), span: Some(Span { start_line: 41, end_line: 41, start: 1240, end: 1294 }) }], unknown_components: [], span: Some(Span { start_line: 3, end_line: 42, start: 2, end: 1304 }) }